@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use thiserror::Error;
 use unstructured_client::error::ClientError;
 
@@ -8,4 +10,46 @@ pub enum CliError {
 
     #[error("JSON error: {0}")]
     JSONError(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    InvalidArguments(#[from] clap::Error),
+
+    #[error("Partitioning failed: {0}")]
+    PartitionFailed(String),
+
+    #[error("{file}: {source}")]
+    FileFailed {
+        file: String,
+        #[source]
+        source: Box<CliError>,
+    },
+
+    #[error("{failed} of {total} files failed")]
+    FilesFailed { failed: usize, total: usize },
+
+    #[error("Output file already exists: {}, pass --force to overwrite it", .0.display())]
+    OutputExists(PathBuf),
+
+    #[error("Failed to write {}: {1}", .0.display())]
+    OutputFailed(PathBuf, #[source] std::io::Error),
+}
+
+impl CliError {
+    /// The process exit code for this error, so that scripts can tell errors apart without
+    /// parsing stderr: 1 for client errors, 2 for JSON errors, 3 for invalid arguments and 4
+    /// when the output file can't be written.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::ClientError(
+                ClientError::ArgumentParseFailed(_) | ClientError::InvalidParameters(_),
+            )
+            | CliError::InvalidArguments(_) => 3,
+            CliError::ClientError(_)
+            | CliError::PartitionFailed(_)
+            | CliError::FilesFailed { .. } => 1,
+            CliError::JSONError(_) => 2,
+            CliError::FileFailed { source, .. } => source.exit_code(),
+            CliError::OutputExists(_) | CliError::OutputFailed(..) => 4,
+        }
+    }
 }
@@ -0,0 +1,360 @@
+use std::io::{IsTerminal, Read};
+use std::path::{Component, Path, PathBuf};
+
+use unstructured_client::error::ClientError;
+use unstructured_client::partition::PartitionResponse;
+use unstructured_client::{PartitionParameters, UnstructuredClient};
+
+use crate::error::CliError;
+
+/// Characters that make an input a glob pattern.
+const GLOB_CHARACTERS: &[char] = &['*', '?', '['];
+
+/// How glob patterns among the inputs are expanded.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct GlobOptions {
+    /// Also match files and directories whose name starts with a dot.
+    pub hidden: bool,
+
+    /// Allow patterns that match no files, instead of failing.
+    pub allow_empty: bool,
+}
+
+/// Where a document to partition is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin { file_name: String },
+}
+
+impl Input {
+    /// The inputs named by `paths`, in order, where `-` stands for stdin and paths with
+    /// wildcards are expanded to the files they match, in sorted order. Files named more than
+    /// once, e.g. both literally and by a pattern or as `./a.pdf` and `a.pdf`, are only kept at
+    /// their first occurrence. Inputs with the same [name](Self::name) that are different
+    /// documents are rejected. Without paths, the document is read from stdin unless it is a
+    /// terminal.
+    ///
+    /// Reading from stdin requires `file_name`, from which the server detects the filetype.
+    pub fn resolve(
+        paths: &[PathBuf],
+        file_name: Option<&str>,
+        glob: GlobOptions,
+    ) -> Result<Vec<Input>, String> {
+        Self::resolve_with(paths, file_name, glob, !std::io::stdin().is_terminal())
+    }
+
+    fn resolve_with(
+        paths: &[PathBuf],
+        file_name: Option<&str>,
+        glob: GlobOptions,
+        stdin_is_piped: bool,
+    ) -> Result<Vec<Input>, String> {
+        let stdin = || match file_name {
+            Some(file_name) => Ok(Input::Stdin {
+                file_name: file_name.to_string(),
+            }),
+            None => Err("--file-name is required when reading the document from stdin".to_string()),
+        };
+
+        if paths.is_empty() {
+            return match stdin_is_piped {
+                true => Ok(vec![stdin()?]),
+                false => Err("A file path is required when stdin is a terminal".to_string()),
+            };
+        }
+        if paths.iter().filter(|path| is_stdin(path)).count() > 1 {
+            return Err("stdin (`-`) can only be read once".to_string());
+        }
+
+        let mut inputs = Vec::new();
+        for path in paths {
+            match path.to_str() {
+                _ if is_stdin(path) => inputs.push(stdin()?),
+                Some(pattern) if pattern.contains(GLOB_CHARACTERS) => {
+                    inputs.extend(expand(pattern, glob)?.into_iter().map(Input::File))
+                }
+                _ => inputs.push(Input::File(path.clone())),
+            }
+        }
+
+        // The results are keyed by input name, so each file is only partitioned once, and
+        // different documents must have different names.
+        let mut unique: Vec<Input> = Vec::with_capacity(inputs.len());
+        let mut files = Vec::new();
+        for input in inputs {
+            if let Input::File(path) = &input {
+                let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+                if files.contains(&canonical) {
+                    continue;
+                }
+                files.push(canonical);
+            }
+            let name = input.name();
+            if unique.iter().any(|other| other.name() == name) {
+                return Err(format!(
+                    "Several inputs are named '{name}', so their results can't be told apart"
+                ));
+            }
+            unique.push(input);
+        }
+        Ok(unique)
+    }
+
+    /// The name of the input in results and progress messages: the path of a file, or the
+    /// `--file-name` of stdin.
+    pub fn name(&self) -> String {
+        match self {
+            Input::File(path) => path.display().to_string(),
+            Input::Stdin { file_name } => file_name.clone(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        match self {
+            Input::File(path) => path,
+            Input::Stdin { file_name } => Path::new(file_name),
+        }
+    }
+
+    pub async fn partition(
+        &self,
+        client: &UnstructuredClient,
+        params: PartitionParameters,
+    ) -> Result<PartitionResponse, CliError> {
+        match self {
+            Input::File(path) => Ok(client.partition_file(path, params).await?),
+            Input::Stdin { file_name } => {
+                let mut content = Vec::new();
+                std::io::stdin()
+                    .lock()
+                    .read_to_end(&mut content)
+                    .map_err(ClientError::from)?;
+                Ok(client.partition_bytes(file_name, content, params).await?)
+            }
+        }
+    }
+}
+
+fn is_stdin(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// The files matched by the glob `pattern`, sorted. Directories are left out.
+fn expand(pattern: &str, options: GlobOptions) -> Result<Vec<PathBuf>, String> {
+    let invalid = |e: &dyn std::fmt::Display| format!("Invalid glob pattern '{pattern}': {e}");
+    let matches = glob::glob(pattern).map_err(|e| invalid(&e))?;
+
+    // The directory the pattern starts from, in which hidden names are allowed.
+    let base: PathBuf = Path::new(pattern)
+        .components()
+        .take_while(|component| {
+            !component
+                .as_os_str()
+                .to_string_lossy()
+                .contains(GLOB_CHARACTERS)
+        })
+        .collect();
+
+    let mut files = Vec::new();
+    for path in matches {
+        let path = path.map_err(|e| invalid(&e))?;
+        if path.is_file() && (options.hidden || !is_hidden(&path, &base)) {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    if files.is_empty() && !options.allow_empty {
+        return Err(format!(
+            "Pattern '{pattern}' matched no files, pass --allow-empty-glob to allow this"
+        ));
+    }
+    Ok(files)
+}
+
+/// Whether a file or directory below `base` on `path` has a name starting with a dot.
+fn is_hidden(path: &Path, base: &Path) -> bool {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .components()
+        .any(|component| match component {
+            Component::Normal(name) => name.to_string_lossy().starts_with('.'),
+            _ => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(paths: &[&str]) -> Vec<PathBuf> {
+        paths.iter().map(PathBuf::from).collect()
+    }
+
+    #[test]
+    fn test_resolve() {
+        let file = |path: &str| Input::File(PathBuf::from(path));
+        let stdin = Input::Stdin {
+            file_name: "c.pdf".to_string(),
+        };
+
+        let glob = GlobOptions::default();
+        assert_eq!(
+            Input::resolve_with(&paths(&["a.pdf", "b.pdf"]), None, glob, false),
+            Ok(vec![file("a.pdf"), file("b.pdf")])
+        );
+        assert_eq!(
+            Input::resolve_with(&paths(&["a.pdf", "-"]), Some("c.pdf"), glob, false),
+            Ok(vec![file("a.pdf"), stdin.clone()])
+        );
+        assert_eq!(
+            Input::resolve_with(&[], Some("c.pdf"), glob, true),
+            Ok(vec![stdin])
+        );
+
+        assert!(Input::resolve_with(&paths(&["-"]), None, glob, true).is_err());
+        assert!(Input::resolve_with(&paths(&["-", "-"]), Some("c.pdf"), glob, true).is_err());
+        assert!(Input::resolve_with(&[], Some("c.pdf"), glob, false).is_err());
+    }
+
+    /// Creates empty files at `paths` below `dir`.
+    fn create_files(dir: &Path, paths: &[&str]) {
+        for path in paths {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+    }
+
+    /// The files below `dir` matching `pattern`, relative to `dir`.
+    fn matches(dir: &Path, pattern: &str, options: GlobOptions) -> Result<Vec<String>, String> {
+        let files = expand(&format!("{}/{pattern}", dir.display()), options)?;
+        Ok(files
+            .iter()
+            .map(|file| file.strip_prefix(dir).unwrap().display().to_string())
+            .collect())
+    }
+
+    #[test]
+    fn test_expand() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(
+            dir.path(),
+            &[
+                "b.pdf",
+                "a.pdf",
+                "notes.txt",
+                "docs/c.pdf",
+                "docs/deep/d.pdf",
+                "docs/.e.pdf",
+                ".hidden/f.pdf",
+            ],
+        );
+        let options = GlobOptions::default();
+
+        assert_eq!(
+            matches(dir.path(), "*.pdf", options).unwrap(),
+            ["a.pdf", "b.pdf"]
+        );
+        assert_eq!(
+            matches(dir.path(), "**/*.pdf", options).unwrap(),
+            ["a.pdf", "b.pdf", "docs/c.pdf", "docs/deep/d.pdf"]
+        );
+        assert_eq!(
+            matches(dir.path(), "docs/?.pdf", options).unwrap(),
+            ["docs/c.pdf"]
+        );
+        // Directories aren't inputs.
+        let allow_empty = GlobOptions {
+            allow_empty: true,
+            ..options
+        };
+        assert!(matches(dir.path(), "doc*", allow_empty).unwrap().is_empty());
+
+        let hidden = GlobOptions {
+            hidden: true,
+            ..options
+        };
+        assert_eq!(
+            matches(dir.path(), "**/*.pdf", hidden).unwrap(),
+            [
+                ".hidden/f.pdf",
+                "a.pdf",
+                "b.pdf",
+                "docs/.e.pdf",
+                "docs/c.pdf",
+                "docs/deep/d.pdf"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_no_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(dir.path(), &["a.pdf"]);
+
+        let error = matches(dir.path(), "*.docx", GlobOptions::default()).unwrap_err();
+        assert!(error.contains("matched no files"), "{error}");
+        let allow_empty = GlobOptions {
+            allow_empty: true,
+            ..GlobOptions::default()
+        };
+        assert!(matches(dir.path(), "*.docx", allow_empty)
+            .unwrap()
+            .is_empty());
+        assert!(matches(dir.path(), "[", allow_empty)
+            .unwrap_err()
+            .starts_with("Invalid glob pattern"));
+    }
+
+    #[test]
+    fn test_resolve_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(dir.path(), &["b.pdf", "a.pdf"]);
+        let literal = dir.path().join("b.pdf");
+        let pattern = dir.path().join("*.pdf");
+
+        let inputs = Input::resolve_with(
+            &[literal.clone(), pattern],
+            None,
+            GlobOptions::default(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            inputs,
+            vec![Input::File(literal), Input::File(dir.path().join("a.pdf"))]
+        );
+    }
+
+    #[test]
+    fn test_resolve_same_file_twice() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(dir.path(), &["a.pdf"]);
+        let path = dir.path().join("a.pdf");
+        let dotted = dir.path().join(".").join("a.pdf");
+
+        let inputs =
+            Input::resolve_with(&[path.clone(), dotted], None, GlobOptions::default(), false)
+                .unwrap();
+        assert_eq!(inputs, vec![Input::File(path)]);
+    }
+
+    #[test]
+    fn test_resolve_name_collision() {
+        let dir = tempfile::tempdir().unwrap();
+        create_files(dir.path(), &["a.pdf"]);
+        let path = dir.path().join("a.pdf");
+        let name = path.display().to_string();
+
+        let error = Input::resolve_with(
+            &[PathBuf::from("-"), path],
+            Some(&name),
+            GlobOptions::default(),
+            true,
+        )
+        .unwrap_err();
+        assert!(error.contains(&format!("named '{name}'")), "{error}");
+    }
+}
@@ -1,51 +1,249 @@
-mod args;
 mod error;
+mod input;
+mod output;
+mod progress;
+mod results;
 
-use clap::Parser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, FromArgMatches, Parser};
+use indicatif::ProgressBar;
 use reqwest::Url;
 use serde_json::to_string;
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use tokio::task::JoinSet;
 
-use crate::args::CliPartitionParameters;
 use crate::error::CliError;
+use crate::input::{GlobOptions, Input};
+use crate::output::OutputOptions;
+use crate::results::{FileResult, ResultFormat};
 use unstructured_client::partition::PartitionResponse;
-use unstructured_client::{PartitionParameters, UnstructuredClient};
+use unstructured_client::{ElementList, PartitionParameters, UnstructuredClient};
 
 #[derive(Debug, Parser)]
 pub struct AppArgs {
-    /// Path to the file to be parsed
+    /// Paths of the files to be parsed, or `-` to read one from stdin. Defaults to stdin when
+    /// stdin is not a terminal. Paths with wildcards, e.g. `docs/**/*.pdf`, are expanded to the
+    /// files they match
+    #[clap(value_name = "FILE")]
+    pub files: Vec<PathBuf>,
+    /// Path of a file to be parsed, like FILE. Can be repeated
     #[clap(long)]
-    pub file_path: PathBuf,
+    pub file_path: Vec<PathBuf>,
+    /// Name of the document read from stdin, from which the server detects its filetype, e.g.
+    /// `report.pdf`. Required when reading from stdin
+    #[clap(long)]
+    pub file_name: Option<String>,
     /// The base URL for the Unstructured API
     #[clap(long, default_value = "http://localhost:8000")]
     pub base_url: Url,
-    #[clap(flatten)]
-    partition_parameters: CliPartitionParameters,
+    /// Disable the progress bar. It is also hidden when stdout is not a terminal
+    #[clap(long)]
+    pub no_progress: bool,
+    /// Write the elements to this file instead of stdout. An existing file is only replaced
+    /// with --force
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+    /// Overwrite the --output file if it exists
+    #[clap(long, requires = "output")]
+    pub force: bool,
+    /// Create the parent directories of the --output file if they don't exist
+    #[clap(long, requires = "output")]
+    pub create_dirs: bool,
+    /// How to write the results of several files [default: json]. A single file is written as
+    /// a bare list of elements unless this is set
+    #[clap(long, value_enum)]
+    pub format: Option<ResultFormat>,
+    /// The number of files partitioned at the same time
+    #[clap(long, default_value = "1")]
+    pub concurrency: NonZeroUsize,
+    /// Stop at the first file that fails, instead of writing its error with the other results
+    #[clap(long)]
+    pub fail_fast: bool,
+    /// Allow wildcard paths that match no files
+    #[clap(long)]
+    pub allow_empty_glob: bool,
+    /// Include files and directories starting with a dot in the matches of wildcard paths
+    #[clap(long)]
+    pub hidden: bool,
 }
 
 #[tokio::main]
-async fn main() -> Result<(), CliError> {
+async fn main() -> ExitCode {
+    match run().await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::from(e.exit_code() as u8)
+        }
+    }
+}
+
+async fn run() -> Result<(), CliError> {
     // Parse CLI Arguments
-    let app_args = AppArgs::parse();
+    let matches = PartitionParameters::augment_args(AppArgs::command())
+        .try_get_matches()
+        .map_err(exit_unless_error)?;
+    let app_args = AppArgs::from_arg_matches(&matches)?;
 
     // Create an instance of UnstructuredClient
     let client = UnstructuredClient::new(app_args.base_url.as_ref())?;
 
     // Define partition parameters
-    let params = PartitionParameters::from(app_args.partition_parameters);
+    let params = PartitionParameters::from_arg_matches(&matches)?;
 
-    // Make the API request
-    let partition_response = client.partition_file(&app_args.file_path, params).await?;
+    let paths: Vec<PathBuf> = app_args
+        .file_path
+        .iter()
+        .chain(&app_args.files)
+        .cloned()
+        .collect();
+    let glob = GlobOptions {
+        hidden: app_args.hidden,
+        allow_empty: app_args.allow_empty_glob,
+    };
+    let inputs =
+        Input::resolve(&paths, app_args.file_name.as_deref(), glob).map_err(|message| {
+            CliError::from(AppArgs::command().error(ErrorKind::MissingRequiredArgument, message))
+        })?;
 
-    // Print the output
-    match partition_response {
-        PartitionResponse::Success(element_list) => {
-            println!("{}", to_string(&element_list)?);
+    // Make the API requests
+    let progress = progress::file_progress(inputs.len(), !app_args.no_progress);
+    match (inputs.as_slice(), app_args.format) {
+        ([input], None) => {
+            progress::set_processing(&progress, &[input.path()]);
+            let partition_response = input.partition(&client, params).await;
+            progress.inc(1);
+            progress.finish_and_clear();
+
+            // Print the output
+            match partition_response? {
+                PartitionResponse::Success(success) => {
+                    emit(&app_args, &to_string(&success.elements)?)?;
+                }
+                value => return Err(CliError::PartitionFailed(to_string(&value)?)),
+            }
         }
-        value => {
-            eprintln!("{}", to_string(&value)?);
+        (_, format) => {
+            let results = partition_all(&client, &inputs, params, &app_args, &progress).await;
+            progress.finish_and_clear();
+            let results = results?;
+
+            let format = format.unwrap_or(ResultFormat::Json);
+            emit(&app_args, &results::render(&results, format)?)?;
+
+            let failed = results.iter().filter(|r| r.elements.is_err()).count();
+            if failed > 0 {
+                return Err(CliError::FilesFailed {
+                    failed,
+                    total: results.len(),
+                });
+            }
         }
     }
 
     Ok(())
 }
+
+/// Partitions the inputs, `--concurrency` at a time, and returns their results in the order of
+/// `inputs`. With `--fail-fast`, the first failure is returned instead and the requests still
+/// in flight are dropped.
+async fn partition_all(
+    client: &UnstructuredClient,
+    inputs: &[Input],
+    params: PartitionParameters,
+    args: &AppArgs,
+    progress: &ProgressBar,
+) -> Result<Vec<FileResult>, CliError> {
+    let mut pending = inputs.iter().enumerate();
+    let mut tasks = JoinSet::new();
+    let mut in_flight = BTreeMap::new();
+    let mut results = Vec::with_capacity(inputs.len());
+    loop {
+        while tasks.len() < args.concurrency.get() {
+            let Some((index, input)) = pending.next() else {
+                break;
+            };
+            in_flight.insert(index, input.path());
+            let (input, client, params) = (input.clone(), client.clone(), params.clone());
+            tasks.spawn(async move {
+                let elements = partition_elements(&input, &client, params).await;
+                (index, input, elements)
+            });
+        }
+        if !in_flight.is_empty() {
+            let file_paths: Vec<&Path> = in_flight.values().copied().collect();
+            progress::set_processing(progress, &file_paths);
+        }
+
+        let Some(joined) = tasks.join_next().await else {
+            break;
+        };
+        let (index, input, elements) = joined.expect("partition task panicked");
+        in_flight.remove(&index);
+        progress.inc(1);
+        let elements = match elements {
+            Err(e) if args.fail_fast => {
+                return Err(CliError::FileFailed {
+                    file: input.name(),
+                    source: Box::new(e),
+                })
+            }
+            elements => elements.map_err(|e| e.to_string()),
+        };
+        results.push((
+            index,
+            FileResult {
+                file: input.name(),
+                elements,
+            },
+        ));
+    }
+
+    results.sort_by_key(|(index, _)| *index);
+    Ok(results.into_iter().map(|(_, result)| result).collect())
+}
+
+/// The elements of `input`, with responses other than a success turned into an error.
+async fn partition_elements(
+    input: &Input,
+    client: &UnstructuredClient,
+    params: PartitionParameters,
+) -> Result<ElementList, CliError> {
+    match input.partition(client, params).await? {
+        PartitionResponse::Success(success) => Ok(success.elements),
+        value => Err(CliError::PartitionFailed(to_string(&value)?)),
+    }
+}
+
+/// Writes the serialized results to `--output`, or to stdout.
+fn emit(args: &AppArgs, contents: &str) -> Result<(), CliError> {
+    match &args.output {
+        Some(path) => {
+            let options = OutputOptions {
+                force: args.force,
+                create_dirs: args.create_dirs,
+            };
+            output::write(path, contents.as_bytes(), options)
+        }
+        None if contents.ends_with('\n') => {
+            print!("{contents}");
+            Ok(())
+        }
+        None => {
+            println!("{contents}");
+            Ok(())
+        }
+    }
+}
+
+/// Exits right away for `--help` and `--version`, which clap reports as errors, and passes on
+/// actual argument errors.
+fn exit_unless_error(e: clap::Error) -> clap::Error {
+    if !e.use_stderr() {
+        e.exit();
+    }
+    e
+}
@@ -0,0 +1,107 @@
+use std::io::Write;
+use std::path::Path;
+
+use tempfile::NamedTempFile;
+
+use crate::error::CliError;
+
+/// How [write] treats the output path.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OutputOptions {
+    /// Replace the file if it already exists.
+    pub force: bool,
+
+    /// Create missing parent directories.
+    pub create_dirs: bool,
+}
+
+/// Writes `contents` to `path` atomically: the contents go to a temporary file in the same
+/// directory, which is then renamed to `path`, so that readers never see a partial result.
+pub fn write(path: &Path, contents: &[u8], options: OutputOptions) -> Result<(), CliError> {
+    let failed = |e: std::io::Error| CliError::OutputFailed(path.to_path_buf(), e);
+
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if options.create_dirs {
+        std::fs::create_dir_all(dir).map_err(failed)?;
+    }
+    if !options.force && path.exists() {
+        return Err(CliError::OutputExists(path.to_path_buf()));
+    }
+
+    let mut file = NamedTempFile::new_in(dir).map_err(failed)?;
+    file.write_all(contents).map_err(failed)?;
+    file.as_file().sync_all().map_err(failed)?;
+
+    if options.force {
+        file.persist(path).map_err(|e| failed(e.error))?;
+    } else {
+        // Fails if the file was created after the check above.
+        file.persist_noclobber(path)
+            .map_err(|e| match e.error.kind() {
+                std::io::ErrorKind::AlreadyExists => CliError::OutputExists(path.to_path_buf()),
+                _ => failed(e.error),
+            })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_new_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("elements.json");
+        write(&path, b"[]", OutputOptions::default()).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"[]");
+        // Only the output is left behind, not the temporary file.
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_refuses_existing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("elements.json");
+        std::fs::write(&path, "old").unwrap();
+
+        let result = write(&path, b"[]", OutputOptions::default());
+        assert!(matches!(result, Err(CliError::OutputExists(p)) if p == path));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "old");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_write_force() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("elements.json");
+        std::fs::write(&path, "old").unwrap();
+
+        let options = OutputOptions {
+            force: true,
+            ..OutputOptions::default()
+        };
+        write(&path, b"[]", options).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"[]");
+    }
+
+    #[test]
+    fn test_write_create_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("out/nested/elements.json");
+        assert!(matches!(
+            write(&path, b"[]", OutputOptions::default()),
+            Err(CliError::OutputFailed(..))
+        ));
+
+        let options = OutputOptions {
+            create_dirs: true,
+            ..OutputOptions::default()
+        };
+        write(&path, b"[]", options).unwrap();
+        assert_eq!(std::fs::read(&path).unwrap(), b"[]");
+    }
+}
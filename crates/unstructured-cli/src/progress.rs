@@ -0,0 +1,64 @@
+use indicatif::{ProgressBar, ProgressStyle};
+use std::borrow::Cow;
+use std::io::IsTerminal;
+use std::path::Path;
+
+/// Template rendering e.g. `[17/52] Processing report_q3.pdf... [00:01:12, ETA 00:02:40]`.
+const TEMPLATE: &str = "{msg} [{elapsed_precise}, ETA {eta}]";
+
+/// Creates a progress bar for processing `len` files.
+///
+/// The bar is hidden when `enabled` is false or when stdout is not a terminal (e.g. in CI or when
+/// piping the output), so it never ends up in captured results.
+pub fn file_progress(len: usize, enabled: bool) -> ProgressBar {
+    if !enabled || !std::io::stdout().is_terminal() {
+        return ProgressBar::hidden();
+    }
+
+    let style = ProgressStyle::with_template(TEMPLATE).expect("Invalid progress bar template.");
+    ProgressBar::new(len as u64).with_style(style)
+}
+
+/// Updates the progress bar message to show the files being processed: the file name when there
+/// is one, or how many there are.
+pub fn set_processing(progress: &ProgressBar, file_paths: &[&Path]) {
+    let len = progress.length().unwrap_or_default();
+    let first = progress.position() + 1;
+
+    let message = match file_paths {
+        [file_path] => format!("[{first}/{len}] Processing {}...", file_name(file_path)),
+        file_paths => {
+            let last = progress.position() + file_paths.len() as u64;
+            format!(
+                "[{first}-{last}/{len}] Processing {} files...",
+                file_paths.len()
+            )
+        }
+    };
+    progress.set_message(message);
+}
+
+fn file_name(file_path: &Path) -> Cow<'_, str> {
+    file_path
+        .file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_else(|| file_path.to_string_lossy())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indicatif::ProgressDrawTarget;
+
+    #[test]
+    fn test_set_processing() {
+        let progress = ProgressBar::with_draw_target(Some(5), ProgressDrawTarget::hidden());
+        progress.inc(2);
+
+        set_processing(&progress, &[Path::new("docs/report.pdf")]);
+        assert_eq!(progress.message(), "[3/5] Processing report.pdf...");
+
+        set_processing(&progress, &[Path::new("a.pdf"), Path::new("b.pdf")]);
+        assert_eq!(progress.message(), "[3-4/5] Processing 2 files...");
+    }
+}
@@ -0,0 +1,114 @@
+use clap::ValueEnum;
+use serde_json::{Map, Value};
+use unstructured_client::ElementList;
+
+use crate::error::CliError;
+
+/// How the results of several inputs are written.
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum ResultFormat {
+    /// A JSON object mapping each input to its elements, or to `{"error": ...}` if it failed
+    Json,
+    /// One JSON object per line for each element, with the input in a `file` field. A failed
+    /// input is a single `{"file": ..., "error": ...}` line
+    Jsonl,
+}
+
+/// The outcome of partitioning one input.
+#[derive(Debug)]
+pub struct FileResult {
+    /// The name of the input, see [Input::name](crate::input::Input::name).
+    pub file: String,
+    pub elements: Result<ElementList, String>,
+}
+
+/// Serializes the results in `format`. JSON objects are keyed by input name in sorted order,
+/// JSONL lines follow the order of `results`.
+pub fn render(results: &[FileResult], format: ResultFormat) -> Result<String, CliError> {
+    match format {
+        ResultFormat::Json => {
+            let mut map = Map::new();
+            for result in results {
+                let value = match &result.elements {
+                    Ok(elements) => serde_json::to_value(elements)?,
+                    Err(error) => serde_json::json!({ "error": error }),
+                };
+                map.insert(result.file.clone(), value);
+            }
+            Ok(serde_json::to_string(&map)?)
+        }
+        ResultFormat::Jsonl => {
+            let mut lines = String::new();
+            for result in results {
+                let records = match &result.elements {
+                    Ok(elements) => elements
+                        .iter()
+                        .map(serde_json::to_value)
+                        .collect::<Result<Vec<_>, _>>()?,
+                    Err(error) => vec![serde_json::json!({ "error": error })],
+                };
+                for mut record in records {
+                    if let Value::Object(record) = &mut record {
+                        record.insert("file".to_string(), result.file.clone().into());
+                    }
+                    lines.push_str(&serde_json::to_string(&record)?);
+                    lines.push('\n');
+                }
+            }
+            Ok(lines)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results() -> Vec<FileResult> {
+        let elements: ElementList = serde_json::from_value(serde_json::json!([
+            {"type": "Title", "element_id": "1", "text": "Report"},
+            {"type": "NarrativeText", "element_id": "2", "text": "Body"}
+        ]))
+        .unwrap();
+        vec![
+            FileResult {
+                file: "b.pdf".to_string(),
+                elements: Ok(elements),
+            },
+            FileResult {
+                file: "a.pdf".to_string(),
+                elements: Err("Service unavailable".to_string()),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_render_json() {
+        let json: Value =
+            serde_json::from_str(&render(&results(), ResultFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["b.pdf"][1]["text"], "Body");
+        let names: Vec<&String> = json.as_object().unwrap().keys().collect();
+        assert_eq!(names, vec!["a.pdf", "b.pdf"]);
+        assert_eq!(
+            json["a.pdf"],
+            serde_json::json!({"error": "Service unavailable"})
+        );
+    }
+
+    #[test]
+    fn test_render_jsonl() {
+        let jsonl = render(&results(), ResultFormat::Jsonl).unwrap();
+        let records: Vec<Value> = jsonl
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0]["file"], "b.pdf");
+        assert_eq!(records[0]["text"], "Report");
+        assert_eq!(records[1]["element_id"], "2");
+        assert_eq!(
+            records[2],
+            serde_json::json!({"file": "a.pdf", "error": "Service unavailable"})
+        );
+    }
+}
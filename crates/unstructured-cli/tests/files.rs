@@ -0,0 +1,155 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+use mockito::Matcher;
+
+/// Runs the CLI against `base_url` with `args`.
+fn run(base_url: &str, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_unstructured-cli"))
+        .args(["--base-url", base_url, "--no-progress"])
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// Writes `a.txt`, `b.txt` and `broken.txt`, and returns their paths.
+fn fixtures(dir: &Path) -> Vec<String> {
+    ["a.txt", "b.txt", "broken.txt"]
+        .iter()
+        .map(|name| {
+            let path: PathBuf = dir.join(name);
+            std::fs::write(&path, format!("Contents of {name}")).unwrap();
+            path.display().to_string()
+        })
+        .collect()
+}
+
+/// A server that returns the uploaded file name as the text of a single element, and fails
+/// with a 500 for `broken.txt`.
+fn mock_server() -> (mockito::ServerGuard, mockito::Mock, mockito::Mock) {
+    let mut server = mockito::Server::new();
+    let broken = server
+        .mock("POST", "/general/v0/general")
+        .match_body(Matcher::Regex(r#"filename="broken.txt""#.to_string()))
+        .with_status(500)
+        .with_body("Internal Server Error")
+        .create();
+    let ok = server
+        .mock("POST", "/general/v0/general")
+        .match_body(Matcher::Regex(r#"filename="(a|b).txt""#.to_string()))
+        .with_status(200)
+        .with_body_from_request(|request| {
+            let body = String::from_utf8_lossy(request.body().unwrap()).to_string();
+            let name = if body.contains(r#"filename="a.txt""#) {
+                "a.txt"
+            } else {
+                "b.txt"
+            };
+            format!(r#"[{{"type": "NarrativeText", "element_id": "1", "text": "{name}"}}]"#)
+                .into_bytes()
+        })
+        .create();
+    (server, ok, broken)
+}
+
+#[test]
+fn test_multiple_files_json() {
+    let (server, ok, broken) = mock_server();
+    let dir = tempfile::tempdir().unwrap();
+    let files = fixtures(dir.path());
+
+    // Positional paths and --file-path can be mixed.
+    let args = ["--file-path", &files[0], &files[1], &files[2]];
+    let output = run(&server.url(), &args);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stderr).contains("1 of 3 files failed"));
+
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results.as_object().unwrap().len(), 3);
+    assert_eq!(results[&files[0]][0]["text"], "a.txt");
+    assert_eq!(results[&files[1]][0]["text"], "b.txt");
+    assert!(results[&files[2]]["error"].is_string());
+    ok.expect(2).assert();
+    broken.expect(1).assert();
+}
+
+#[test]
+fn test_multiple_files_jsonl_concurrently() {
+    let (server, _ok, _broken) = mock_server();
+    let dir = tempfile::tempdir().unwrap();
+    let files = fixtures(dir.path());
+
+    let mut args = vec!["--format", "jsonl", "--concurrency", "3"];
+    args.extend(files.iter().map(String::as_str));
+    let output = run(&server.url(), &args);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+
+    // Records follow the order of the inputs, whichever request finished first.
+    let records: Vec<serde_json::Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let files_and_texts: Vec<_> = records
+        .iter()
+        .map(|record| (record["file"].as_str().unwrap(), record["text"].as_str()))
+        .collect();
+    assert_eq!(
+        files_and_texts,
+        vec![
+            (files[0].as_str(), Some("a.txt")),
+            (files[1].as_str(), Some("b.txt")),
+            (files[2].as_str(), None)
+        ]
+    );
+    assert!(records[2]["error"].is_string());
+}
+
+#[test]
+fn test_multiple_files_fail_fast() {
+    let (server, _ok, _broken) = mock_server();
+    let dir = tempfile::tempdir().unwrap();
+    let files = fixtures(dir.path());
+
+    let args = ["--fail-fast", &files[2], &files[0], &files[1]];
+    let output = run(&server.url(), &args);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    assert!(output.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with(&files[2]));
+}
+
+#[test]
+fn test_single_file_keeps_bare_list() {
+    let (server, _ok, _broken) = mock_server();
+    let dir = tempfile::tempdir().unwrap();
+    let files = fixtures(dir.path());
+
+    let output = run(&server.url(), &[&files[0]]);
+    assert!(output.status.success(), "{output:?}");
+    let elements: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(elements[0]["text"], "a.txt");
+
+    let output = run(&server.url(), &["--format", "json", &files[0]]);
+    let results: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(results[&files[0]][0]["text"], "a.txt");
+}
+
+#[test]
+fn test_single_file_validation_failure() {
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/general/v0/general")
+        .with_status(422)
+        .with_body(r#"{"detail": [{"loc": ["body", "files"], "msg": "field required", "type": "value_error.missing"}]}"#)
+        .create();
+    let dir = tempfile::tempdir().unwrap();
+    let files = fixtures(dir.path());
+
+    let output = run(&server.url(), &[&files[0]]);
+    assert_eq!(output.status.code(), Some(1), "{output:?}");
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Partitioning failed"), "{stderr}");
+    assert!(stderr.contains("field required"), "{stderr}");
+    mock.assert();
+}
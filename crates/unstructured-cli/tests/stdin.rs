@@ -0,0 +1,70 @@
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/sample-pdf.pdf");
+
+/// Runs the CLI against `base_url`, writing `stdin` to its standard input.
+fn run(base_url: &str, args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_unstructured-cli"))
+        .args(["--base-url", base_url, "--no-progress"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// A server answering with a single element that tells whether the upload was named
+/// `sample-pdf.pdf` and contained the fixture byte for byte.
+fn mock_server() -> (mockito::ServerGuard, mockito::Mock) {
+    let fixture = std::fs::read(FIXTURE).unwrap();
+    let mut server = mockito::Server::new();
+    let mock = server
+        .mock("POST", "/general/v0/general")
+        .with_status(200)
+        .with_body_from_request(move |request| {
+            let body = request.body().unwrap();
+            let name = br#"filename="sample-pdf.pdf""#;
+            let named = body.windows(name.len()).any(|window| window == name);
+            let intact = body.windows(fixture.len()).any(|window| window == fixture);
+            format!(
+                r#"[{{"type": "NarrativeText", "element_id": "1", "text": "{}"}}]"#,
+                named && intact
+            )
+            .into_bytes()
+        })
+        .expect(3)
+        .create();
+    (server, mock)
+}
+
+#[test]
+fn test_stdin_matches_file_path() {
+    let (server, mock) = mock_server();
+    let fixture = std::fs::read(FIXTURE).unwrap();
+
+    let from_file = run(&server.url(), &["--file-path", FIXTURE], &[]);
+    assert!(from_file.status.success(), "{from_file:?}");
+    assert!(String::from_utf8_lossy(&from_file.stdout).contains(r#""text":"true""#));
+
+    let dash = ["--file-path", "-", "--file-name", "sample-pdf.pdf"];
+    let from_stdin = run(&server.url(), &dash, &fixture);
+    assert!(from_stdin.status.success(), "{from_stdin:?}");
+    assert_eq!(from_stdin.stdout, from_file.stdout);
+
+    // Without --file-path, a piped stdin is read as well.
+    let implicit = run(&server.url(), &["--file-name", "sample-pdf.pdf"], &fixture);
+    assert!(implicit.status.success(), "{implicit:?}");
+    assert_eq!(implicit.stdout, from_file.stdout);
+    mock.assert();
+}
+
+#[test]
+fn test_stdin_requires_file_name() {
+    let output = run("http://localhost:1", &["--file-path", "-"], b"%PDF");
+    assert_eq!(output.status.code(), Some(3));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--file-name"));
+}
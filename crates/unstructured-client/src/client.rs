@@ -1,22 +1,96 @@
-use reqwest::multipart::Form;
+use reqwest::header::{HeaderValue, USER_AGENT};
 use reqwest::{multipart, Url};
+use serde_json::value::RawValue;
+use std::fmt;
 use std::fs;
+#[cfg(feature = "rate_limit")]
+use std::num::NonZeroU32;
 use std::path::Path;
+#[cfg(feature = "rate_limit")]
+use std::sync::Arc;
+use std::time::Duration;
+#[cfg(feature = "cancellation")]
+use tokio_util::sync::CancellationToken;
 
+use crate::element::{ElementList, ElementParseError};
 use crate::error::{ClientError, Result};
-use crate::partition::{PartitionParameters, PartitionResponse};
+use crate::partition::{ListEncoding, PartitionParameters, PartitionResponse, PartitionSuccess};
 
-/// Current crate version
-const VERSION: &str = env!("CARGO_PKG_VERSION");
+/// User-Agent sent unless overridden with [UnstructuredClient::with_user_agent]
+const DEFAULT_USER_AGENT: &str = concat!("Unstructured-Rust-Client/", env!("CARGO_PKG_VERSION"));
 
 /// The sub-route for partitioning
 const API_ROUTE: &str = "/general/v0/general";
 
-#[derive(Debug, Clone)]
+/// Settings for the `reqwest::Client` built by [UnstructuredClient].
+#[derive(Debug, Clone, Default)]
+struct HttpOptions {
+    http2_prior_knowledge: bool,
+    pool_idle_timeout: Option<Duration>,
+    tcp_keepalive: Option<Duration>,
+}
+
+impl HttpOptions {
+    /// Builds the HTTP client. Like `reqwest::Client::new`, this panics only when the TLS
+    /// backend can't be initialized.
+    fn build(&self) -> reqwest::Client {
+        let mut builder = reqwest::Client::builder();
+        if self.http2_prior_knowledge {
+            builder = builder.http2_prior_knowledge();
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        builder
+            .build()
+            .expect("failed to initialize the HTTP client")
+    }
+}
+
+/// Client for the Unstructured API.
+///
+/// `UnstructuredClient` is `Send + Sync` and cheap to clone (the underlying connection pool is
+/// shared), so a single instance can be shared across tasks and threads.
+#[derive(Clone)]
 pub struct UnstructuredClient {
     client: reqwest::Client,
+    /// `None` when the HTTP client was passed to [with_client](Self::with_client), so that it
+    /// isn't replaced by one built from these options.
+    http_options: Option<HttpOptions>,
     base_url: Url,
     api_key: Option<String>,
+    user_agent: HeaderValue,
+    keep_raw_json: bool,
+    list_encoding: ListEncoding,
+    #[cfg(feature = "rate_limit")]
+    rate_limiter: Option<Arc<governor::DefaultDirectRateLimiter>>,
+}
+
+// Fails to compile if a field that isn't `Send + Sync` is ever added.
+const _: () = {
+    fn assert_send_sync<T: Send + Sync>() {}
+    let _ = assert_send_sync::<UnstructuredClient>;
+};
+
+/// Redacts the API key, so that clients can be logged safely.
+impl fmt::Debug for UnstructuredClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("UnstructuredClient");
+        debug
+            .field("client", &self.client)
+            .field("http_options", &self.http_options)
+            .field("base_url", &self.base_url.as_str())
+            .field("api_key", &self.api_key.as_ref().map(|_| "***"))
+            .field("user_agent", &self.user_agent)
+            .field("keep_raw_json", &self.keep_raw_json)
+            .field("list_encoding", &self.list_encoding);
+        #[cfg(feature = "rate_limit")]
+        debug.field("rate_limiter", &self.rate_limiter);
+        debug.finish()
+    }
 }
 
 impl UnstructuredClient {
@@ -39,11 +113,39 @@ impl UnstructuredClient {
     /// }
     /// ```
     pub fn new(base_url: &str) -> Result<Self> {
+        Ok(UnstructuredClient {
+            http_options: Some(HttpOptions::default()),
+            ..Self::with_client(reqwest::Client::new(), base_url)?
+        })
+    }
+
+    /// Creates a new `UnstructuredClient` that sends its requests through an existing
+    /// `reqwest::Client`.
+    ///
+    /// Use this to share a connection pool, or to reuse a client configured with proxies,
+    /// timeouts or a custom DNS resolver. The HTTP options such as
+    /// [with_http2](Self::with_http2) can't be used with such a client, configure it directly
+    /// instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `client`: The HTTP client used to send requests.
+    /// * `base_url`: A string slice that holds the base URL for the client.
+    ///
+    /// returns: `Result<UnstructuredClient, ClientError>` - On failure, returns a `ClientError`
+    /// explaining what went wrong.
+    pub fn with_client(client: reqwest::Client, base_url: &str) -> Result<Self> {
         let url = Url::parse(base_url).map_err(|e| ClientError::URLParseFailed(e.to_string()))?;
         Ok(UnstructuredClient {
-            client: reqwest::Client::new(),
+            client,
+            http_options: None,
             base_url: url,
             api_key: None,
+            user_agent: HeaderValue::from_static(DEFAULT_USER_AGENT),
+            keep_raw_json: false,
+            list_encoding: ListEncoding::default(),
+            #[cfg(feature = "rate_limit")]
+            rate_limiter: None,
         })
     }
 
@@ -66,6 +168,90 @@ impl UnstructuredClient {
         }
     }
 
+    /// Sets the User-Agent header sent with each request, replacing the default
+    /// `Unstructured-Rust-Client/<version>`.
+    ///
+    /// Fails when `agent` isn't a valid header value, e.g. because it contains a newline.
+    pub fn with_user_agent(self, agent: &str) -> Result<Self> {
+        Ok(Self {
+            user_agent: HeaderValue::from_str(agent)?,
+            ..self
+        })
+    }
+
+    /// Keeps the JSON of each returned element in [Element::raw](crate::Element::raw), so that
+    /// elements serialize exactly as received. Off by default, since it about doubles the memory
+    /// used by the elements.
+    pub fn with_raw_json(self, keep_raw_json: bool) -> Self {
+        Self {
+            keep_raw_json,
+            ..self
+        }
+    }
+
+    /// Sets how list parameters such as `languages` are sent, for API versions that expect one
+    /// form field per value instead of a JSON array.
+    pub fn with_list_encoding(self, list_encoding: ListEncoding) -> Self {
+        Self {
+            list_encoding,
+            ..self
+        }
+    }
+
+    /// Sends all requests over HTTP/2, without first negotiating the protocol, so that
+    /// concurrent requests can be multiplexed over a single connection.
+    ///
+    /// The server must support HTTP/2, otherwise every request fails. Only needed for plain-HTTP
+    /// deployments: over TLS, HTTP/2 is negotiated automatically when the server supports it.
+    ///
+    /// Fails when the HTTP client was passed to [with_client](Self::with_client).
+    pub fn with_http2(self) -> Result<Self> {
+        self.with_http_options(|options| options.http2_prior_knowledge = true)
+    }
+
+    /// Closes pooled connections that have been idle for `timeout`, so that connections to
+    /// backends that were replaced, e.g. behind a load balancer, aren't reused.
+    ///
+    /// Fails when the HTTP client was passed to [with_client](Self::with_client).
+    pub fn with_pool_idle_timeout(self, timeout: Duration) -> Result<Self> {
+        self.with_http_options(|options| options.pool_idle_timeout = Some(timeout))
+    }
+
+    /// Sends TCP keepalive probes every `interval`, so that dead connections are detected.
+    ///
+    /// Fails when the HTTP client was passed to [with_client](Self::with_client).
+    pub fn with_tcp_keepalive(self, interval: Duration) -> Result<Self> {
+        self.with_http_options(|options| options.tcp_keepalive = Some(interval))
+    }
+
+    /// Updates the HTTP options and rebuilds the HTTP client from them.
+    fn with_http_options(self, update: impl FnOnce(&mut HttpOptions)) -> Result<Self> {
+        let mut http_options = self
+            .http_options
+            .clone()
+            .ok_or(ClientError::InjectedHttpClient)?;
+        update(&mut http_options);
+        Ok(Self {
+            client: http_options.build(),
+            http_options: Some(http_options),
+            ..self
+        })
+    }
+
+    /// Limits the client to `requests_per_second` partition requests, allowing bursts of the
+    /// same size.
+    ///
+    /// Requests over the limit wait until they're allowed through rather than failing. Clones of
+    /// the client share the same limit.
+    #[cfg(feature = "rate_limit")]
+    pub fn with_rate_limit(self, requests_per_second: NonZeroU32) -> Self {
+        let quota = governor::Quota::per_second(requests_per_second);
+        Self {
+            rate_limiter: Some(Arc::new(governor::RateLimiter::direct(quota))),
+            ..self
+        }
+    }
+
     /// Partitions the content of a given file using Unstructured's API.
     ///
     /// This asynchronous function reads the content of a specified file, creates a multipart
@@ -86,14 +272,119 @@ impl UnstructuredClient {
         file_path: &Path,
         params: PartitionParameters,
     ) -> Result<PartitionResponse> {
-        let url = self
-            .base_url
-            .join(API_ROUTE)
-            .map_err(|e| ClientError::URLParseFailed(e.to_string()))?;
+        let response = self.send_partition_request(file_path, params).await?;
+        self.read_partition_response(response).await
+    }
+
+    /// Partitions a document that is already in memory, e.g. one read from stdin, like
+    /// [partition_file](Self::partition_file) does for a file on disk.
+    ///
+    /// The server detects the filetype from `file_name`, so it should have the right extension.
+    #[tracing::instrument(skip(content))]
+    pub async fn partition_bytes(
+        &self,
+        file_name: &str,
+        content: Vec<u8>,
+        params: PartitionParameters,
+    ) -> Result<PartitionResponse> {
+        params.validate()?;
+        let response = self
+            .send_partition_form(file_name.to_string(), content, params)
+            .await?;
+        self.read_partition_response(response).await
+    }
 
-        tracing::trace!("Building partition request for {file_path:?} to {url}.");
+    async fn read_partition_response(
+        &self,
+        response: reqwest::Response,
+    ) -> Result<PartitionResponse> {
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let partition_response: PartitionResponse = if self.keep_raw_json {
+            // Deserialize as in typed mode, so that the same body gives the same variant.
+            let body = response.text().await?;
+            let mut partition_response: PartitionResponse = serde_json::from_str(&body)?;
+            if let PartitionResponse::Success(success) = &mut partition_response {
+                let items: Vec<&RawValue> = serde_json::from_str(&body)?;
+                for (element, item) in success.elements.iter_mut().zip(items) {
+                    element.raw = Some(item.to_owned());
+                }
+            }
+            partition_response
+        } else {
+            response.json().await?
+        };
+
+        Ok(partition_response.with_http_metadata(status, headers))
+    }
+
+    /// Like [partition_file](Self::partition_file), but gives up with [ClientError::Cancelled]
+    /// as soon as `token` is cancelled.
+    ///
+    /// The in-flight request is dropped on cancellation, which closes its connection. A token
+    /// that is already cancelled fails before anything is sent.
+    #[cfg(feature = "cancellation")]
+    #[tracing::instrument(skip(token))]
+    pub async fn partition_file_cancellable(
+        &self,
+        file_path: &Path,
+        params: PartitionParameters,
+        token: CancellationToken,
+    ) -> Result<PartitionResponse> {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => {
+                tracing::debug!("Partition request cancelled");
+                Err(ClientError::Cancelled)
+            }
+            result = self.partition_file(file_path, params) => result,
+        }
+    }
+
+    /// Like [partition_file](Self::partition_file), but elements that fail to deserialize are
+    /// skipped and reported next to the response instead of failing the whole request.
+    ///
+    /// See [ElementList::from_json_lenient].
+    #[tracing::instrument]
+    pub async fn partition_file_lenient(
+        &self,
+        file_path: &Path,
+        params: PartitionParameters,
+    ) -> Result<(PartitionResponse, Vec<ElementParseError>)> {
+        let response = self.send_partition_request(file_path, params).await?;
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
 
-        let file = fs::read(file_path)?;
+        let (partition_response, errors) = if body.trim_start().starts_with('[') {
+            let (elements, errors) = ElementList::parse_lenient(&body, self.keep_raw_json)?;
+            if !errors.is_empty() {
+                tracing::warn!("Skipped {} elements that failed to parse", errors.len());
+            }
+            let success = PartitionSuccess {
+                elements,
+                ..PartitionSuccess::default()
+            };
+            (PartitionResponse::Success(success), errors)
+        } else {
+            (serde_json::from_str(&body)?, Vec::new())
+        };
+        Ok((
+            partition_response.with_http_metadata(status, headers),
+            errors,
+        ))
+    }
+
+    async fn send_partition_request(
+        &self,
+        file_path: &Path,
+        params: PartitionParameters,
+    ) -> Result<reqwest::Response> {
+        params.validate()?;
+
+        tracing::trace!("Building partition request for {file_path:?}.");
+
+        let file = read_file(file_path)?;
 
         let file_name = file_path
             .file_name()
@@ -102,11 +393,24 @@ impl UnstructuredClient {
             .ok_or(ClientError::FileIOError("File name not valid UTF-8".into()))?
             .to_string();
 
-        tracing::debug!("Reading file into memory");
-        let file_part = multipart::Part::bytes(file).file_name(file_name);
+        self.send_partition_form(file_name, file, params).await
+    }
+
+    async fn send_partition_form(
+        &self,
+        file_name: String,
+        content: Vec<u8>,
+        params: PartitionParameters,
+    ) -> Result<reqwest::Response> {
+        let url = self
+            .base_url
+            .join(API_ROUTE)
+            .map_err(|e| ClientError::URLParseFailed(e.to_string()))?;
+
+        tracing::trace!("Sending {file_name} to {url}.");
+        let file_part = multipart::Part::bytes(content).file_name(file_name);
 
-        // Create reqwest multipart Form using the implementation for Into<Form>
-        let form: Form = params.into();
+        let form = params.to_form_with(self.list_encoding);
 
         // Add file part
         let form = form.part("files", file_part);
@@ -118,7 +422,7 @@ impl UnstructuredClient {
             .post(url)
             .multipart(form)
             .header("Content-Type", "multipart/form-data")
-            .header("User-Agent", format!("Unstructured-Rust-Client/{VERSION}"));
+            .header(USER_AGENT, self.user_agent.clone());
 
         // Add api key
         let request = {
@@ -128,14 +432,39 @@ impl UnstructuredClient {
             }
         };
 
-        // Process response
-        let response = request.send().await?;
-        let element_list = response.json().await?;
+        #[cfg(feature = "rate_limit")]
+        if let Some(rate_limiter) = &self.rate_limiter {
+            tracing::trace!("Waiting for rate limiter");
+            rate_limiter.until_ready().await;
+        }
 
-        Ok(element_list)
+        Ok(request.send().await?)
     }
 }
 
+/// Reads the file to upload, with errors naming the path when it's missing or unreadable.
+fn read_file(file_path: &Path) -> Result<Vec<u8>> {
+    if !file_path.try_exists()? {
+        return Err(ClientError::FileIOError(format!(
+            "File not found: {}",
+            file_path.display()
+        )));
+    }
+    if !file_path.is_file() {
+        return Err(ClientError::FileIOError(format!(
+            "Not a file: {}",
+            file_path.display()
+        )));
+    }
+
+    fs::read(file_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::PermissionDenied => {
+            ClientError::FileIOError(format!("File is not readable: {}", file_path.display()))
+        }
+        _ => e.into(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,6 +473,19 @@ mod tests {
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn test_debug_redacts_api_key() -> Result<()> {
+        let client = UnstructuredClient::new("https://example.com")?;
+        assert!(format!("{client:?}").contains("api_key: None"));
+
+        let client = client.with_api_key("secret");
+        let debug = format!("{client:?}");
+        assert!(debug.contains(r#"api_key: Some("***")"#));
+        assert!(debug.contains("https://example.com/"));
+        assert!(!debug.contains("secret"));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_partition_file() -> Result<()> {
         // Request a new server from the pool
@@ -160,6 +502,7 @@ mod tests {
                 Matcher::Regex("multipart/form-data.*".to_string()),
             )
             .with_status(200)
+            .with_header("x-unstructured-pages-remaining", "42")
             .with_body(
                 r#"
 		        [
@@ -202,6 +545,8 @@ mod tests {
         match result {
             Success(element_list) => {
                 assert_eq!(element_list.len(), 3);
+                assert_eq!(element_list.status, 200);
+                assert_eq!(element_list.headers["x-unstructured-pages-remaining"], "42");
             }
             e => {
                 panic!("Test failed with error: {:?}", e);
@@ -213,4 +558,396 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_with_client() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .match_header("x-custom", "shared")
+            .with_status(200)
+            .with_body("[]")
+            .create();
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-custom", "shared".parse().unwrap());
+        let http_client = reqwest::Client::builder()
+            .default_headers(headers)
+            .build()?;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let client = UnstructuredClient::with_client(http_client, &server.url())?;
+        let result = client
+            .partition_file(temp_file.path(), PartitionParameters::default())
+            .await?;
+
+        assert!(matches!(result, Success(elements) if elements.is_empty()));
+        mock.assert();
+
+        assert!(matches!(
+            UnstructuredClient::with_client(reqwest::Client::new(), "not a url"),
+            Err(ClientError::URLParseFailed(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_http_options() -> Result<()> {
+        let client = UnstructuredClient::new("http://localhost:8000")?
+            .with_api_key("secret")
+            .with_http2()?;
+        let http2 = |client: &UnstructuredClient| {
+            client
+                .http_options
+                .as_ref()
+                .is_some_and(|options| options.http2_prior_knowledge)
+        };
+        assert!(http2(&client));
+        assert_eq!(client.api_key.as_deref(), Some("secret"));
+
+        // Options accumulate, whatever the order they are set in.
+        let client = client
+            .with_pool_idle_timeout(Duration::from_secs(30))?
+            .with_tcp_keepalive(Duration::from_secs(60))?;
+        assert!(http2(&client));
+        let options = client.http_options.as_ref().unwrap();
+        assert_eq!(options.pool_idle_timeout, Some(Duration::from_secs(30)));
+        assert_eq!(options.tcp_keepalive, Some(Duration::from_secs(60)));
+        assert!(!http2(&UnstructuredClient::new("http://localhost:8000")?));
+
+        // A client passed in by the caller is never replaced.
+        let injected =
+            || UnstructuredClient::with_client(reqwest::Client::new(), "http://localhost:8000");
+        for result in [
+            injected()?.with_http2(),
+            injected()?.with_pool_idle_timeout(Duration::from_secs(30)),
+            injected()?.with_tcp_keepalive(Duration::from_secs(60)),
+        ] {
+            assert!(matches!(result, Err(ClientError::InjectedHttpClient)));
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_partition_file_invalid_parameters() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .expect(0)
+            .create();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let client = UnstructuredClient::new(&server.url()).unwrap();
+        let params = PartitionParameters {
+            extract_image_block_types: vec!["foo".to_string()],
+            ..PartitionParameters::default()
+        };
+
+        let result = client.partition_file(temp_file.path(), params).await;
+        assert!(matches!(result, Err(ClientError::InvalidParameters(_))));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_partition_bytes() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .match_body(Matcher::AllOf(vec![
+                Matcher::Regex(r#"filename="notes.txt""#.to_string()),
+                Matcher::Regex("Some notes.".to_string()),
+            ]))
+            .with_status(200)
+            .with_body(r#"[{"type": "NarrativeText", "element_id": "1", "text": "Some notes."}]"#)
+            .create_async()
+            .await;
+
+        let client = UnstructuredClient::new(&server.url())?;
+        let response = client
+            .partition_bytes(
+                "notes.txt",
+                b"Some notes.".to_vec(),
+                PartitionParameters::default(),
+            )
+            .await?;
+        match response {
+            Success(success) => assert_eq!(success.elements[0].text, "Some notes."),
+            other => panic!("expected elements, got {other:?}"),
+        }
+        mock.assert_async().await;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_partition_file_missing_file() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .expect(0)
+            .create();
+        let client = UnstructuredClient::new(&server.url()).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing.pdf");
+        let result = client
+            .partition_file(&missing, PartitionParameters::default())
+            .await;
+        match result {
+            Err(ClientError::FileIOError(message)) => {
+                assert_eq!(message, format!("File not found: {}", missing.display()))
+            }
+            other => panic!("expected a file error, got {other:?}"),
+        }
+
+        let result = client
+            .partition_file(dir.path(), PartitionParameters::default())
+            .await;
+        assert!(
+            matches!(result, Err(ClientError::FileIOError(m)) if m.starts_with("Not a file: "))
+        );
+        mock.assert();
+    }
+
+    #[cfg(feature = "rate_limit")]
+    #[tokio::test]
+    async fn test_with_rate_limit() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .with_status(200)
+            .with_body("[]")
+            .expect(3)
+            .create();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let client =
+            UnstructuredClient::new(&server.url())?.with_rate_limit(NonZeroU32::new(2).unwrap());
+
+        // The first two requests are within the burst, the third has to wait for half a second.
+        let start = std::time::Instant::now();
+        for _ in 0..3 {
+            client
+                .partition_file(temp_file.path(), PartitionParameters::default())
+                .await?;
+        }
+        assert!(start.elapsed() >= std::time::Duration::from_millis(400));
+        mock.assert();
+
+        Ok(())
+    }
+
+    #[cfg(feature = "cancellation")]
+    #[tokio::test]
+    async fn test_partition_file_cancellable() -> Result<()> {
+        // A server that accepts connections but never responds.
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((stream, _)) = listener.accept().await {
+                connections.push(stream);
+            }
+        });
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let client = UnstructuredClient::new(&url)?;
+
+        let token = CancellationToken::new();
+        let cancel = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+            cancel.cancel();
+        });
+        let result = client
+            .partition_file_cancellable(temp_file.path(), PartitionParameters::default(), token)
+            .await;
+        assert!(matches!(result, Err(ClientError::Cancelled)));
+
+        // Nothing is sent when the token is already cancelled.
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .expect(0)
+            .create();
+        let token = CancellationToken::new();
+        token.cancel();
+        let result = UnstructuredClient::new(&server.url())?
+            .partition_file_cancellable(temp_file.path(), PartitionParameters::default(), token)
+            .await;
+        assert!(matches!(result, Err(ClientError::Cancelled)));
+        mock.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_partition_file_lenient() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .with_status(200)
+            .with_body(
+                r#"[
+                    {"type": "Title", "element_id": "1", "text": "Title"},
+                    {"type": "Title", "element_id": "2", "text": null}
+                ]"#,
+            )
+            .create();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let client = UnstructuredClient::new(&server.url())?;
+        let (response, errors) = client
+            .partition_file_lenient(temp_file.path(), PartitionParameters::default())
+            .await?;
+
+        assert!(
+            matches!(response, Success(elements) if elements.len() == 1 && elements.status == 200)
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].index, 1);
+        mock.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_with_raw_json() -> Result<()> {
+        let body =
+            r#"[{"type": "Title", "element_id": "1", "text": "Title", "new_field": [1, 2.50]}]"#;
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .with_status(200)
+            .with_body(body)
+            .expect(2)
+            .create();
+        let temp_file = NamedTempFile::new().unwrap();
+
+        let client = UnstructuredClient::new(&server.url())?;
+        let Success(typed) = client
+            .partition_file(temp_file.path(), PartitionParameters::default())
+            .await?
+        else {
+            panic!("expected a successful response");
+        };
+        assert!(typed.elements[0].raw.is_none());
+
+        let Success(raw) = client
+            .with_raw_json(true)
+            .partition_file(temp_file.path(), PartitionParameters::default())
+            .await?
+        else {
+            panic!("expected a successful response");
+        };
+        assert_eq!(raw.status, 200);
+        assert_eq!(raw.elements, typed.elements);
+        assert_eq!(
+            serde_json::to_string(&raw.elements)?,
+            r#"[{"type": "Title", "element_id": "1", "text": "Title", "new_field": [1, 2.50]}]"#
+        );
+        mock.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_with_raw_json_malformed_element() -> Result<()> {
+        let body = r#"[{"type": "Title", "element_id": "1", "text": null}]"#;
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .with_status(200)
+            .with_body(body)
+            .expect(2)
+            .create();
+        let temp_file = NamedTempFile::new().unwrap();
+
+        let client = UnstructuredClient::new(&server.url())?;
+        let mut responses = Vec::new();
+        for keep_raw_json in [false, true] {
+            let response = client
+                .clone()
+                .with_raw_json(keep_raw_json)
+                .partition_file(temp_file.path(), PartitionParameters::default())
+                .await?;
+            let PartitionResponse::UnknownFailure(value) = response else {
+                panic!("expected an unknown failure, got {response:?}");
+            };
+            responses.push(value);
+        }
+        assert_eq!(responses[0], responses[1]);
+        assert_eq!(
+            responses[1],
+            serde_json::from_str::<serde_json::Value>(body)?
+        );
+        mock.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_with_list_encoding() -> Result<()> {
+        let field = |name: &str, value: &str| {
+            // The values don't contain regex metacharacters.
+            Matcher::Regex(format!("name=\"{name}\"\r\n\r\n{value}\r\n"))
+        };
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/general/v0/general")
+            .match_body(Matcher::AllOf(vec![
+                field("extract_image_block_types", "Image"),
+                field("extract_image_block_types", "Table"),
+                field("languages", "eng"),
+            ]))
+            .with_status(200)
+            .with_body("[]")
+            .create();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let client = UnstructuredClient::new(&server.url())?
+            .with_list_encoding(ListEncoding::RepeatedFields);
+        let params = PartitionParameters {
+            extract_image_block_types: vec!["Image".to_string(), "Table".to_string()],
+            languages: Some(vec!["eng".to_string()]),
+            ..PartitionParameters::default()
+        };
+        client.partition_file(temp_file.path(), params).await?;
+        mock.assert();
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_user_agent() -> Result<()> {
+        let mut server = mockito::Server::new_async().await;
+        let default_mock = server
+            .mock("POST", "/general/v0/general")
+            .match_header("user-agent", DEFAULT_USER_AGENT)
+            .with_body("[]")
+            .create();
+        let custom_mock = server
+            .mock("POST", "/general/v0/general")
+            .match_header("user-agent", "MyProduct/1.0")
+            .with_body("[]")
+            .create();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let client = UnstructuredClient::new(&server.url())?;
+        client
+            .partition_file(temp_file.path(), PartitionParameters::default())
+            .await?;
+        default_mock.assert();
+
+        let client = client.with_user_agent("MyProduct/1.0")?;
+        client
+            .partition_file(temp_file.path(), PartitionParameters::default())
+            .await?;
+        custom_mock.assert();
+
+        assert!(matches!(
+            client.with_user_agent("bad\nagent"),
+            Err(ClientError::InvalidHeaderValue(_))
+        ));
+        Ok(())
+    }
 }
@@ -1,8 +1,76 @@
-use crate::metadata::Metadata;
+#[cfg(test)]
+mod arbitrary;
+#[cfg(feature = "arrow")]
+mod arrow;
+mod boilerplate;
+mod chunks;
+mod citation;
+mod csv;
+mod dedupe;
+mod diff;
+mod document;
+mod hierarchy;
+mod id;
+mod jsonl;
+mod markdown;
+pub mod normalize;
+mod order;
+mod orig;
+mod outline;
+mod pages;
+mod rich_text;
+mod search;
+#[cfg(feature = "sqlite")]
+mod sqlite;
+mod stats;
+#[cfg(feature = "tables")]
+mod table;
+#[cfg(feature = "tiktoken")]
+mod tokens;
+mod window;
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::ops::{Deref, DerefMut, Index, IndexMut, Range};
+use std::slice::SliceIndex;
+use std::str::FromStr;
+
+use crate::error::Result;
+use crate::metadata::{
+    CommonMetadata, EmailMetadata, EpubMetadata, ExcelMetadata, HtmlMetadata, Metadata,
+    MsgMetadata, PagedDocument, PptxMetadata, TextMetadata, WordDocMetadata,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+use thiserror::Error;
+
+pub use self::boilerplate::BoilerplateOptions;
+pub use self::chunks::ChunkIndex;
+pub use self::citation::Citation;
+pub use self::csv::{CoordinatesFormat, CsvColumn, CsvOptions};
+pub use self::dedupe::DedupeOptions;
+pub use self::diff::{diff, ChangedElement, ChangedField, ElementDiff};
+pub use self::document::Document;
+pub use self::hierarchy::{depth_first, DepthFirst, ElementNode};
+pub use self::id::{ElementId, IdKind};
+#[cfg(feature = "tokio")]
+pub use self::jsonl::write_jsonl_async;
+pub use self::jsonl::{read_jsonl, read_jsonl_lenient, write_jsonl, JsonlLineError};
+pub use self::normalize::NormalizeOptions;
+pub use self::outline::Section;
+pub use self::rich_text::RichTextFormat;
+pub use self::search::SearchHit;
+pub use self::stats::{ElementStats, LengthDistribution};
+#[cfg(feature = "tables")]
+pub use self::table::{Cell, LineBreaks, MarkdownTableOptions, Table};
+pub use self::window::{WindowPart, WindowSlice};
 
 /// Enum representing various types of elements in a document.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+///
+/// Serializes as the name used by the API, see [ElementType::as_str].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ElementType {
     /// An element containing formulas in a document.
     Formula,
@@ -52,17 +120,1025 @@ pub enum ElementType {
 
     /// A chunk formed from text (non-Table) elements. It is only produced by chunking.
     CompositeElement,
+
+    /// A chunk of a table that was split because it exceeded `max_characters`. It is only
+    /// produced by chunking.
+    TableChunk,
+
+    /// Text that doesn't fit a more specific category.
+    Text,
+
+    /// Bulleted text that isn't recognized as part of a list.
+    BulletedText,
+
+    /// The abstract of a paper or article.
+    Abstract,
+
+    /// A form, e.g. in a scanned document.
+    Form,
+
+    /// The name of a form field, named `Field-Name` by the API.
+    FieldName,
+
+    /// The value of a form field.
+    Value,
+
+    /// A hyperlink.
+    Link,
+
+    /// Any element type this version of the crate doesn't know about yet, so that new types
+    /// added to the API don't fail deserialization. It holds the original type name, which is
+    /// written back unchanged when serializing.
+    Unknown(String),
 }
 
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+impl ElementType {
+    /// All element types known to the API, i.e. all but [ElementType::Unknown].
+    pub const ALL: &'static [ElementType] = &[
+        ElementType::Formula,
+        ElementType::FigureCaption,
+        ElementType::NarrativeText,
+        ElementType::ListItem,
+        ElementType::Title,
+        ElementType::Address,
+        ElementType::EmailAddress,
+        ElementType::Image,
+        ElementType::PageBreak,
+        ElementType::Table,
+        ElementType::Header,
+        ElementType::Footer,
+        ElementType::CodeSnippet,
+        ElementType::PageNumber,
+        ElementType::UncategorizedText,
+        ElementType::CompositeElement,
+        ElementType::TableChunk,
+        ElementType::Text,
+        ElementType::BulletedText,
+        ElementType::Abstract,
+        ElementType::Form,
+        ElementType::FieldName,
+        ElementType::Value,
+        ElementType::Link,
+    ];
+}
+
+impl ElementType {
+    /// The name of the element type as used by the API, e.g. `NarrativeText`. For
+    /// [ElementType::Unknown] this is the original name.
+    pub fn as_str(&self) -> &str {
+        match self {
+            ElementType::Formula => "Formula",
+            ElementType::FigureCaption => "FigureCaption",
+            ElementType::NarrativeText => "NarrativeText",
+            ElementType::ListItem => "ListItem",
+            ElementType::Title => "Title",
+            ElementType::Address => "Address",
+            ElementType::EmailAddress => "EmailAddress",
+            ElementType::Image => "Image",
+            ElementType::PageBreak => "PageBreak",
+            ElementType::Table => "Table",
+            ElementType::Header => "Header",
+            ElementType::Footer => "Footer",
+            ElementType::CodeSnippet => "CodeSnippet",
+            ElementType::PageNumber => "PageNumber",
+            ElementType::UncategorizedText => "UncategorizedText",
+            ElementType::CompositeElement => "CompositeElement",
+            ElementType::TableChunk => "TableChunk",
+            ElementType::Text => "Text",
+            ElementType::BulletedText => "BulletedText",
+            ElementType::Abstract => "Abstract",
+            ElementType::Form => "Form",
+            ElementType::FieldName => "Field-Name",
+            ElementType::Value => "Value",
+            ElementType::Link => "Link",
+            ElementType::Unknown(name) => name,
+        }
+    }
+}
+
+/// Displays the element type as it is named by the API, e.g. `NarrativeText`.
+impl fmt::Display for ElementType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for ElementType {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Deserializes an exact API name, keeping names that aren't known as [ElementType::Unknown].
+impl<'de> Deserialize<'de> for ElementType {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(ElementType::from(name.as_str()))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for ElementType {
+    fn schema_name() -> String {
+        "ElementType".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        // Any string is accepted, since unknown names are kept as `Unknown`.
+        String::json_schema(gen)
+    }
+}
+
+/// Error returned when parsing an unknown [ElementType].
+#[derive(Debug, Clone, Error, PartialEq)]
+#[error(
+    "Unknown element type '{input}', expected one of: {}",
+    valid_element_types()
+)]
+pub struct ParseElementTypeError {
+    pub input: String,
+}
+
+fn valid_element_types() -> String {
+    ElementType::ALL
+        .iter()
+        .map(ElementType::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Key used to compare element type names regardless of case and word separators.
+fn normalized_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Parses an element type from its API name (`NarrativeText`), its snake_case form
+/// (`narrative_text`), or any case-insensitive variation thereof. Only known element types are
+/// accepted, so typos in e.g. command line arguments are reported; use [From] to keep unknown
+/// names.
+impl FromStr for ElementType {
+    type Err = ParseElementTypeError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let input = normalized_name(s.trim());
+        ElementType::ALL
+            .iter()
+            .find(|t| normalized_name(t.as_str()) == input)
+            .cloned()
+            .ok_or_else(|| ParseElementTypeError {
+                input: s.to_string(),
+            })
+    }
+}
+
+/// Converts an exact API name, e.g. `NarrativeText`, into an element type.
+///
+/// The match is case-sensitive, as it is when deserializing, and names that aren't known become
+/// [ElementType::Unknown]. This also provides an infallible [TryFrom].
+impl From<&str> for ElementType {
+    fn from(s: &str) -> Self {
+        ElementType::ALL
+            .iter()
+            .find(|t| t.as_str() == s)
+            .cloned()
+            .unwrap_or_else(|| ElementType::Unknown(s.to_string()))
+    }
+}
+
+/// An element of a partitioned document.
+///
+/// Elements compare equal when their typed fields are equal, regardless of [raw](Self::raw).
+#[derive(Debug, Clone, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Element {
     pub r#type: ElementType,
-    pub element_id: String,
+    pub element_id: ElementId,
     pub text: String,
     pub metadata: Option<Metadata>,
+
+    /// The JSON of the element exactly as it was received, only kept when parsed with
+    /// [ElementList::from_json_with_raw] or by a client set up with
+    /// [with_raw_json](crate::UnstructuredClient::with_raw_json).
+    ///
+    /// When present, it is what the element serializes to, so that fields this crate doesn't
+    /// model are preserved byte for byte. Changes to the typed fields are then not serialized;
+    /// clear it after modifying the element.
+    #[serde(skip)]
+    pub raw: Option<Box<RawValue>>,
+}
+
+/// The typed fields of an [Element], for serializing elements without raw JSON.
+#[derive(Serialize)]
+struct TypedElement<'a> {
+    r#type: &'a ElementType,
+    element_id: &'a ElementId,
+    text: &'a str,
+    metadata: &'a Option<Metadata>,
+}
+
+impl Serialize for Element {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        match &self.raw {
+            Some(raw) => raw.serialize(serializer),
+            None => TypedElement {
+                r#type: &self.r#type,
+                element_id: &self.element_id,
+                text: &self.text,
+                metadata: &self.metadata,
+            }
+            .serialize(serializer),
+        }
+    }
+}
+
+impl PartialEq for Element {
+    fn eq(&self, other: &Self) -> bool {
+        self.r#type == other.r#type
+            && self.element_id == other.element_id
+            && self.text == other.text
+            && self.metadata == other.metadata
+    }
+}
+
+impl Element {
+    /// Borrows the metadata fields common to all file types, if the element has metadata.
+    pub fn common_metadata(&self) -> Option<&CommonMetadata> {
+        self.metadata.as_ref().map(Metadata::common)
+    }
+
+    /// Name of the file the element was extracted from.
+    pub fn filename(&self) -> Option<&str> {
+        self.common_metadata()?.filename.as_deref()
+    }
+
+    /// MIME type of the file the element was extracted from.
+    pub fn filetype(&self) -> Option<&str> {
+        self.metadata.as_ref()?.filetype()
+    }
+
+    /// Page number of the element, for the formats that carry one.
+    pub fn page_number(&self) -> Option<u32> {
+        self.metadata.as_ref()?.page_number()
+    }
+
+    /// ID of the parent element, if any.
+    pub fn parent_id(&self) -> Option<&ElementId> {
+        self.metadata.as_ref()?.parent_id()
+    }
+
+    /// The paged metadata of PDF, DOCX and PPTX elements, or `None` for other file types.
+    pub fn as_paged(&self) -> Option<&PagedDocument> {
+        self.metadata.as_ref()?.as_paged()
+    }
+
+    /// The PPTX metadata, or `None` for other file types.
+    pub fn as_pptx(&self) -> Option<&PptxMetadata> {
+        self.metadata.as_ref()?.as_pptx()
+    }
+
+    /// The XLSX metadata, or `None` for other file types.
+    pub fn as_excel(&self) -> Option<&ExcelMetadata> {
+        self.metadata.as_ref()?.as_excel()
+    }
+
+    /// The EML metadata, or `None` for other file types.
+    pub fn as_email(&self) -> Option<&EmailMetadata> {
+        self.metadata.as_ref()?.as_email()
+    }
+
+    /// The MSG metadata, or `None` for other file types.
+    pub fn as_msg(&self) -> Option<&MsgMetadata> {
+        self.metadata.as_ref()?.as_msg()
+    }
+
+    /// The DOC metadata, or `None` for other file types.
+    pub fn as_word_doc(&self) -> Option<&WordDocMetadata> {
+        self.metadata.as_ref()?.as_word_doc()
+    }
+
+    /// The HTML metadata, or `None` for other file types.
+    pub fn as_html(&self) -> Option<&HtmlMetadata> {
+        self.metadata.as_ref()?.as_html()
+    }
+
+    /// The EPUB metadata, or `None` for other file types.
+    pub fn as_epub(&self) -> Option<&EpubMetadata> {
+        self.metadata.as_ref()?.as_epub()
+    }
+
+    /// The metadata of plain text, CSV, Markdown, reStructuredText and Org elements, or `None`
+    /// for other file types.
+    pub fn as_text(&self) -> Option<&TextMetadata> {
+        self.metadata.as_ref()?.as_text()
+    }
+
+    /// Decodes the elements a chunk was formed from, from its `orig_elements` metadata.
+    ///
+    /// Returns `None` when the element has no `orig_elements`, e.g. because it isn't a chunk or
+    /// `include_orig_elements` wasn't set.
+    pub fn orig_elements(&self) -> Result<Option<ElementList>> {
+        self.common_metadata()
+            .and_then(|common| common.orig_elements.as_deref())
+            .map(orig::decode)
+            .transpose()
+    }
+
+    /// A reference to the source of the element, made of its filename, the pages it spans and
+    /// the URL of the source document.
+    ///
+    /// The pages of chunks are those of their [orig_elements](Self::orig_elements) when
+    /// available. Returns `None` when the element has neither a filename nor a URL.
+    pub fn citation(&self) -> Option<Citation> {
+        citation::cite(self)
+    }
+
+    /// The highest probability in the `detection_class_prob` metadata, i.e. the confidence of
+    /// the layout model in the element type. Only present for `hi_res` partitioning; `None` when
+    /// the field is missing or empty.
+    pub fn detection_confidence(&self) -> Option<f64> {
+        self.common_metadata()?
+            .detection_class_prob
+            .as_ref()?
+            .iter()
+            .copied()
+            .filter(|prob| !prob.is_nan())
+            .reduce(f64::max)
+    }
+
+    /// Number of whitespace separated words in the element text.
+    pub fn word_count(&self) -> usize {
+        self.text.split_whitespace().count()
+    }
+
+    /// Number of characters (Unicode scalar values, not bytes) in the element text.
+    pub fn char_count(&self) -> usize {
+        self.text.chars().count()
+    }
+
+    /// Number of tokens in the element text for the tokenizer used by `model`, e.g. `gpt-4o`.
+    ///
+    /// Tokenizers are loaded once and cached.
+    #[cfg(feature = "tiktoken")]
+    pub fn token_count(&self, model: &str) -> Result<usize> {
+        let bpe = tokens::tokenizer(model)?;
+        Ok(tokens::count(&bpe, &self.text))
+    }
+
+    /// Parses the `text_as_html` metadata of a table element into a [Table].
+    ///
+    /// Returns `None` when the element has no `text_as_html` or it contains no table rows.
+    #[cfg(feature = "tables")]
+    pub fn parse_table(&self) -> Result<Option<Table>> {
+        let Some(html) = self
+            .common_metadata()
+            .and_then(|common| common.text_as_html.as_deref())
+        else {
+            return Ok(None);
+        };
+
+        let table = Table::parse(html)?;
+        Ok((!table.rows.is_empty()).then_some(table))
+    }
+
+    /// The text with its emphasized runs (`emphasized_text_contents` and
+    /// `emphasized_text_tags`) marked up as bold or italic. Runs that can't be found in the
+    /// text, or whose tag isn't bold or italic, are left as plain text.
+    ///
+    /// Each run is matched at its first occurrence from the run before it onwards, falling back
+    /// to earlier occurrences, so repeated substrings are matched in the order the runs are
+    /// listed. A bold and an italic run of the same text mark the same occurrence, and runs
+    /// that would cross an already matched run, rather than nest in it, are left out.
+    pub fn to_rich_text(&self, format: RichTextFormat) -> String {
+        rich_text::render(self, format)
+    }
+
+    /// Renders the `text_as_html` metadata of a table element as a GitHub-flavored Markdown
+    /// table, see [Table::to_markdown].
+    #[cfg(feature = "tables")]
+    pub fn table_markdown(&self) -> Result<Option<String>> {
+        Ok(self.parse_table()?.map(|table| table.to_markdown()))
+    }
+}
+
+/// Number of characters of the element text shown by [Element]'s [Display](fmt::Display)
+/// implementation, unless a precision is given.
+const DISPLAY_TEXT_WIDTH: usize = 80;
+
+/// Number of characters of the element ID shown by [Element]'s [Display](fmt::Display)
+/// implementation.
+const DISPLAY_ID_WIDTH: usize = 4;
+
+/// Keeps the first `width` characters of `text`, marking the truncation with `…`.
+fn truncate(text: &str, width: usize) -> Cow<'_, str> {
+    match text.char_indices().nth(width) {
+        Some((end, _)) => Cow::Owned(format!("{}…", &text[..end])),
+        None => Cow::Borrowed(text),
+    }
+}
+
+/// Displays the element on a single line, e.g.
+/// `[NarrativeText p.3 id=ab12…] "First 80 chars of text…"`.
+///
+/// The text is truncated to 80 characters, or to the precision if one is given (`{:.40}`). The
+/// alternate form (`{:#}`) shows the full ID and text, followed by the filename, filetype and
+/// parent ID on their own indented lines when present.
+impl fmt::Display for Element {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}", self.r#type)?;
+        if let Some(page_number) = self.page_number() {
+            write!(f, " p.{page_number}")?;
+        }
+
+        if f.alternate() {
+            write!(f, " id={}] {:?}", self.element_id, self.text)?;
+            for (key, value) in [
+                ("filename", self.filename()),
+                ("filetype", self.filetype()),
+                ("parent_id", self.parent_id().map(ElementId::as_str)),
+            ] {
+                if let Some(value) = value {
+                    write!(f, "\n  {key}: {value}")?;
+                }
+            }
+            return Ok(());
+        }
+
+        let width = f.precision().unwrap_or(DISPLAY_TEXT_WIDTH);
+        write!(
+            f,
+            " id={}] {:?}",
+            truncate(self.element_id.as_str(), DISPLAY_ID_WIDTH),
+            truncate(&self.text, width)
+        )
+    }
+}
+
+/// An element that failed to deserialize, see [ElementList::from_json_lenient].
+#[derive(Debug, Error)]
+#[error("Failed to parse element {index}: {source}")]
+pub struct ElementParseError {
+    /// Position of the element in the array.
+    pub index: usize,
+
+    /// The deserialization error.
+    pub source: serde_json::Error,
+
+    /// The JSON of the element, as received.
+    pub raw: String,
+}
+
+/// A list of elements, as returned by the partition endpoint.
+///
+/// Serializes as a bare JSON array. It dereferences to `[Element]`, so the usual slice methods
+/// are available, and converts to and from `Vec<Element>`.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct ElementList(Vec<Element>);
+
+/// JSON schema of a partition response, i.e. an [ElementList].
+///
+/// Metadata of known file types is described as a `oneOf` discriminated by `filetype`.
+#[cfg(feature = "schemars")]
+pub fn json_schema() -> schemars::schema::RootSchema {
+    schemars::schema_for!(ElementList)
+}
+
+impl ElementList {
+    /// Creates an empty list.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an element to the end of the list.
+    pub fn push(&mut self, element: Element) {
+        self.0.push(element);
+    }
+
+    /// Parses a JSON array of elements, deserializing each element on its own so that one
+    /// malformed element doesn't discard the rest.
+    ///
+    /// Returns the elements that could be parsed, in order, and an error for each that couldn't.
+    /// Fails only when `json` isn't a JSON array.
+    pub fn from_json_lenient(json: &str) -> Result<(Self, Vec<ElementParseError>)> {
+        Self::parse_lenient(json, false)
+    }
+
+    /// Parses a JSON array of elements, keeping the JSON of each element in [Element::raw].
+    pub fn from_json_with_raw(json: &str) -> Result<Self> {
+        let items: Vec<&RawValue> = serde_json::from_str(json)?;
+        items
+            .into_iter()
+            .map(|item| -> Result<Element> {
+                let mut element: Element = serde_json::from_str(item.get())?;
+                element.raw = Some(item.to_owned());
+                Ok(element)
+            })
+            .collect()
+    }
+
+    /// [from_json_lenient](Self::from_json_lenient), keeping the JSON of each element in
+    /// [Element::raw] when `keep_raw` is set.
+    pub(crate) fn parse_lenient(
+        json: &str,
+        keep_raw: bool,
+    ) -> Result<(Self, Vec<ElementParseError>)> {
+        let items: Vec<&RawValue> = serde_json::from_str(json)?;
+
+        let mut elements = ElementList::new();
+        let mut errors = Vec::new();
+        for (index, item) in items.into_iter().enumerate() {
+            match serde_json::from_str::<Element>(item.get()) {
+                Ok(mut element) => {
+                    if keep_raw {
+                        element.raw = Some(item.to_owned());
+                    }
+                    elements.push(element)
+                }
+                Err(source) => errors.push(ElementParseError {
+                    index,
+                    source,
+                    raw: item.get().to_string(),
+                }),
+            }
+        }
+        Ok((elements, errors))
+    }
+
+    /// Unwraps the list into its elements.
+    pub fn into_vec(self) -> Vec<Element> {
+        self.0
+    }
+
+    /// Elements whose type is one of `types`.
+    pub fn filter_types<'a>(
+        &'a self,
+        types: &'a [ElementType],
+    ) -> impl Iterator<Item = &'a Element> + 'a {
+        self.iter()
+            .filter(|element| types.contains(&element.r#type))
+    }
+
+    /// Elements whose type is none of `types`.
+    pub fn exclude_types<'a>(
+        &'a self,
+        types: &'a [ElementType],
+    ) -> impl Iterator<Item = &'a Element> + 'a {
+        self.iter()
+            .filter(|element| !types.contains(&element.r#type))
+    }
+
+    /// Elements whose OCR [confidence_score](CommonMetadata::confidence_score) is at least
+    /// `min`. Elements without a confidence score are left out.
+    ///
+    /// See [filter_min_confidence](Self::filter_min_confidence) for the confidence of the
+    /// layout detection instead.
+    pub fn filter_by_confidence(&self, min: f64) -> impl Iterator<Item = &Element> {
+        self.iter().filter(move |element| {
+            element
+                .common_metadata()
+                .and_then(|metadata| metadata.confidence_score)
+                .is_some_and(|score| score >= min)
+        })
+    }
+
+    /// Elements located on page `page_number`.
+    pub fn on_page(&self, page_number: u32) -> impl Iterator<Item = &Element> {
+        self.iter()
+            .filter(move |element| element.page_number() == Some(page_number))
+    }
+
+    /// Elements whose parent is the element with ID `parent_id`.
+    pub fn with_parent<'a>(&'a self, parent_id: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.iter()
+            .filter(move |element| element.parent_id().is_some_and(|id| id == parent_id))
+    }
+
+    /// The first element whose text contains `query`, ignoring case. An empty `query` matches
+    /// nothing.
+    pub fn find_by_text(&self, query: &str) -> Option<&Element> {
+        self.iter()
+            .find(|element| search::contains(&element.text, query, true))
+    }
+
+    /// Like [find_by_text](Self::find_by_text), but case-sensitive.
+    pub fn find_by_text_case_sensitive(&self, query: &str) -> Option<&Element> {
+        self.iter()
+            .find(|element| search::contains(&element.text, query, false))
+    }
+
+    /// Elements whose text contains `query`. An empty `query` matches nothing.
+    pub fn containing_text<'a>(
+        &'a self,
+        query: &'a str,
+        ignore_case: bool,
+    ) -> impl Iterator<Item = &'a Element> + 'a {
+        self.iter()
+            .filter(move |element| search::contains(&element.text, query, ignore_case))
+    }
+
+    /// Elements whose text matches `pattern`.
+    #[cfg(feature = "regex")]
+    pub fn matching<'a>(
+        &'a self,
+        pattern: &'a regex::Regex,
+    ) -> impl Iterator<Item = &'a Element> + 'a {
+        self.iter()
+            .filter(move |element| pattern.is_match(&element.text))
+    }
+
+    /// The elements whose text matches the regular expression `pattern`, ignoring case unless
+    /// the pattern turns that off with `(?-i)`.
+    #[cfg(feature = "regex")]
+    pub fn matching_regex(&self, pattern: &str) -> std::result::Result<ElementList, regex::Error> {
+        let pattern = regex::RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()?;
+        Ok(self.matching(&pattern).cloned().collect())
+    }
+
+    /// Finds the matches of `pattern` in the element texts, in order. As with
+    /// [Regex::find_iter](regex::Regex::find_iter), the matches within an element don't overlap.
+    #[cfg(feature = "regex")]
+    pub fn search(&self, pattern: &regex::Regex) -> Vec<SearchHit> {
+        search::regex(self, pattern)
+    }
+
+    /// Finds the occurrences of `needle` in the element texts, in order, including overlapping
+    /// occurrences. An empty `needle` matches nothing.
+    pub fn search_text(&self, needle: &str, ignore_case: bool) -> Vec<SearchHit> {
+        search::substring(self, needle, ignore_case)
+    }
+
+    /// Removes duplicate elements, keeping the first occurrence and preserving order.
+    ///
+    /// Exact duplicates are detected on the trimmed text; near-duplicates only when
+    /// [DedupeOptions::similarity_threshold] is set. Elements without text are always kept.
+    pub fn dedupe(&self, options: &DedupeOptions) -> ElementList {
+        dedupe::dedupe(self, options)
+    }
+
+    /// Sorts the elements into reading order, e.g. after merging the results of several
+    /// requests.
+    ///
+    /// Elements are ordered by page number, then top to bottom by the top edge of their bounding
+    /// box, then left to right by its left edge. Remaining ties keep their original order. Only
+    /// single-column layouts are handled.
+    ///
+    /// Elements without a page number take the page of the element before them, and elements
+    /// without coordinates take the position of the element before them on the same page, so
+    /// they stay right after it. Elements without a page number at the start of the list
+    /// count as being on page 0. Coordinates without a known coordinate system are assumed to
+    /// be in pixels.
+    pub fn sort_reading_order(&mut self) {
+        order::sort(&mut self.0);
+    }
+
+    /// Sorts the elements by element ID, for an ordering that doesn't depend on the API version,
+    /// e.g. to compare output in tests. Elements with the same ID keep their order.
+    ///
+    /// Only meaningful when `unique_element_ids` is off, so that the IDs are hashes of the
+    /// content; random UUIDs give an arbitrary order.
+    pub fn sort_by_element_id(&mut self) {
+        self.0
+            .sort_by(|a, b| a.element_id.as_str().cmp(b.element_id.as_str()));
+    }
+
+    /// Removes running headers, footers and page numbers, returning the removed elements in
+    /// order so they can be audited.
+    ///
+    /// Elements of one of [BoilerplateOptions::types] are always removed. When
+    /// [BoilerplateOptions::min_repeated_pages] is set, short texts repeated across that many
+    /// pages are removed too; texts are compared ignoring case, whitespace and digits.
+    pub fn strip_boilerplate(&mut self, options: &BoilerplateOptions) -> ElementList {
+        boilerplate::strip(&mut self.0, options)
+    }
+
+    /// Removes the elements whose [detection confidence](Element::detection_confidence) is
+    /// below `threshold`, returning them in document order.
+    ///
+    /// Elements without a detection confidence are kept when `keep_unscored` is set, and removed
+    /// otherwise.
+    pub fn filter_min_confidence(&mut self, threshold: f64, keep_unscored: bool) -> ElementList {
+        let (kept, removed) = std::mem::take(&mut self.0)
+            .into_iter()
+            .partition(|element| match element.detection_confidence() {
+                Some(confidence) => confidence >= threshold,
+                None => keep_unscored,
+            });
+        self.0 = kept;
+        ElementList(removed)
+    }
+
+    /// Cleans up extraction artifacts in the element texts in place, see [normalize]. The text
+    /// of `CodeSnippet` elements is left untouched.
+    pub fn normalize_text(&mut self, options: &NormalizeOptions) {
+        normalize::normalize(&mut self.0, options)
+    }
+
+    /// Splits the elements into `n` groups of consecutive elements, e.g. to distribute them
+    /// over workers. Unlike chunking, this splits by count and not by content size.
+    ///
+    /// Group sizes differ by at most one, with the larger groups first. Some groups are empty
+    /// when there are fewer than `n` elements, and no groups are returned when `n` is 0.
+    pub fn into_chunks(self, n: usize) -> Vec<ElementList> {
+        if n == 0 {
+            return Vec::new();
+        }
+
+        let (size, remainder) = (self.len() / n, self.len() % n);
+        let mut elements = self.0.into_iter();
+        (0..n)
+            .map(|i| {
+                let len = size + usize::from(i < remainder);
+                ElementList(elements.by_ref().take(len).collect())
+            })
+            .collect()
+    }
+
+    /// Converts each element into a generic [Document].
+    pub fn into_documents(self) -> Vec<Document> {
+        self.0.into_iter().map(Document::from).collect()
+    }
+
+    /// Converts each element into a generic [Document], cloning the element texts. Use
+    /// [into_documents](Self::into_documents) when the list isn't needed afterwards.
+    pub fn to_documents(&self) -> Vec<Document> {
+        self.iter().map(Document::from).collect()
+    }
+
+    /// Splits the elements into pages at `PageBreak` elements, which the API emits when
+    /// `include_page_breaks` is set. Useful for file types that don't report page numbers.
+    ///
+    /// Each break ends a page, so consecutive breaks yield an empty page and a break at the end
+    /// of the document doesn't start a new one. Without any breaks, all elements form a single
+    /// page. The breaks end their page when `keep_breaks` is set, and are dropped otherwise.
+    pub fn split_at_page_breaks(&self, keep_breaks: bool) -> Vec<ElementList> {
+        pages::split(self, keep_breaks)
+    }
+
+    /// Sets the page number of the elements that have none, counting pages from
+    /// `starting_page` by the `PageBreak` elements, see
+    /// [split_at_page_breaks](Self::split_at_page_breaks). A break gets the number of the page
+    /// it ends.
+    ///
+    /// For file types without a typed page number, it is kept in
+    /// [CommonMetadata::extra].
+    pub fn infer_page_numbers(&mut self, starting_page: u32) {
+        pages::infer_page_numbers(&mut self.0, starting_page)
+    }
+
+    /// The highest page number of any element, or 0 when no element has a page number.
+    pub fn page_count(&self) -> u32 {
+        self.iter()
+            .filter_map(Element::page_number)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Summary statistics of the elements, e.g. to compare the output of different partition
+    /// parameters.
+    pub fn stats(&self) -> ElementStats {
+        stats::compute(self)
+    }
+
+    /// Groups the elements by the ID of their parent, keeping their order within each group.
+    /// Elements without a parent are grouped under `None`.
+    ///
+    /// Unlike [hierarchy](Self::hierarchy) this only looks one level deep, and parents that
+    /// aren't in the list still get a group.
+    pub fn group_by_parent(&self) -> HashMap<Option<ElementId>, ElementList> {
+        let mut groups: HashMap<Option<ElementId>, ElementList> = HashMap::new();
+        for element in self.iter() {
+            groups
+                .entry(element.parent_id().cloned())
+                .or_default()
+                .push(element.clone());
+        }
+        groups
+    }
+
+    /// Builds the document hierarchy from the `parent_id` metadata of the elements.
+    ///
+    /// Elements without a parent, or whose parent isn't in the list, become roots. Parent cycles
+    /// are broken with a warning by making the first element of the cycle a root.
+    pub fn hierarchy(&self) -> Vec<ElementNode> {
+        hierarchy::build(self)
+    }
+
+    /// Builds the document outline from the `Title` elements and their `category_depth`, e.g.
+    /// for a table of contents or to filter chunks by section.
+    ///
+    /// A section spans the elements from its title up to the next title at the same or a
+    /// lower depth; elements before the first title aren't in any section. Titles deeper than
+    /// the one before them become its subsections even when levels are skipped. Titles without
+    /// a depth get the depth of the title before them, or 0 for the first title.
+    pub fn outline(&self) -> Vec<Section> {
+        outline::build(self)
+    }
+
+    /// Reorders the elements so that every element directly follows its parent, or its previous
+    /// sibling, according to the `parent_id` metadata.
+    ///
+    /// This is the pre-order traversal of the [hierarchy](Self::hierarchy): roots and siblings
+    /// keep their original relative order, and no element is added or removed.
+    pub fn apply_hierarchy_from_metadata(&self) -> ElementList {
+        depth_first(&self.hierarchy())
+            .map(|(_, node)| self[node.index].clone())
+            .collect()
+    }
+
+    /// Number of tokens in the text of each element for the tokenizer used by `model`.
+    #[cfg(feature = "tiktoken")]
+    pub fn token_counts(&self, model: &str) -> Result<Vec<usize>> {
+        let bpe = tokens::tokenizer(model)?;
+        Ok(self
+            .iter()
+            .map(|element| tokens::count(&bpe, &element.text))
+            .collect())
+    }
+
+    /// Indices of the elements (typically chunks) whose text exceeds `budget` tokens for the
+    /// tokenizer used by `model`.
+    #[cfg(feature = "tiktoken")]
+    pub fn over_token_budget(&self, model: &str, budget: usize) -> Result<Vec<usize>> {
+        Ok(self
+            .token_counts(model)?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, count)| (count > budget).then_some(index))
+            .collect())
+    }
+
+    /// The text of each element, in order.
+    pub fn text_iter(&self) -> impl Iterator<Item = &str> {
+        self.iter().map(|element| element.text.as_str())
+    }
+
+    /// The text of the document: the texts of the elements, separated by blank lines. Elements
+    /// without text, such as page breaks, are skipped.
+    pub fn to_text(&self) -> String {
+        window::to_text(self)
+    }
+
+    /// The characters `range` of [to_text](Self::to_text), along with the elements and pages
+    /// they came from, e.g. to build embedding windows that can be traced back to their source.
+    ///
+    /// `range` counts characters, not bytes, and is clipped to the length of the text. The
+    /// separators between elements are part of the window text but don't belong to any
+    /// element.
+    pub fn char_window(&self, range: Range<usize>) -> WindowSlice {
+        window::char_window(self, range)
+    }
+
+    /// Renders the elements as a Markdown document, e.g. for human review or as LLM input.
+    ///
+    /// Titles become headings (using `category_depth` for the level), list item runs become
+    /// bullet lists, code snippets become fenced code blocks and, with the `tables` feature,
+    /// tables are rendered from `text_as_html` when available.
+    pub fn to_markdown(&self) -> String {
+        markdown::render(self)
+    }
+
+    /// Converts the elements into an Arrow record batch with the
+    /// [record_batch_schema](Self::record_batch_schema).
+    ///
+    /// The `filename`, `filetype`, `page_number` and `parent_id` metadata get their own
+    /// column; the remaining metadata is stored as a JSON object string in the `metadata`
+    /// column, which is null when no other metadata is set.
+    #[cfg(feature = "arrow")]
+    pub fn to_record_batch(&self) -> Result<arrow_array::RecordBatch> {
+        arrow::to_record_batch(self)
+    }
+
+    /// Writes the [record batch](Self::to_record_batch) of the elements in the Arrow IPC file
+    /// format, e.g. for reading with polars, DataFusion or PyArrow.
+    #[cfg(feature = "arrow")]
+    pub fn to_arrow_ipc(&self) -> Result<Vec<u8>> {
+        arrow::to_ipc(self)
+    }
+
+    /// The schema of the batches made by [to_record_batch](Self::to_record_batch). Only the
+    /// `type`, `element_id` and `text` columns are non-nullable.
+    #[cfg(feature = "arrow")]
+    pub fn record_batch_schema() -> arrow_schema::SchemaRef {
+        arrow::schema()
+    }
+
+    /// Writes the elements to `writer` as CSV, one row per element, preceded by a header row.
+    pub fn to_csv<W: Write>(&self, writer: W, options: &CsvOptions) -> Result<()> {
+        csv::write(self, writer, options)
+    }
+
+    /// Stores the elements in the SQLite table `table`, creating it if it doesn't exist, and
+    /// returns the number of rows inserted.
+    ///
+    /// The table has the columns `element_id` (the primary key), `type`, `text`, `page_number`,
+    /// `filename` and `metadata_json`, the latter holding the metadata as JSON. The elements are
+    /// inserted in a single transaction, so none are inserted when one fails, e.g. because its
+    /// ID is already in the table.
+    #[cfg(feature = "sqlite")]
+    pub fn to_sqlite(&self, conn: &rusqlite::Connection, table: &str) -> Result<usize> {
+        sqlite::insert(self, conn, table)
+    }
+
+    /// The elements as [JSON Lines](https://jsonlines.org), see [write_jsonl].
+    pub fn to_jsonl_string(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        write_jsonl(self, &mut buffer)?;
+        Ok(String::from_utf8(buffer).expect("serde_json writes valid UTF-8"))
+    }
+}
+
+impl Deref for ElementList {
+    type Target = [Element];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
 }
 
-pub type ElementList = Vec<Element>;
+impl DerefMut for ElementList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<I: SliceIndex<[Element]>> Index<I> for ElementList {
+    type Output = I::Output;
+
+    fn index(&self, index: I) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<I: SliceIndex<[Element]>> IndexMut<I> for ElementList {
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl From<Vec<Element>> for ElementList {
+    fn from(elements: Vec<Element>) -> Self {
+        Self(elements)
+    }
+}
+
+impl From<ElementList> for Vec<Element> {
+    fn from(elements: ElementList) -> Self {
+        elements.0
+    }
+}
+
+impl FromIterator<Element> for ElementList {
+    fn from_iter<T: IntoIterator<Item = Element>>(iter: T) -> Self {
+        Self(Vec::from_iter(iter))
+    }
+}
+
+impl Extend<Element> for ElementList {
+    fn extend<T: IntoIterator<Item = Element>>(&mut self, iter: T) {
+        self.0.extend(iter);
+    }
+}
+
+impl IntoIterator for ElementList {
+    type Item = Element;
+    type IntoIter = std::vec::IntoIter<Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ElementList {
+    type Item = &'a Element;
+    type IntoIter = std::slice::Iter<'a, Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut ElementList {
+    type Item = &'a mut Element;
+    type IntoIter = std::slice::IterMut<'a, Element>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -81,9 +1157,10 @@ mod tests {
 
         let expected = Element {
             r#type: ElementType::NarrativeText,
-            element_id: "1".to_string(),
+            element_id: "1".into(),
             text: "Hello, world!".to_string(),
             metadata: None,
+            raw: None,
         };
 
         let element: Element = serde_json::from_str(json_str).unwrap();
@@ -102,9 +1179,10 @@ mod tests {
 
         let expected = Element {
             r#type: ElementType::Image,
-            element_id: "2".to_string(),
+            element_id: "2".into(),
             text: "An image element".to_string(),
             metadata: None,
+            raw: None,
         };
 
         let element: Element = serde_json::from_str(json_str).unwrap();
@@ -123,22 +1201,642 @@ mod tests {
 
         let expected = Element {
             r#type: ElementType::ListItem,
-            element_id: "3".to_string(),
+            element_id: "3".into(),
             text: "A list element.".to_string(),
             metadata: None,
+            raw: None,
         };
 
         let element: Element = serde_json::from_str(json_str).unwrap();
         assert_eq!(element, expected);
     }
 
+    fn paged_fixture() -> ElementList {
+        let json_str = r#"
+        [
+            {"type": "Title", "element_id": "t1", "text": "Intro",
+             "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "NarrativeText", "element_id": "n1", "text": "First paragraph.",
+             "metadata": {"filetype": "application/pdf", "page_number": 1, "parent_id": "t1"}},
+            {"type": "Footer", "element_id": "f1", "text": "Page 1",
+             "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "Title", "element_id": "t2", "text": "Results",
+             "metadata": {"filetype": "application/pdf", "page_number": 2}},
+            {"type": "ListItem", "element_id": "l1", "text": "Item one",
+             "metadata": {"filetype": "application/pdf", "page_number": 2, "parent_id": "t2"}},
+            {"type": "NarrativeText", "element_id": "n2", "text": "Second paragraph.",
+             "metadata": {"filetype": "application/pdf", "page_number": 2, "parent_id": "t2"}},
+            {"type": "Footer", "element_id": "f2", "text": "Page 2",
+             "metadata": {"filetype": "application/pdf", "page_number": 2}},
+            {"type": "UncategorizedText", "element_id": "u1", "text": "No metadata"}
+        ]
+        "#;
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    fn ids<'a>(elements: impl Iterator<Item = &'a Element>) -> Vec<&'a str> {
+        elements
+            .map(|element| element.element_id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_filter_types() {
+        let elements = paged_fixture();
+        let types = [ElementType::Title, ElementType::ListItem];
+        assert_eq!(ids(elements.filter_types(&types)), vec!["t1", "t2", "l1"]);
+        assert_eq!(elements.filter_types(&[]).count(), 0);
+    }
+
+    #[test]
+    fn test_exclude_types() {
+        let elements = paged_fixture();
+        let types = [ElementType::Footer, ElementType::UncategorizedText];
+        assert_eq!(
+            ids(elements.exclude_types(&types)),
+            vec!["t1", "n1", "t2", "l1", "n2"]
+        );
+        assert_eq!(elements.exclude_types(&[]).count(), elements.len());
+    }
+
+    #[test]
+    fn test_on_page() {
+        let elements = paged_fixture();
+        assert_eq!(ids(elements.on_page(1)), vec!["t1", "n1", "f1"]);
+        assert_eq!(ids(elements.on_page(2)), vec!["t2", "l1", "n2", "f2"]);
+        assert_eq!(elements.on_page(3).count(), 0);
+    }
+
+    #[test]
+    fn test_with_parent() {
+        let elements = paged_fixture();
+        assert_eq!(ids(elements.with_parent("t1")), vec!["n1"]);
+        assert_eq!(ids(elements.with_parent("t2")), vec!["l1", "n2"]);
+        assert_eq!(elements.with_parent("missing").count(), 0);
+    }
+
+    #[test]
+    fn test_element_list_serializes_as_array() {
+        let elements = paged_fixture();
+        let value = serde_json::to_value(&elements).unwrap();
+        assert_eq!(value.as_array().map(Vec::len), Some(8));
+        assert_eq!(value, serde_json::to_value(elements.to_vec()).unwrap());
+        assert_eq!(serde_json::to_string(&ElementList::new()).unwrap(), "[]");
+    }
+
+    #[test]
+    fn test_element_list_conversions() {
+        let mut elements = paged_fixture();
+        assert_eq!(elements[0].element_id, "t1");
+        assert_eq!(elements[1..3].len(), 2);
+
+        elements[0].text = "Changed".to_string();
+        for element in &mut elements {
+            element.text.make_ascii_uppercase();
+        }
+        assert_eq!(elements.first().unwrap().text, "CHANGED");
+
+        let titles: ElementList = elements
+            .iter()
+            .filter(|element| element.r#type == ElementType::Title)
+            .cloned()
+            .collect();
+        assert_eq!(titles.len(), 2);
+
+        let vec: Vec<Element> = titles.clone().into();
+        assert_eq!(ElementList::from(vec), titles);
+
+        let documents = titles.into_documents();
+        assert_eq!(documents[1].id, "t2");
+    }
+
+    #[test]
+    fn test_filter_min_confidence() {
+        let json_str = r#"
+        [
+            {"type": "Title", "element_id": "1", "text": "",
+             "metadata": {"filetype": "application/pdf", "detection_class_prob": [0.91]}},
+            {"type": "Header", "element_id": "2", "text": "",
+             "metadata": {"filetype": "application/pdf", "detection_class_prob": [0.2, 0.45, 0.1]}},
+            {"type": "Image", "element_id": "3", "text": "",
+             "metadata": {"filetype": "application/pdf", "detection_class_prob": [0.5]}},
+            {"type": "Footer", "element_id": "4", "text": "",
+             "metadata": {"filetype": "application/pdf", "detection_class_prob": []}},
+            {"type": "NarrativeText", "element_id": "5", "text": "",
+             "metadata": {"filetype": "application/pdf"}},
+            {"type": "NarrativeText", "element_id": "6", "text": ""}
+        ]
+        "#;
+        let elements: ElementList = serde_json::from_str(json_str).unwrap();
+        let confidences: Vec<Option<f64>> =
+            elements.iter().map(Element::detection_confidence).collect();
+        assert_eq!(
+            confidences,
+            vec![Some(0.91), Some(0.45), Some(0.5), None, None, None]
+        );
+
+        let ids = |elements: &ElementList| -> Vec<String> {
+            elements
+                .iter()
+                .map(|element| element.element_id.to_string())
+                .collect()
+        };
+
+        let mut kept = elements.clone();
+        let removed = kept.filter_min_confidence(0.5, true);
+        assert_eq!(ids(&kept), vec!["1", "3", "4", "5", "6"]);
+        assert_eq!(ids(&removed), vec!["2"]);
+
+        let mut kept = elements.clone();
+        let removed = kept.filter_min_confidence(0.5, false);
+        assert_eq!(ids(&kept), vec!["1", "3"]);
+        assert_eq!(ids(&removed), vec!["2", "4", "5", "6"]);
+    }
+
+    #[test]
+    fn test_filter_by_confidence() {
+        let elements: ElementList = serde_json::from_str(
+            r#"[
+                {"type": "NarrativeText", "element_id": "1", "text": "",
+                 "metadata": {"filetype": "application/pdf", "confidence_score": 0.93}},
+                {"type": "NarrativeText", "element_id": "2", "text": "",
+                 "metadata": {"filetype": "application/pdf", "confidence_score": 0.41}},
+                {"type": "NarrativeText", "element_id": "3", "text": "",
+                 "metadata": {"filetype": "image/png", "confidence_score": 0.8}},
+                {"type": "NarrativeText", "element_id": "4", "text": "",
+                 "metadata": {"filetype": "application/pdf"}},
+                {"type": "NarrativeText", "element_id": "5", "text": ""}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            elements[0].common_metadata().unwrap().confidence_score,
+            Some(0.93)
+        );
+
+        let ids: Vec<&str> = elements
+            .filter_by_confidence(0.8)
+            .map(|e| e.element_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1", "3"]);
+        assert_eq!(elements.filter_by_confidence(0.0).count(), 3);
+    }
+
+    #[test]
+    fn test_sort_by_element_id() {
+        let mut elements: ElementList = serde_json::from_str(
+            r#"[
+                {"type": "Title", "element_id": "c3", "text": "first c3"},
+                {"type": "Title", "element_id": "a1", "text": ""},
+                {"type": "Title", "element_id": "c3", "text": "second c3"},
+                {"type": "Title", "element_id": "B2", "text": ""}
+            ]"#,
+        )
+        .unwrap();
+        elements.sort_by_element_id();
+
+        let ids: Vec<&str> = elements.iter().map(|e| e.element_id.as_str()).collect();
+        assert_eq!(ids, vec!["B2", "a1", "c3", "c3"]);
+        assert_eq!(elements[2].text, "first c3");
+        assert_eq!(elements[3].text, "second c3");
+    }
+
+    #[test]
+    fn test_into_chunks() {
+        let elements = |count: usize| -> ElementList {
+            (0..count)
+                .map(|i| serde_json::json!({"type": "NarrativeText", "element_id": i.to_string(), "text": ""}))
+                .map(|value| serde_json::from_value::<Element>(value).unwrap())
+                .collect()
+        };
+        let sizes =
+            |chunks: &[ElementList]| chunks.iter().map(|chunk| chunk.len()).collect::<Vec<_>>();
+
+        let chunks = elements(10).into_chunks(4);
+        assert_eq!(sizes(&chunks), vec![3, 3, 2, 2]);
+        assert_eq!(chunks[1][0].element_id, "3");
+        assert_eq!(chunks[3][1].element_id, "9");
+
+        assert_eq!(sizes(&elements(9).into_chunks(3)), vec![3, 3, 3]);
+        assert_eq!(sizes(&elements(2).into_chunks(4)), vec![1, 1, 0, 0]);
+        assert_eq!(sizes(&elements(0).into_chunks(2)), vec![0, 0]);
+        assert!(elements(3).into_chunks(0).is_empty());
+    }
+
+    #[test]
+    fn test_from_json_with_raw() -> Result<()> {
+        let json = r#"[
+            {"text": "Body",   "type": "NarrativeText", "element_id": "1",
+             "metadata": {"filetype": "application/pdf", "page_number": 1, "score": 1.50}},
+            {"type": "Title", "element_id": "2", "text": "Title"}
+        ]"#;
+
+        let typed: ElementList = serde_json::from_str(json)?;
+        assert!(typed.iter().all(|element| element.raw.is_none()));
+
+        let raw = ElementList::from_json_with_raw(json)?;
+        assert_eq!(raw, typed);
+        assert_eq!(
+            raw[0].raw.as_ref().map(|raw| raw.get()),
+            Some(
+                r#"{"text": "Body",   "type": "NarrativeText", "element_id": "1",
+             "metadata": {"filetype": "application/pdf", "page_number": 1, "score": 1.50}}"#
+            )
+        );
+        assert_eq!(
+            serde_json::to_string(&raw[1])?,
+            r#"{"type": "Title", "element_id": "2", "text": "Title"}"#
+        );
+
+        // Without raw JSON the typed fields are serialized.
+        assert_eq!(
+            serde_json::to_string(&typed[1])?,
+            r#"{"type":"Title","element_id":"2","text":"Title","metadata":null}"#
+        );
+
+        let mut cleared = raw[1].clone();
+        cleared.raw = None;
+        assert_eq!(
+            serde_json::to_string(&cleared)?,
+            serde_json::to_string(&typed[1])?
+        );
+
+        assert!(ElementList::from_json_with_raw(r#"[{"type": "Title"}]"#).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_json_lenient() {
+        let json_str = r#"
+        [
+            {"type": "Title", "element_id": "1", "text": "Title"},
+            {"type": "NarrativeText", "element_id": "2", "text": 42},
+            {"type": "NarrativeText", "element_id": "3", "text": "Body"},
+            {"type": "NarrativeText", "element_id": "4", "text": "Bad metadata", "metadata": []}
+        ]
+        "#;
+        let (elements, errors) = ElementList::from_json_lenient(json_str).unwrap();
+
+        let ids: Vec<&str> = elements.iter().map(|e| e.element_id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "3"]);
+
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].index, 1);
+        assert_eq!(
+            errors[0].raw,
+            r#"{"type": "NarrativeText", "element_id": "2", "text": 42}"#
+        );
+        assert_eq!(errors[1].index, 3);
+        assert!(errors[1]
+            .to_string()
+            .starts_with("Failed to parse element 3"));
+
+        assert!(ElementList::from_json_lenient(r#"{"detail": "error"}"#).is_err());
+    }
+
+    #[test]
+    fn test_group_by_parent() {
+        let groups = paged_fixture().group_by_parent();
+        assert_eq!(groups.len(), 3);
+
+        let ids = |key: Option<&str>| -> Vec<String> {
+            groups[&key.map(ElementId::from)]
+                .iter()
+                .map(|element| element.element_id.to_string())
+                .collect()
+        };
+        assert_eq!(ids(None), vec!["t1", "f1", "t2", "f2", "u1"]);
+        assert_eq!(ids(Some("t1")), vec!["n1"]);
+        assert_eq!(ids(Some("t2")), vec!["l1", "n2"]);
+
+        assert!(ElementList::new().group_by_parent().is_empty());
+    }
+
+    #[test]
+    fn test_page_count() {
+        let elements = paged_fixture();
+        assert_eq!(elements.page_count(), 2);
+        assert_eq!(ElementList::from(elements[..3].to_vec()).page_count(), 1);
+        assert_eq!(ElementList::from(elements[7..].to_vec()).page_count(), 0);
+        assert_eq!(ElementList::new().page_count(), 0);
+    }
+
+    #[test]
+    fn test_text_iter() {
+        let elements = paged_fixture();
+        let texts: Vec<&str> = elements.text_iter().take(3).collect();
+        assert_eq!(texts, vec!["Intro", "First paragraph.", "Page 1"]);
+        assert_eq!(elements.text_iter().nth(2), Some("Page 1"));
+    }
+
+    #[test]
+    fn test_element_type_all_is_exhaustive() {
+        for element_type in ElementType::ALL {
+            // Adding a variant breaks this match, as a reminder to add it to `ALL`.
+            match element_type {
+                ElementType::Formula
+                | ElementType::FigureCaption
+                | ElementType::NarrativeText
+                | ElementType::ListItem
+                | ElementType::Title
+                | ElementType::Address
+                | ElementType::EmailAddress
+                | ElementType::Image
+                | ElementType::PageBreak
+                | ElementType::Table
+                | ElementType::Header
+                | ElementType::Footer
+                | ElementType::CodeSnippet
+                | ElementType::PageNumber
+                | ElementType::UncategorizedText
+                | ElementType::CompositeElement
+                | ElementType::TableChunk
+                | ElementType::Text
+                | ElementType::BulletedText
+                | ElementType::Abstract
+                | ElementType::Form
+                | ElementType::FieldName
+                | ElementType::Value
+                | ElementType::Link => {}
+                ElementType::Unknown(_) => panic!("Unknown is not an API element type"),
+            }
+        }
+        assert_eq!(ElementType::ALL.len(), 24);
+    }
+
+    #[test]
+    fn test_deserialize_unknown_element_type() {
+        let element_type: ElementType = serde_json::from_str(r#""Paragraph""#).unwrap();
+        assert_eq!(element_type, ElementType::Unknown("Paragraph".to_string()));
+        assert_eq!(element_type.to_string(), "Paragraph");
+        assert_eq!(
+            serde_json::to_string(&element_type).unwrap(),
+            r#""Paragraph""#
+        );
+        assert!("Unknown".parse::<ElementType>().is_err());
+    }
+
+    #[test]
+    fn test_unknown_element_type_round_trip() {
+        let json = r#"{"type":"FutureType","element_id":"1","text":"Hello","metadata":null}"#;
+        let element: Element = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            element.r#type,
+            ElementType::Unknown("FutureType".to_string())
+        );
+        assert_eq!(serde_json::to_string(&element).unwrap(), json);
+    }
+
+    #[test]
+    fn test_deserialize_new_element_types() {
+        let cases = [
+            ("Text", ElementType::Text),
+            ("BulletedText", ElementType::BulletedText),
+            ("Abstract", ElementType::Abstract),
+            ("Form", ElementType::Form),
+            ("Field-Name", ElementType::FieldName),
+            ("Value", ElementType::Value),
+            ("Link", ElementType::Link),
+            ("TableChunk", ElementType::TableChunk),
+        ];
+        for (name, expected) in cases {
+            let json = format!("\"{name}\"");
+            let element_type: ElementType = serde_json::from_str(&json).unwrap();
+            assert_eq!(element_type, expected);
+            assert_eq!(serde_json::to_string(&element_type).unwrap(), json);
+            assert_eq!(element_type.to_string(), name);
+            assert_eq!(name.parse::<ElementType>(), Ok(expected));
+        }
+        assert_eq!(
+            "field_name".parse::<ElementType>(),
+            Ok(ElementType::FieldName)
+        );
+    }
+
+    #[test]
+    fn test_element_type_round_trip() {
+        for element_type in ElementType::ALL {
+            let name = element_type.to_string();
+            assert_eq!(
+                serde_json::to_string(element_type).unwrap(),
+                format!("\"{name}\"")
+            );
+            assert_eq!(name.parse::<ElementType>(), Ok(element_type.clone()));
+            assert_eq!(
+                name.to_lowercase().parse::<ElementType>(),
+                Ok(element_type.clone())
+            );
+            assert_eq!(
+                name.to_uppercase().parse::<ElementType>(),
+                Ok(element_type.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn test_element_type_from_str_round_trip() {
+        for element_type in ElementType::ALL {
+            let name = element_type.as_str();
+            assert_eq!(ElementType::from(name), element_type.clone());
+            assert_eq!(
+                ElementType::from(name.to_lowercase().as_str()),
+                ElementType::Unknown(name.to_lowercase())
+            );
+        }
+        assert_eq!(
+            ElementType::from("Unknown"),
+            ElementType::Unknown("Unknown".to_string())
+        );
+        assert_eq!(
+            ElementType::from("narrative_text"),
+            ElementType::Unknown("narrative_text".to_string())
+        );
+        assert_eq!(
+            ElementType::from("FutureType"),
+            serde_json::from_str(r#""FutureType""#).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_element_type_aliases() {
+        assert_eq!(ElementType::NarrativeText.to_string(), "NarrativeText");
+        assert_eq!(
+            "narrative_text".parse::<ElementType>(),
+            Ok(ElementType::NarrativeText)
+        );
+        assert_eq!(
+            " Uncategorized_Text ".parse::<ElementType>(),
+            Ok(ElementType::UncategorizedText)
+        );
+        assert_eq!(
+            "page-break".parse::<ElementType>(),
+            Ok(ElementType::PageBreak)
+        );
+    }
+
+    #[test]
+    fn test_element_type_parse_error() {
+        let error = "Paragraph".parse::<ElementType>().unwrap_err();
+        assert_eq!(error.input, "Paragraph");
+        let message = error.to_string();
+        assert!(message.starts_with("Unknown element type 'Paragraph', expected one of: "));
+        assert!(message.contains("NarrativeText, ListItem"));
+    }
+
+    #[test]
+    fn test_metadata_getters() {
+        let json_str = r#"
+        [
+            {"type": "Title", "element_id": "1", "text": "",
+             "metadata": {"filetype": "application/pdf", "filename": "a.pdf", "page_number": 3, "parent_id": "0"}},
+            {"type": "Title", "element_id": "2", "text": "",
+             "metadata": {"filetype": "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet", "filename": "a.xlsx", "page_number": 2}},
+            {"type": "Title", "element_id": "3", "text": "",
+             "metadata": {"filetype": "message/rfc822", "filename": "a.eml", "parent_id": "1"}},
+            {"type": "Title", "element_id": "4", "text": "",
+             "metadata": {"filetype": "text/html", "filename": "a.html"}},
+            {"type": "Title", "element_id": "5", "text": "",
+             "metadata": {"filetype": "text/x-unknown", "filename": "a.txt", "parent_id": "2"}},
+            {"type": "Title", "element_id": "6", "text": ""}
+        ]
+        "#;
+        let elements: ElementList = serde_json::from_str(json_str).unwrap();
+        let getters: Vec<_> = elements
+            .iter()
+            .map(|e| {
+                (
+                    e.filename(),
+                    e.filetype(),
+                    e.page_number(),
+                    e.parent_id().map(ElementId::as_str),
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            getters,
+            vec![
+                (Some("a.pdf"), Some("application/pdf"), Some(3), Some("0")),
+                (
+                    Some("a.xlsx"),
+                    Some("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+                    Some(2),
+                    None
+                ),
+                (Some("a.eml"), Some("message/rfc822"), None, Some("1")),
+                (Some("a.html"), Some("text/html"), None, None),
+                (Some("a.txt"), Some("text/x-unknown"), None, Some("2")),
+                (None, None, None, None),
+            ]
+        );
+        assert!(elements[5].common_metadata().is_none());
+        assert_eq!(
+            elements[0]
+                .common_metadata()
+                .and_then(|c| c.filename.as_deref()),
+            Some("a.pdf")
+        );
+
+        assert_eq!(elements[0].as_paged().unwrap().page_number, Some(3));
+        assert!(elements[0].as_excel().is_none());
+        assert_eq!(elements[1].as_excel().unwrap().page_number, Some(2));
+        assert!(elements[2].as_email().is_some());
+        assert!(elements[2].as_html().is_none());
+        assert!(elements[3].as_html().is_some());
+        assert!(elements[4].as_paged().is_none());
+        assert!(elements[5].as_email().is_none());
+    }
+
+    fn display_fixture(text: &str) -> Element {
+        serde_json::from_value(serde_json::json!({
+            "type": "NarrativeText",
+            "element_id": "ab12cd34",
+            "text": text,
+            "metadata": {"filetype": "application/pdf", "filename": "report.pdf",
+                         "page_number": 3, "parent_id": "ef56"}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_display() {
+        let element = display_fixture("Short text.\nSecond line.");
+        assert_eq!(
+            element.to_string(),
+            r#"[NarrativeText p.3 id=ab12…] "Short text.\nSecond line.""#
+        );
+        assert_eq!(
+            format!("{element:#}"),
+            "[NarrativeText p.3 id=ab12cd34] \"Short text.\\nSecond line.\"\n  \
+             filename: report.pdf\n  filetype: application/pdf\n  parent_id: ef56"
+        );
+
+        let element: Element =
+            serde_json::from_str(r#"{"type": "PageBreak", "element_id": "abc", "text": ""}"#)
+                .unwrap();
+        assert_eq!(element.to_string(), r#"[PageBreak id=abc] """#);
+        assert_eq!(format!("{element:#}"), "[PageBreak id=abc] \"\"");
+    }
+
+    #[test]
+    fn test_display_truncation() {
+        let text = "a".repeat(DISPLAY_TEXT_WIDTH);
+        let element = display_fixture(&text);
+        assert!(element.to_string().ends_with(&format!("\"{text}\"")));
+
+        let element = display_fixture(&format!("{text}b"));
+        assert!(element.to_string().ends_with(&format!("\"{text}…\"")));
+
+        // Multibyte characters count as one and are never split.
+        let element = display_fixture("日本語のテキスト");
+        assert_eq!(
+            format!("{element:.3}"),
+            r#"[NarrativeText p.3 id=ab12…] "日本語…""#
+        );
+        let element = display_fixture("🦀🦀🦀");
+        assert_eq!(
+            format!("{element:.2}"),
+            r#"[NarrativeText p.3 id=ab12…] "🦀🦀…""#
+        );
+        assert_eq!(
+            format!("{element:.3}"),
+            r#"[NarrativeText p.3 id=ab12…] "🦀🦀🦀""#
+        );
+        assert_eq!(
+            format!("{element:.0}"),
+            r#"[NarrativeText p.3 id=ab12…] "…""#
+        );
+    }
+
+    #[test]
+    fn test_word_and_char_count() {
+        let element = Element {
+            r#type: ElementType::NarrativeText,
+            element_id: "1".into(),
+            text: "  Größe   über\talles\n ".to_string(),
+            metadata: None,
+            raw: None,
+        };
+        assert_eq!(element.word_count(), 3);
+        assert_eq!(element.char_count(), 22);
+        assert_ne!(element.char_count(), element.text.len());
+
+        let empty = Element {
+            text: String::new(),
+            ..element
+        };
+        assert_eq!(empty.word_count(), 0);
+        assert_eq!(empty.char_count(), 0);
+    }
+
     #[test]
     fn test_serialize() {
         let element = Element {
             r#type: ElementType::NarrativeText,
-            element_id: "1".to_string(),
+            element_id: "1".into(),
             text: "Hello, world!".to_string(),
             metadata: None,
+            raw: None,
         };
 
         let expected_json =
@@ -146,4 +1844,60 @@ mod tests {
         let json_str = serde_json::to_string(&element).unwrap();
         assert_eq!(json_str, expected_json);
     }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn test_json_schema_validates_fixture() {
+        let json_str = r#"
+        [
+            {
+                "type": "Title",
+                "element_id": "7f5b0c1e2d3a4b5c6d7e8f9a0b1c2d3e",
+                "text": "Quarterly Report",
+                "metadata": {
+                    "filetype": "application/pdf",
+                    "languages": ["eng"],
+                    "page_number": 1,
+                    "filename": "report.pdf",
+                    "coordinates": null
+                }
+            },
+            {
+                "type": "NarrativeText",
+                "element_id": "0a1b2c3d4e5f60718293a4b5c6d7e8f9",
+                "text": "Revenue grew in all regions.",
+                "metadata": {
+                    "filetype": "text/html",
+                    "languages": ["eng"],
+                    "page_number": 1,
+                    "parent_id": "7f5b0c1e2d3a4b5c6d7e8f9a0b1c2d3e",
+                    "link_urls": ["https://example.com"],
+                    "link_texts": ["example"]
+                }
+            },
+            {
+                "type": "UncategorizedText",
+                "element_id": "ffeeddccbbaa99887766554433221100",
+                "text": "plain text",
+                "metadata": {"filetype": "text/plain", "filename": "notes.txt"}
+            },
+            {"type": "PageBreak", "element_id": "00112233445566778899aabbccddeeff", "text": ""}
+        ]
+        "#;
+        let schema = serde_json::to_value(json_schema()).unwrap();
+        let extended = &schema["definitions"]["ExtendedMetadata"];
+        assert!(extended["oneOf"].as_array().is_some_and(|variants| variants
+            .iter()
+            .all(|v| v["properties"]["filetype"].get("enum").is_some())));
+        let validator = jsonschema::JSONSchema::compile(&schema).unwrap();
+
+        let instance: serde_json::Value = serde_json::from_str(json_str).unwrap();
+        assert!(validator.is_valid(&instance));
+
+        // The fixture should also deserialize, so the schema and the types agree.
+        let _: ElementList = serde_json::from_value(instance).unwrap();
+
+        let invalid = serde_json::json!([{"type": "Title", "element_id": 1, "text": ""}]);
+        assert!(!validator.is_valid(&invalid));
+    }
 }
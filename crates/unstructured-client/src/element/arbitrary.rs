@@ -0,0 +1,279 @@
+//! [Arbitrary] implementations for property tests of the element model.
+//!
+//! The generated values are restricted to those that survive a JSON round-trip unchanged:
+//! floats are multiples of 1/8, `extra` keys don't clash with modeled fields, and the
+//! `filetype` of [Metadata::UnknownFormat] is never one of [ExtendedMetadata::FILETYPES].
+
+use std::collections::BTreeMap;
+
+use proptest::prelude::*;
+
+use crate::element::{Element, ElementType};
+use crate::metadata::{
+    CommonMetadata, CoordinateSystem, Coordinates, EmailMetadata, EpubMetadata, ExcelMetadata,
+    ExtendedMetadata, HtmlMetadata, Metadata, MsgMetadata, PagedDocument, PptxMetadata,
+    TextMetadata, WordDocMetadata,
+};
+
+fn float() -> impl Strategy<Value = f64> {
+    (-8_000_000i32..8_000_000).prop_map(|n| f64::from(n) / 8.0)
+}
+
+fn texts() -> impl Strategy<Value = Option<Vec<String>>> {
+    proptest::option::of(prop::collection::vec(any::<String>(), 0..3))
+}
+
+fn coordinates() -> impl Strategy<Value = Coordinates> {
+    let system = prop_oneof![
+        Just(CoordinateSystem::PixelSpace),
+        Just(CoordinateSystem::PointSpace),
+        Just(CoordinateSystem::RelativeCoordinateSystem),
+        Just(CoordinateSystem::CartesianCoordinateSystem),
+        "Future[A-Z][a-z]{0,8}".prop_map(CoordinateSystem::Unknown),
+    ];
+    (
+        prop::collection::vec((float(), float()), 0..5),
+        proptest::option::of(system),
+        proptest::option::of(float()),
+        proptest::option::of(float()),
+    )
+        .prop_map(
+            |(points, system, layout_width, layout_height)| Coordinates {
+                points,
+                system,
+                layout_width,
+                layout_height,
+            },
+        )
+}
+
+fn extra() -> impl Strategy<Value = BTreeMap<String, serde_json::Value>> {
+    let value = prop_oneof![
+        Just(serde_json::Value::Null),
+        any::<bool>().prop_map(serde_json::Value::from),
+        any::<i64>().prop_map(serde_json::Value::from),
+        any::<String>().prop_map(serde_json::Value::from),
+    ];
+    prop::collection::btree_map("x_[a-z_]{1,12}", value, 0..3)
+}
+
+/// Common metadata without a `filetype`, which is set by the caller if needed.
+fn common() -> impl Strategy<Value = CommonMetadata> {
+    let string = || proptest::option::of(any::<String>());
+    let files = (string(), string(), string());
+    let layout = (
+        proptest::option::of(coordinates()),
+        proptest::option::of("[a-z0-9]{1,16}".prop_map(Into::into)),
+        proptest::option::of(any::<u32>()),
+        string(),
+        proptest::option::of(prop::collection::vec(
+            prop::sample::select(&["eng", "deu", "fra", "nld", "spa"][..])
+                .prop_map(|code| code.parse().unwrap()),
+            0..3,
+        )),
+    );
+    let emphasis = (texts(), texts(), proptest::option::of(any::<bool>()));
+    let detection = (
+        proptest::option::of(prop::collection::vec(float(), 0..3)),
+        proptest::option::of(float()),
+    );
+    let payloads = (string(), string(), string(), extra());
+
+    (files, layout, emphasis, detection, payloads).prop_map(
+        |(
+            (filename, file_directory, last_modified),
+            (coordinates, parent_id, category_depth, text_as_html, languages),
+            (emphasized_text_contents, emphasized_text_tags, is_continuation),
+            (detection_class_prob, confidence_score),
+            (image_base64, image_mime_type, orig_elements, extra),
+        )| CommonMetadata {
+            filename,
+            file_directory,
+            last_modified,
+            filetype: None,
+            coordinates,
+            parent_id,
+            category_depth,
+            text_as_html,
+            languages,
+            emphasized_text_contents,
+            emphasized_text_tags,
+            is_continuation,
+            detection_class_prob,
+            confidence_score,
+            image_base64,
+            image_mime_type,
+            orig_elements,
+            extra,
+        },
+    )
+}
+
+fn paged() -> impl Strategy<Value = PagedDocument> {
+    (
+        common(),
+        proptest::option::of(any::<u32>()),
+        proptest::option::of(any::<u32>()),
+    )
+        .prop_map(|(common, page_number, total_pages)| PagedDocument {
+            common,
+            page_number,
+            total_pages,
+        })
+}
+
+fn text() -> impl Strategy<Value = TextMetadata> {
+    common().prop_map(|common| TextMetadata { common })
+}
+
+fn extended() -> impl Strategy<Value = ExtendedMetadata> {
+    let string = || proptest::option::of(any::<String>());
+    prop_oneof![
+        paged().prop_map(ExtendedMetadata::PdfPage),
+        paged().prop_map(ExtendedMetadata::DocxPage),
+        (paged(), proptest::option::of(any::<bool>())).prop_map(|(paged, is_slide_notes)| {
+            ExtendedMetadata::PptPage(PptxMetadata {
+                paged,
+                is_slide_notes,
+            })
+        }),
+        (common(), proptest::option::of(any::<u32>()), string()).prop_map(
+            |(common, page_number, page_name)| {
+                ExtendedMetadata::XlsxPage(ExcelMetadata {
+                    common,
+                    page_number,
+                    page_name,
+                })
+            }
+        ),
+        (common(), texts(), texts(), string()).prop_map(|(common, sent_from, sent_to, subject)| {
+            ExtendedMetadata::Eml(EmailMetadata {
+                common,
+                sent_from,
+                sent_to,
+                subject,
+            })
+        }),
+        (
+            common(),
+            (string(), texts(), string()),
+            (texts(), texts(), texts())
+        )
+            .prop_map(
+                |(common, (attached_to_filename, sent_from, subject), (recipients, cc, bcc))| {
+                    ExtendedMetadata::Msg(MsgMetadata {
+                        common,
+                        attached_to_filename,
+                        sent_from,
+                        recipients,
+                        cc,
+                        bcc,
+                        subject,
+                    })
+                }
+            ),
+        (common(), proptest::option::of(any::<u32>()), string()).prop_map(
+            |(common, page_number, header_footer_type)| {
+                ExtendedMetadata::WordDoc(WordDocMetadata {
+                    common,
+                    page_number,
+                    header_footer_type,
+                })
+            }
+        ),
+        (common(), texts(), texts()).prop_map(|(common, link_urls, link_texts)| {
+            ExtendedMetadata::Html(HtmlMetadata {
+                common,
+                link_urls,
+                link_texts,
+            })
+        }),
+        (common(), string())
+            .prop_map(|(common, section)| ExtendedMetadata::Epub(EpubMetadata { common, section })),
+        text().prop_map(ExtendedMetadata::Text),
+        text().prop_map(ExtendedMetadata::Csv),
+        text().prop_map(ExtendedMetadata::Markdown),
+        text().prop_map(ExtendedMetadata::Rst),
+        text().prop_map(ExtendedMetadata::Org),
+    ]
+}
+
+impl Arbitrary for Metadata {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Metadata>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        let unknown_filetype = proptest::option::of(
+            "[a-z]{1,12}/[a-z.+-]{1,12}".prop_filter("filetype of a known format", |filetype| {
+                !ExtendedMetadata::FILETYPES.contains(&filetype.as_str())
+            }),
+        );
+        prop_oneof![
+            extended().prop_map(Metadata::KnownFormat),
+            (common(), unknown_filetype).prop_map(|(common, filetype)| {
+                Metadata::UnknownFormat(CommonMetadata { filetype, ..common })
+            }),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for ElementType {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<ElementType>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            prop::sample::select(ElementType::ALL),
+            "Future[A-Z][a-z]{0,8}".prop_map(ElementType::Unknown),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Element {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Element>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            any::<ElementType>(),
+            "[a-f0-9]{1,32}",
+            any::<String>(),
+            proptest::option::of(any::<Metadata>()),
+        )
+            .prop_map(|(r#type, element_id, text, metadata)| Element {
+                r#type,
+                element_id: element_id.into(),
+                text,
+                metadata,
+                raw: None,
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::element::{read_jsonl, Element, ElementList};
+
+    proptest! {
+        #[test]
+        fn test_element_list_json_round_trip(elements in prop::collection::vec(any::<Element>(), 0..6)) {
+            let elements = ElementList(elements);
+            let json = serde_json::to_string(&elements).unwrap();
+            let parsed: ElementList = serde_json::from_str(&json).unwrap();
+            prop_assert_eq!(parsed, elements);
+        }
+
+        #[test]
+        fn test_element_jsonl_lines(elements in prop::collection::vec(any::<Element>(), 0..6)) {
+            let elements = ElementList(elements);
+            let jsonl = elements.to_jsonl_string().unwrap();
+            prop_assert_eq!(jsonl.lines().count(), elements.len());
+            prop_assert_eq!(read_jsonl(jsonl.as_bytes()).unwrap(), elements);
+        }
+    }
+}
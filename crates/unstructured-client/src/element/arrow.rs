@@ -0,0 +1,186 @@
+use std::sync::{Arc, OnceLock};
+
+use arrow_array::{ArrayRef, RecordBatch, StringArray, UInt32Array};
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use serde_json::Value;
+
+use crate::element::{Element, ElementId};
+use crate::error::Result;
+
+/// Metadata fields that get their own column, and are left out of the `metadata` column.
+const COLUMN_FIELDS: &[&str] = &["filename", "filetype", "page_number", "parent_id"];
+
+static SCHEMA: OnceLock<SchemaRef> = OnceLock::new();
+
+pub(crate) fn schema() -> SchemaRef {
+    SCHEMA
+        .get_or_init(|| {
+            Arc::new(Schema::new(vec![
+                Field::new("type", DataType::Utf8, false),
+                Field::new("element_id", DataType::Utf8, false),
+                Field::new("text", DataType::Utf8, false),
+                Field::new("page_number", DataType::UInt32, true),
+                Field::new("filename", DataType::Utf8, true),
+                Field::new("filetype", DataType::Utf8, true),
+                Field::new("parent_id", DataType::Utf8, true),
+                Field::new("metadata", DataType::Utf8, true),
+            ]))
+        })
+        .clone()
+}
+
+/// The metadata of `element` without the fields stored in their own column, or `None` when
+/// nothing is left. Unset fields are left out.
+fn remaining_metadata(element: &Element) -> Result<Option<String>> {
+    let Some(metadata) = &element.metadata else {
+        return Ok(None);
+    };
+    let Value::Object(mut fields) = serde_json::to_value(metadata)? else {
+        return Ok(None);
+    };
+    fields.retain(|key, value| !value.is_null() && !COLUMN_FIELDS.contains(&key.as_str()));
+
+    if fields.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(serde_json::to_string(&fields)?))
+}
+
+pub(crate) fn to_record_batch(elements: &[Element]) -> Result<RecordBatch> {
+    let metadata = elements
+        .iter()
+        .map(remaining_metadata)
+        .collect::<Result<Vec<_>>>()?;
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            elements.iter().map(|e| e.r#type.to_string()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            elements.iter().map(|e| e.element_id.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            elements.iter().map(|e| e.text.as_str()),
+        )),
+        Arc::new(UInt32Array::from_iter(
+            elements.iter().map(Element::page_number),
+        )),
+        Arc::new(StringArray::from_iter(
+            elements.iter().map(Element::filename),
+        )),
+        Arc::new(StringArray::from_iter(
+            elements.iter().map(Element::filetype),
+        )),
+        Arc::new(StringArray::from_iter(
+            elements
+                .iter()
+                .map(|e| e.parent_id().map(ElementId::as_str)),
+        )),
+        Arc::new(StringArray::from(metadata)),
+    ];
+
+    Ok(RecordBatch::try_new(schema(), columns)?)
+}
+
+pub(crate) fn to_ipc(elements: &[Element]) -> Result<Vec<u8>> {
+    let batch = to_record_batch(elements)?;
+    let mut writer = FileWriter::try_new(Vec::new(), &batch.schema())?;
+    writer.write(&batch)?;
+    Ok(writer.into_inner()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+    use arrow_array::Array;
+
+    fn fixture() -> ElementList {
+        let json_str = r#"
+        [
+            {"type": "Title", "element_id": "1", "text": "Report",
+             "metadata": {"filetype": "application/pdf", "filename": "report.pdf",
+                          "page_number": 1, "languages": ["eng"]}},
+            {"type": "NarrativeText", "element_id": "2", "text": "Body",
+             "metadata": {"filetype": "application/pdf", "filename": "report.pdf",
+                          "page_number": 2, "parent_id": "1"}},
+            {"type": "Image", "element_id": "3", "text": ""}
+        ]
+        "#;
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    fn strings(batch: &RecordBatch, name: &str) -> Vec<Option<String>> {
+        batch
+            .column_by_name(name)
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap()
+            .iter()
+            .map(|value| value.map(String::from))
+            .collect()
+    }
+
+    #[test]
+    fn test_to_record_batch() -> Result<()> {
+        let batch = fixture().to_record_batch()?;
+
+        assert_eq!(batch.schema(), ElementList::record_batch_schema());
+        assert_eq!(batch.num_rows(), 3);
+        assert_eq!(
+            strings(&batch, "type"),
+            vec![
+                Some("Title".to_string()),
+                Some("NarrativeText".to_string()),
+                Some("Image".to_string())
+            ]
+        );
+        assert_eq!(
+            strings(&batch, "parent_id"),
+            vec![None, Some("1".to_string()), None]
+        );
+        assert_eq!(
+            strings(&batch, "metadata"),
+            vec![Some(r#"{"languages":["eng"]}"#.to_string()), None, None]
+        );
+
+        let page_numbers = batch
+            .column_by_name("page_number")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<UInt32Array>()
+            .unwrap();
+        assert_eq!(page_numbers.value(1), 2);
+        assert!(page_numbers.is_null(2));
+        assert_eq!(page_numbers.null_count(), 1);
+
+        for name in ["type", "element_id", "text"] {
+            assert_eq!(batch.column_by_name(name).unwrap().null_count(), 0);
+        }
+        assert_eq!(batch.column_by_name("filename").unwrap().null_count(), 1);
+        assert_eq!(batch.column_by_name("filetype").unwrap().null_count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_record_batch_empty() -> Result<()> {
+        let batch = ElementList::new().to_record_batch()?;
+        assert_eq!(batch.num_rows(), 0);
+        assert_eq!(batch.num_columns(), 8);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_arrow_ipc() -> Result<()> {
+        let elements = fixture();
+        let bytes = elements.to_arrow_ipc()?;
+
+        let reader = arrow_ipc::reader::FileReader::try_new(std::io::Cursor::new(bytes), None)?;
+        assert_eq!(reader.schema(), ElementList::record_batch_schema());
+        let batches = reader.collect::<std::result::Result<Vec<_>, _>>()?;
+        assert_eq!(batches, vec![elements.to_record_batch()?]);
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::element::{Element, ElementList, ElementType};
+
+/// Options for [ElementList::strip_boilerplate](crate::ElementList::strip_boilerplate).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoilerplateOptions {
+    /// Element types that are always removed.
+    pub types: Vec<ElementType>,
+
+    /// When set, short texts repeated on at least this many distinct pages are removed as well,
+    /// whatever their type, e.g. running headers typed as `UncategorizedText`.
+    pub min_repeated_pages: Option<usize>,
+
+    /// Maximum number of characters of a text for it to count as a repeated line.
+    pub max_repeated_chars: usize,
+}
+
+impl Default for BoilerplateOptions {
+    fn default() -> Self {
+        BoilerplateOptions {
+            types: vec![
+                ElementType::Header,
+                ElementType::Footer,
+                ElementType::PageNumber,
+            ],
+            min_repeated_pages: None,
+            max_repeated_chars: 80,
+        }
+    }
+}
+
+/// Key under which repeated lines are counted: lowercased, with whitespace collapsed and every
+/// digit replaced by `#`, so that `Page 3 of 10` and `Page 4 of 10` count as the same line.
+fn line_key(text: &str, max_chars: usize) -> Option<String> {
+    let text = text.trim();
+    if text.is_empty() || text.chars().count() > max_chars {
+        return None;
+    }
+    let key = text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_digit() { '#' } else { c })
+        .collect();
+    Some(key)
+}
+
+/// Keys of the lines appearing on at least `min_pages` distinct pages.
+fn repeated_lines(elements: &[Element], min_pages: usize, max_chars: usize) -> HashSet<String> {
+    let mut pages: HashMap<String, HashSet<u32>> = HashMap::new();
+    for element in elements {
+        if let (Some(page), Some(key)) = (element.page_number(), line_key(&element.text, max_chars))
+        {
+            pages.entry(key).or_default().insert(page);
+        }
+    }
+    pages
+        .into_iter()
+        .filter(|(_, pages)| pages.len() >= min_pages)
+        .map(|(key, _)| key)
+        .collect()
+}
+
+pub(crate) fn strip(elements: &mut Vec<Element>, options: &BoilerplateOptions) -> ElementList {
+    let repeated = match options.min_repeated_pages {
+        Some(min_pages) => repeated_lines(elements, min_pages, options.max_repeated_chars),
+        None => HashSet::new(),
+    };
+
+    let (removed, kept) = std::mem::take(elements).into_iter().partition(|element| {
+        options.types.contains(&element.r#type)
+            || line_key(&element.text, options.max_repeated_chars)
+                .is_some_and(|key| repeated.contains(&key))
+    });
+    *elements = kept;
+    ElementList::from(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> ElementList {
+        let json_str = r#"
+        [
+            {"type": "Header", "element_id": "h1", "text": "ACME Annual Report",
+             "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "Title", "element_id": "t1", "text": "Introduction",
+             "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "UncategorizedText", "element_id": "f1", "text": "Page 1 of 3",
+             "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "Text", "element_id": "h2", "text": "ACME  annual report",
+             "metadata": {"filetype": "application/pdf", "page_number": 2}},
+            {"type": "NarrativeText", "element_id": "n2", "text": "Introduction",
+             "metadata": {"filetype": "application/pdf", "page_number": 2}},
+            {"type": "UncategorizedText", "element_id": "f2", "text": "Page 2 of 3",
+             "metadata": {"filetype": "application/pdf", "page_number": 2}},
+            {"type": "Header", "element_id": "h3", "text": "ACME Annual Report",
+             "metadata": {"filetype": "application/pdf", "page_number": 3}},
+            {"type": "PageNumber", "element_id": "p3", "text": "3",
+             "metadata": {"filetype": "application/pdf", "page_number": 3}},
+            {"type": "UncategorizedText", "element_id": "f3", "text": "Page 3 of 3",
+             "metadata": {"filetype": "application/pdf", "page_number": 3}}
+        ]
+        "#;
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    fn ids(elements: &ElementList) -> Vec<&str> {
+        elements.iter().map(|e| e.element_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_strip_boilerplate_types() {
+        let mut elements = fixture();
+        let removed = elements.strip_boilerplate(&BoilerplateOptions::default());
+
+        assert_eq!(ids(&removed), vec!["h1", "h3", "p3"]);
+        assert_eq!(ids(&elements), vec!["t1", "f1", "h2", "n2", "f2", "f3"]);
+    }
+
+    #[test]
+    fn test_strip_boilerplate_repeated_lines() {
+        let mut elements = fixture();
+        let options = BoilerplateOptions {
+            min_repeated_pages: Some(3),
+            ..BoilerplateOptions::default()
+        };
+        let removed = elements.strip_boilerplate(&options);
+
+        // The header typed as Text and the page footers are detected by repetition, while
+        // "Introduction" only appears on two pages.
+        assert_eq!(
+            ids(&removed),
+            vec!["h1", "f1", "h2", "f2", "h3", "p3", "f3"]
+        );
+        assert_eq!(ids(&elements), vec!["t1", "n2"]);
+    }
+
+    #[test]
+    fn test_strip_boilerplate_repeated_lines_only() {
+        let mut elements = fixture();
+        let options = BoilerplateOptions {
+            types: vec![],
+            min_repeated_pages: Some(2),
+            max_repeated_chars: 12,
+        };
+        let removed = elements.strip_boilerplate(&options);
+
+        // "ACME Annual Report" is longer than 12 characters.
+        assert_eq!(ids(&removed), vec!["t1", "f1", "n2", "f2", "f3"]);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use crate::element::{ElementId, ElementList};
+use crate::error::Result;
+
+/// Lookups between chunks and the elements they were formed from.
+///
+/// Built from the `orig_elements` metadata of chunks, so the partition request needs
+/// `include_orig_elements` set. When chunks overlap, an element can belong to several chunks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChunkIndex {
+    /// Original elements per chunk ID.
+    elements: HashMap<String, ElementList>,
+
+    /// Chunk IDs per original element ID, in the order of the chunks.
+    chunks: HashMap<String, Vec<ElementId>>,
+}
+
+impl ChunkIndex {
+    /// Indexes the `orig_elements` of `chunks`. Chunks without `orig_elements` are left out.
+    ///
+    /// Fails when the `orig_elements` of a chunk can't be decoded.
+    pub fn build(chunks: &ElementList) -> Result<ChunkIndex> {
+        let mut index = ChunkIndex::default();
+        for chunk in chunks {
+            let Some(orig_elements) = chunk.orig_elements()? else {
+                continue;
+            };
+
+            for element in &orig_elements {
+                let chunk_ids = index
+                    .chunks
+                    .entry(element.element_id.to_string())
+                    .or_default();
+                if !chunk_ids.contains(&chunk.element_id) {
+                    chunk_ids.push(chunk.element_id.clone());
+                }
+            }
+            index
+                .elements
+                .insert(chunk.element_id.to_string(), orig_elements);
+        }
+        Ok(index)
+    }
+
+    /// The first chunk containing the element with ID `element_id`.
+    pub fn chunk_for(&self, element_id: &str) -> Option<&ElementId> {
+        self.chunks_for(element_id).first()
+    }
+
+    /// All chunks containing the element with ID `element_id`, in order. Holds more than one
+    /// chunk when chunks overlap, or when an element was split over several chunks.
+    pub fn chunks_for(&self, element_id: &str) -> &[ElementId] {
+        self.chunks.get(element_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// The elements the chunk with ID `chunk_id` was formed from.
+    pub fn elements_of(&self, chunk_id: &str) -> Option<&ElementList> {
+        self.elements.get(chunk_id)
+    }
+
+    /// The number of indexed chunks.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::orig::tests::encode;
+    use crate::error::ClientError;
+    use serde_json::json;
+
+    fn element(id: &str, text: &str) -> serde_json::Value {
+        json!({"type": "NarrativeText", "element_id": id, "text": text,
+               "metadata": {"filetype": "application/pdf", "page_number": 1}})
+    }
+
+    fn chunk(id: &str, orig_elements: Option<serde_json::Value>) -> serde_json::Value {
+        let mut metadata = json!({"filetype": "application/pdf", "page_number": 1});
+        if let Some(orig_elements) = orig_elements {
+            metadata["orig_elements"] = json!(encode(&orig_elements));
+        }
+        json!({"type": "CompositeElement", "element_id": id, "text": "…", "metadata": metadata})
+    }
+
+    fn fixture() -> ElementList {
+        // "b" was split over both chunks, as happens with overlap or long elements.
+        serde_json::from_value(json!([
+            chunk(
+                "c1",
+                Some(json!([element("a", "One."), element("b", "Two")]))
+            ),
+            chunk(
+                "c2",
+                Some(json!([element("b", "Two"), element("c", "Three.")]))
+            ),
+            chunk("c3", None)
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_elements_of() -> Result<()> {
+        let index = ChunkIndex::build(&fixture())?;
+        assert_eq!(index.len(), 2);
+
+        let ids: Vec<&str> = index
+            .elements_of("c2")
+            .unwrap()
+            .iter()
+            .map(|element| element.element_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["b", "c"]);
+        assert_eq!(index.elements_of("c1").unwrap()[0].text, "One.");
+        assert!(index.elements_of("c3").is_none());
+        assert!(index.elements_of("a").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_chunk_for() -> Result<()> {
+        let index = ChunkIndex::build(&fixture())?;
+
+        assert_eq!(index.chunk_for("a").map(ElementId::as_str), Some("c1"));
+        assert_eq!(index.chunk_for("c").map(ElementId::as_str), Some("c2"));
+        assert_eq!(
+            index.chunks_for("b"),
+            &[ElementId::from("c1"), ElementId::from("c2")]
+        );
+        assert_eq!(index.chunk_for("b").map(ElementId::as_str), Some("c1"));
+        assert_eq!(index.chunk_for("missing"), None);
+        assert!(index.chunks_for("missing").is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_invalid_orig_elements() {
+        let chunks: ElementList = serde_json::from_value(json!([
+            {"type": "CompositeElement", "element_id": "c1", "text": "…",
+             "metadata": {"filetype": "application/pdf", "orig_elements": "not base64!"}}
+        ]))
+        .unwrap();
+        assert!(matches!(
+            ChunkIndex::build(&chunks),
+            Err(ClientError::OrigElementsDecodeFailed(_))
+        ));
+        assert!(ChunkIndex::build(&ElementList::default())
+            .unwrap()
+            .is_empty());
+    }
+}
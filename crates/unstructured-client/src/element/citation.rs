@@ -0,0 +1,163 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::element::Element;
+
+/// A reference to the source of an element, e.g. `report.pdf, p. 12–13`. See
+/// [Element::citation].
+///
+/// Displays in a short form, with the source and pages, or with the alternate flag (`{:#}`) in
+/// a full form that adds the URL, e.g. `report.pdf, pp. 12–13, https://example.com/report.pdf`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Citation {
+    /// The filename, or the URL when the element has no filename.
+    pub source: String,
+
+    /// The pages the element spans.
+    pub pages: Option<RangeInclusive<u32>>,
+
+    /// The URL of the source document, from the `url` or `data_source.url` metadata.
+    pub url: Option<String>,
+}
+
+impl fmt::Display for Citation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)?;
+        match &self.pages {
+            Some(pages) if pages.start() == pages.end() => write!(f, ", p. {}", pages.start())?,
+            Some(pages) => {
+                let prefix = if f.alternate() { "pp." } else { "p." };
+                write!(f, ", {prefix} {}–{}", pages.start(), pages.end())?
+            }
+            None => {}
+        }
+        match &self.url {
+            Some(url) if f.alternate() && *url != self.source => write!(f, ", {url}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn url(element: &Element) -> Option<String> {
+    let extra = &element.common_metadata()?.extra;
+    extra
+        .get("url")
+        .or_else(|| extra.get("data_source")?.get("url"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::to_string)
+}
+
+/// The pages spanned by the original elements of a chunk, if it has any with a page number.
+fn orig_pages(element: &Element) -> Option<RangeInclusive<u32>> {
+    let elements = match element.orig_elements() {
+        Ok(elements) => elements?,
+        Err(e) => {
+            tracing::warn!(
+                "Ignoring orig_elements of element {}: {e}",
+                element.element_id
+            );
+            return None;
+        }
+    };
+    let pages = elements.iter().filter_map(Element::page_number);
+    let first = pages.clone().min()?;
+    let last = pages.max()?;
+    Some(first..=last)
+}
+
+pub(crate) fn cite(element: &Element) -> Option<Citation> {
+    let url = url(element);
+    let source = element
+        .filename()
+        .map(str::to_string)
+        .or_else(|| url.clone())?;
+    let pages = orig_pages(element).or_else(|| element.page_number().map(|page| page..=page));
+
+    Some(Citation { source, pages, url })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::orig::tests::encode;
+
+    fn element(metadata: serde_json::Value) -> Element {
+        serde_json::from_value(serde_json::json!({
+            "type": "CompositeElement",
+            "element_id": "chunk",
+            "text": "Some text",
+            "metadata": metadata
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_citation_single_page() {
+        let citation = element(serde_json::json!({
+            "filetype": "application/pdf", "filename": "report.pdf", "page_number": 12
+        }))
+        .citation()
+        .unwrap();
+
+        assert_eq!(
+            citation,
+            Citation {
+                source: "report.pdf".to_string(),
+                pages: Some(12..=12),
+                url: None,
+            }
+        );
+        assert_eq!(citation.to_string(), "report.pdf, p. 12");
+        assert_eq!(format!("{citation:#}"), "report.pdf, p. 12");
+    }
+
+    #[test]
+    fn test_citation_chunk_spanning_pages() {
+        let orig_elements = encode(&serde_json::json!([
+            {"type": "NarrativeText", "element_id": "a", "text": "First",
+             "metadata": {"filetype": "application/pdf", "page_number": 13}},
+            {"type": "NarrativeText", "element_id": "b", "text": "Second",
+             "metadata": {"filetype": "application/pdf", "page_number": 12}},
+            {"type": "Image", "element_id": "c", "text": ""}
+        ]));
+        let citation = element(serde_json::json!({
+            "filetype": "application/pdf", "filename": "report.pdf", "page_number": 12,
+            "orig_elements": orig_elements,
+            "data_source": {"url": "https://example.com/report.pdf"}
+        }))
+        .citation()
+        .unwrap();
+
+        assert_eq!(citation.pages, Some(12..=13));
+        assert_eq!(citation.to_string(), "report.pdf, p. 12–13");
+        assert_eq!(
+            format!("{citation:#}"),
+            "report.pdf, pp. 12–13, https://example.com/report.pdf"
+        );
+    }
+
+    #[test]
+    fn test_citation_fallbacks() {
+        // Undecodable orig_elements fall back to the page number of the chunk.
+        let citation = element(serde_json::json!({
+            "filetype": "application/pdf", "filename": "report.pdf", "page_number": 4,
+            "orig_elements": "not base64!"
+        }))
+        .citation()
+        .unwrap();
+        assert_eq!(citation.pages, Some(4..=4));
+
+        // The URL is the source when there is no filename.
+        let citation = element(serde_json::json!({
+            "filetype": "text/html", "url": "https://example.com"
+        }))
+        .citation()
+        .unwrap();
+        assert_eq!(format!("{citation:#}"), "https://example.com");
+
+        assert_eq!(
+            element(serde_json::json!({"filetype": "text/html"})).citation(),
+            None
+        );
+    }
+}
@@ -0,0 +1,243 @@
+use std::io::Write;
+
+use crate::element::{Element, ElementId};
+use crate::error::Result;
+
+/// A column in the CSV export of an element list.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvColumn {
+    /// The element type, e.g. `NarrativeText`.
+    Type,
+    ElementId,
+    Text,
+    PageNumber,
+    Filename,
+    Filetype,
+    ParentId,
+    /// The element coordinates, flattened according to [CoordinatesFormat].
+    Coordinates,
+}
+
+/// How coordinates are flattened into CSV columns.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CoordinatesFormat {
+    /// A single `coordinates` column holding the coordinates as JSON.
+    Raw,
+
+    /// Four `x0`, `y0`, `x1`, `y1` columns holding the bounding box of the coordinates.
+    #[default]
+    BoundingBox,
+}
+
+/// Options for [ElementList::to_csv](crate::ElementList::to_csv).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvOptions {
+    /// Columns to write, in order.
+    pub columns: Vec<CsvColumn>,
+
+    /// How to flatten the [CsvColumn::Coordinates] column.
+    pub coordinates: CoordinatesFormat,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            columns: vec![
+                CsvColumn::Type,
+                CsvColumn::PageNumber,
+                CsvColumn::Text,
+                CsvColumn::Filename,
+                CsvColumn::ParentId,
+                CsvColumn::Coordinates,
+            ],
+            coordinates: CoordinatesFormat::default(),
+        }
+    }
+}
+
+impl CsvColumn {
+    fn headers(self, coordinates: CoordinatesFormat) -> &'static [&'static str] {
+        match self {
+            CsvColumn::Type => &["type"],
+            CsvColumn::ElementId => &["element_id"],
+            CsvColumn::Text => &["text"],
+            CsvColumn::PageNumber => &["page_number"],
+            CsvColumn::Filename => &["filename"],
+            CsvColumn::Filetype => &["filetype"],
+            CsvColumn::ParentId => &["parent_id"],
+            CsvColumn::Coordinates => match coordinates {
+                CoordinatesFormat::Raw => &["coordinates"],
+                CoordinatesFormat::BoundingBox => &["x0", "y0", "x1", "y1"],
+            },
+        }
+    }
+
+    fn push_fields(
+        self,
+        element: &Element,
+        coordinates: CoordinatesFormat,
+        record: &mut Vec<String>,
+    ) {
+        let common = element.common_metadata();
+
+        match self {
+            CsvColumn::Type => record.push(element.r#type.to_string()),
+            CsvColumn::ElementId => record.push(element.element_id.to_string()),
+            CsvColumn::Text => record.push(element.text.clone()),
+            CsvColumn::PageNumber => record.push(
+                element
+                    .page_number()
+                    .map(|page| page.to_string())
+                    .unwrap_or_default(),
+            ),
+            CsvColumn::Filename => record.push(element.filename().unwrap_or_default().to_string()),
+            CsvColumn::Filetype => record.push(element.filetype().unwrap_or_default().to_string()),
+            CsvColumn::ParentId => record.push(
+                element
+                    .parent_id()
+                    .map(ElementId::to_string)
+                    .unwrap_or_default(),
+            ),
+            CsvColumn::Coordinates => {
+                let value = common.and_then(|c| c.coordinates.as_ref());
+                match coordinates {
+                    CoordinatesFormat::Raw => record.push(
+                        value
+                            .and_then(|c| serde_json::to_string(c).ok())
+                            .unwrap_or_default(),
+                    ),
+                    CoordinatesFormat::BoundingBox => match value.and_then(|c| c.bounding_box()) {
+                        Some(bbox) => record.extend(
+                            [bbox.x0, bbox.y0, bbox.x1, bbox.y1]
+                                .iter()
+                                .map(f64::to_string),
+                        ),
+                        None => record.extend(std::iter::repeat_n(String::new(), 4)),
+                    },
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn write<W: Write>(elements: &[Element], writer: W, options: &CsvOptions) -> Result<()> {
+    let mut csv_writer = ::csv::Writer::from_writer(writer);
+
+    let headers = options
+        .columns
+        .iter()
+        .flat_map(|column| column.headers(options.coordinates));
+    csv_writer.write_record(headers)?;
+
+    let mut record = Vec::new();
+    for element in elements {
+        record.clear();
+        for column in &options.columns {
+            column.push_fields(element, options.coordinates, &mut record);
+        }
+        csv_writer.write_record(&record)?;
+    }
+
+    csv_writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    fn fixture() -> ElementList {
+        let json_str = r#"
+        [
+            {"type": "Title", "element_id": "1", "text": "Report, \"final\"",
+             "metadata": {"filetype": "application/pdf", "filename": "report.pdf",
+                          "page_number": 1, "coordinates": {"points": [[10, 20], [10, 40], [30, 40], [30, 20]],
+                                          "system": "PixelSpace"}}},
+            {"type": "NarrativeText", "element_id": "2", "text": "Line one\nline two",
+             "metadata": {"filetype": "application/pdf", "filename": "report.pdf",
+                          "page_number": 2, "parent_id": "1"}},
+            {"type": "Image", "element_id": "3", "text": ""}
+        ]
+        "#;
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    fn read_back(bytes: &[u8]) -> Vec<Vec<String>> {
+        ::csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(bytes)
+            .records()
+            .map(|r| r.unwrap().iter().map(String::from).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_write_default_options() -> Result<()> {
+        let mut buffer = Vec::new();
+        write(&fixture(), &mut buffer, &CsvOptions::default())?;
+        let rows = read_back(&buffer);
+
+        assert_eq!(rows.len(), 4);
+        assert!(rows.iter().all(|row| row.len() == 9));
+        assert_eq!(
+            rows[0],
+            vec![
+                "type",
+                "page_number",
+                "text",
+                "filename",
+                "parent_id",
+                "x0",
+                "y0",
+                "x1",
+                "y1"
+            ]
+        );
+        assert_eq!(
+            rows[1],
+            vec![
+                "Title",
+                "1",
+                "Report, \"final\"",
+                "report.pdf",
+                "",
+                "10",
+                "20",
+                "30",
+                "40"
+            ]
+        );
+        assert_eq!(rows[2][2], "Line one\nline two");
+        assert_eq!(rows[2][4], "1");
+        assert_eq!(rows[3], vec!["Image", "", "", "", "", "", "", "", ""]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_custom_columns() -> Result<()> {
+        let options = CsvOptions {
+            columns: vec![
+                CsvColumn::ElementId,
+                CsvColumn::Filetype,
+                CsvColumn::Coordinates,
+            ],
+            coordinates: CoordinatesFormat::Raw,
+        };
+        let mut buffer = Vec::new();
+        write(&fixture(), &mut buffer, &options)?;
+        let rows = read_back(&buffer);
+
+        assert_eq!(rows[0], vec!["element_id", "filetype", "coordinates"]);
+        assert_eq!(
+            rows[1],
+            vec![
+                "1",
+                "application/pdf",
+                r#"{"points":[[10.0,20.0],[10.0,40.0],[30.0,40.0],[30.0,20.0]],"system":"PixelSpace","layout_width":null,"layout_height":null}"#
+            ]
+        );
+        assert_eq!(rows[3], vec!["3", "", ""]);
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashSet;
+
+use crate::element::{Element, ElementList, ElementType};
+
+/// Options for [ElementList::dedupe](crate::ElementList::dedupe).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DedupeOptions {
+    /// Element types to deduplicate. All types are considered when empty.
+    pub types: Vec<ElementType>,
+
+    /// When set, elements whose text has a Jaccard similarity (over word shingles) of at least
+    /// this value with an earlier element are removed as near-duplicates. Only exact duplicates
+    /// are removed when `None`.
+    pub similarity_threshold: Option<f64>,
+
+    /// Number of consecutive words per shingle used for near-duplicate detection.
+    pub shingle_size: usize,
+}
+
+impl Default for DedupeOptions {
+    fn default() -> Self {
+        DedupeOptions {
+            types: vec![],
+            similarity_threshold: None,
+            shingle_size: 3,
+        }
+    }
+}
+
+impl DedupeOptions {
+    /// Options targeting the running headers, footers and page numbers repeated on every page.
+    pub fn boilerplate() -> Self {
+        DedupeOptions {
+            types: vec![
+                ElementType::Header,
+                ElementType::Footer,
+                ElementType::PageNumber,
+            ],
+            ..DedupeOptions::default()
+        }
+    }
+}
+
+/// Lowercased word n-grams of `text`. Texts shorter than `size` words form a single shingle.
+pub(super) fn shingles(text: &str, size: usize) -> HashSet<String> {
+    let words: Vec<String> = text.split_whitespace().map(str::to_lowercase).collect();
+    if words.len() <= size.max(1) {
+        return HashSet::from([words.join(" ")]);
+    }
+    words.windows(size.max(1)).map(|w| w.join(" ")).collect()
+}
+
+pub(super) fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+pub(crate) fn dedupe(elements: &[Element], options: &DedupeOptions) -> ElementList {
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut kept_shingles: Vec<HashSet<String>> = Vec::new();
+
+    elements
+        .iter()
+        .filter(|element| {
+            let text = element.text.trim();
+            let targeted = options.types.is_empty() || options.types.contains(&element.r#type);
+            // Elements without text, such as page breaks, are never duplicates.
+            if !targeted || text.is_empty() {
+                return true;
+            }
+
+            if !seen.insert(text) {
+                return false;
+            }
+
+            if let Some(threshold) = options.similarity_threshold {
+                let current = shingles(text, options.shingle_size);
+                if kept_shingles
+                    .iter()
+                    .any(|kept| jaccard(kept, &current) >= threshold)
+                {
+                    return false;
+                }
+                kept_shingles.push(current);
+            }
+
+            true
+        })
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> ElementList {
+        let json_str = r#"
+        [
+            {"type": "Header", "element_id": "h1", "text": "ACME Corp Annual Report"},
+            {"type": "Title", "element_id": "t1", "text": "Introduction"},
+            {"type": "NarrativeText", "element_id": "n1", "text": "The year in review."},
+            {"type": "PageNumber", "element_id": "p1", "text": "1"},
+            {"type": "PageBreak", "element_id": "b1", "text": ""},
+            {"type": "Header", "element_id": "h2", "text": "ACME Corp Annual Report"},
+            {"type": "NarrativeText", "element_id": "n2", "text": "The year in review."},
+            {"type": "PageNumber", "element_id": "p2", "text": "2"},
+            {"type": "PageBreak", "element_id": "b2", "text": ""},
+            {"type": "Header", "element_id": "h3", "text": "ACME Corp  annual report  "},
+            {"type": "Header", "element_id": "h4", "text": "ACME Corp Annual Report 2023"},
+            {"type": "NarrativeText", "element_id": "n3", "text": "Closing remarks."}
+        ]
+        "#;
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    fn ids(elements: &ElementList) -> Vec<&str> {
+        elements.iter().map(|e| e.element_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_dedupe_exact() {
+        let deduped = dedupe(&fixture(), &DedupeOptions::default());
+        assert_eq!(
+            ids(&deduped),
+            vec!["h1", "t1", "n1", "p1", "b1", "p2", "b2", "h3", "h4", "n3"]
+        );
+    }
+
+    #[test]
+    fn test_dedupe_boilerplate_only() {
+        let deduped = dedupe(&fixture(), &DedupeOptions::boilerplate());
+        assert_eq!(
+            ids(&deduped),
+            vec!["h1", "t1", "n1", "p1", "b1", "n2", "p2", "b2", "h3", "h4", "n3"]
+        );
+    }
+
+    #[test]
+    fn test_dedupe_near_duplicates() {
+        let options = DedupeOptions {
+            similarity_threshold: Some(0.6),
+            ..DedupeOptions::boilerplate()
+        };
+        let deduped = dedupe(&fixture(), &options);
+        assert_eq!(
+            ids(&deduped),
+            vec!["h1", "t1", "n1", "p1", "b1", "n2", "p2", "b2", "n3"]
+        );
+    }
+
+    #[test]
+    fn test_jaccard() {
+        let a = shingles("a b c d", 2);
+        let b = shingles("a b c e", 2);
+        assert_eq!(a.len(), 3);
+        assert!((jaccard(&a, &b) - 0.5).abs() < 1e-9);
+        assert_eq!(jaccard(&a, &a), 1.0);
+        assert_eq!(shingles("short", 3), HashSet::from(["short".to_string()]));
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::element::dedupe::{jaccard, shingles};
+use crate::element::{Element, ElementList};
+
+/// Minimum word Jaccard similarity for two elements with different IDs to be considered the same
+/// element.
+const MIN_SIMILARITY: f64 = 0.5;
+
+/// A field that differs between two aligned elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangedField {
+    Type,
+    Text,
+    PageNumber,
+}
+
+/// An element present in both lists whose fields differ.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChangedElement<'a> {
+    pub old_index: usize,
+    pub new_index: usize,
+    pub old: &'a Element,
+    pub new: &'a Element,
+
+    /// The fields that differ, never empty.
+    pub fields: Vec<ChangedField>,
+}
+
+/// The differences between two element lists, see [diff].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ElementDiff<'a> {
+    /// Elements only in the new list, with their index in it.
+    pub added: Vec<(usize, &'a Element)>,
+
+    /// Elements only in the old list, with their index in it.
+    pub removed: Vec<(usize, &'a Element)>,
+
+    /// Elements in both lists whose type, text or page number differ, in new list order.
+    pub changed: Vec<ChangedElement<'a>>,
+}
+
+impl ElementDiff<'_> {
+    /// Whether the lists hold the same elements, ignoring order.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compares the elements of two partition results of the same document, e.g. before and after
+/// upgrading the API.
+///
+/// Elements are aligned by element ID first. The remaining elements, e.g. when the IDs are
+/// random UUIDs or hashes of the changed text, are aligned with the unaligned element of the
+/// old list whose text has the highest word Jaccard similarity, provided it is at least 0.5.
+/// Ties prefer an element of the same type, then the earliest one.
+pub fn diff<'a>(old: &'a ElementList, new: &'a ElementList) -> ElementDiff<'a> {
+    let mut old_by_id: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (index, element) in old.iter().enumerate().rev() {
+        old_by_id
+            .entry(element.element_id.as_str())
+            .or_default()
+            .push(index);
+    }
+
+    let mut aligned: Vec<Option<usize>> = new
+        .iter()
+        .map(|element| old_by_id.get_mut(element.element_id.as_str())?.pop())
+        .collect();
+
+    let mut unaligned_old: Vec<(usize, HashSet<String>)> = {
+        let taken: HashSet<usize> = aligned.iter().flatten().copied().collect();
+        old.iter()
+            .enumerate()
+            .filter(|(index, _)| !taken.contains(index))
+            .map(|(index, element)| (index, shingles(&element.text, 1)))
+            .collect()
+    };
+
+    for (new_index, element) in new.iter().enumerate() {
+        if aligned[new_index].is_some() {
+            continue;
+        }
+        let words = shingles(&element.text, 1);
+        let mut best: Option<(usize, f64, bool)> = None;
+        for (position, (old_index, old_words)) in unaligned_old.iter().enumerate() {
+            let similarity = jaccard(&words, old_words);
+            let same_type = old[*old_index].r#type == element.r#type;
+            let better = match best {
+                None => true,
+                Some((_, best_similarity, best_same_type)) => {
+                    similarity > best_similarity
+                        || (similarity == best_similarity && same_type && !best_same_type)
+                }
+            };
+            if similarity >= MIN_SIMILARITY && better {
+                best = Some((position, similarity, same_type));
+            }
+        }
+        if let Some((position, _, _)) = best {
+            aligned[new_index] = Some(unaligned_old.remove(position).0);
+        }
+    }
+
+    let mut result = ElementDiff {
+        removed: unaligned_old
+            .into_iter()
+            .map(|(index, _)| (index, &old[index]))
+            .collect(),
+        ..ElementDiff::default()
+    };
+
+    for (new_index, old_index) in aligned.into_iter().enumerate() {
+        let new_element = &new[new_index];
+        let Some(old_index) = old_index else {
+            result.added.push((new_index, new_element));
+            continue;
+        };
+
+        let old_element = &old[old_index];
+        let mut fields = Vec::new();
+        if old_element.r#type != new_element.r#type {
+            fields.push(ChangedField::Type);
+        }
+        if old_element.text != new_element.text {
+            fields.push(ChangedField::Text);
+        }
+        if old_element.page_number() != new_element.page_number() {
+            fields.push(ChangedField::PageNumber);
+        }
+        if !fields.is_empty() {
+            result.changed.push(ChangedElement {
+                old_index,
+                new_index,
+                old: old_element,
+                new: new_element,
+                fields,
+            });
+        }
+    }
+
+    result
+}
+
+/// Maximum number of characters of an element text shown by the [Display] implementation.
+const MAX_DISPLAYED_CHARS: usize = 60;
+
+fn excerpt(text: &str) -> String {
+    if text.chars().count() <= MAX_DISPLAYED_CHARS {
+        return format!("{text:?}");
+    }
+    let truncated: String = text.chars().take(MAX_DISPLAYED_CHARS).collect();
+    format!("{truncated:?}...")
+}
+
+fn page(element: &Element) -> String {
+    element
+        .page_number()
+        .map_or_else(|| "-".to_string(), |page| page.to_string())
+}
+
+/// Renders the diff for terminal review, with a line per removed (`-`) and added (`+`) element,
+/// and an indented line per changed field of modified (`~`) elements.
+impl fmt::Display for ElementDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes");
+        }
+
+        for (index, element) in &self.removed {
+            writeln!(
+                f,
+                "- [{index}] {}: {}",
+                element.r#type,
+                excerpt(&element.text)
+            )?;
+        }
+        for (index, element) in &self.added {
+            writeln!(
+                f,
+                "+ [{index}] {}: {}",
+                element.r#type,
+                excerpt(&element.text)
+            )?;
+        }
+        for change in &self.changed {
+            writeln!(
+                f,
+                "~ [{} -> {}] {}",
+                change.old_index, change.new_index, change.new.element_id
+            )?;
+            for field in &change.fields {
+                match field {
+                    ChangedField::Type => writeln!(
+                        f,
+                        "    type: {} -> {}",
+                        change.old.r#type, change.new.r#type
+                    )?,
+                    ChangedField::Text => writeln!(
+                        f,
+                        "    text: {} -> {}",
+                        excerpt(&change.old.text),
+                        excerpt(&change.new.text)
+                    )?,
+                    ChangedField::PageNumber => writeln!(
+                        f,
+                        "    page_number: {} -> {}",
+                        page(change.old),
+                        page(change.new)
+                    )?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementType;
+
+    fn elements(json: serde_json::Value) -> ElementList {
+        serde_json::from_value(json).unwrap()
+    }
+
+    fn old() -> ElementList {
+        elements(serde_json::json!([
+            {"type": "Title", "element_id": "a", "text": "Annual Report"},
+            {"type": "NarrativeText", "element_id": "b", "text": "Revenue grew by ten percent this year."},
+            {"type": "NarrativeText", "element_id": "c", "text": "Costs stayed flat."},
+            {"type": "Footer", "element_id": "d", "text": "Confidential"}
+        ]))
+    }
+
+    #[test]
+    fn test_identical() {
+        let elements = old();
+        let diff = diff(&elements, &elements);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "No changes\n");
+    }
+
+    #[test]
+    fn test_insertion() {
+        let old = old();
+        let mut new = old.clone().into_vec();
+        new.insert(
+            2,
+            serde_json::from_value(serde_json::json!(
+                {"type": "Table", "element_id": "t", "text": "Q1 10 Q2 12"}
+            ))
+            .unwrap(),
+        );
+        let new = ElementList::from(new);
+
+        let diff = diff(&old, &new);
+        assert_eq!(diff.added, vec![(2, &new[2])]);
+        assert!(diff.removed.is_empty());
+        assert!(diff.changed.is_empty());
+        assert_eq!(diff.to_string(), "+ [2] Table: \"Q1 10 Q2 12\"\n");
+    }
+
+    #[test]
+    fn test_deletion() {
+        let old = old();
+        let new = ElementList::from(old[..3].to_vec());
+
+        let diff = diff(&old, &new);
+        assert_eq!(diff.removed, vec![(3, &old[3])]);
+        assert!(diff.added.is_empty());
+        assert!(diff.changed.is_empty());
+        assert_eq!(diff.to_string(), "- [3] Footer: \"Confidential\"\n");
+    }
+
+    #[test]
+    fn test_type_change() {
+        let old = old();
+        let mut new = old.clone();
+        new[3].r#type = ElementType::Header;
+
+        let diff = diff(&old, &new);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(diff.changed[0].old_index, 3);
+        assert_eq!(diff.changed[0].new_index, 3);
+        assert_eq!(diff.changed[0].fields, vec![ChangedField::Type]);
+        assert_eq!(
+            diff.to_string(),
+            "~ [3 -> 3] d\n    type: Footer -> Header\n"
+        );
+    }
+
+    #[test]
+    fn test_aligns_by_text_when_ids_change() {
+        let old = old();
+        let new = elements(serde_json::json!([
+            {"type": "Title", "element_id": "x1", "text": "Annual Report"},
+            {"type": "NarrativeText", "element_id": "x2", "text": "Revenue grew by twelve percent this year."},
+            {"type": "NarrativeText", "element_id": "x3", "text": "Something else entirely."},
+            {"type": "Footer", "element_id": "x4", "text": "Confidential"}
+        ]));
+
+        let diff = diff(&old, &new);
+        assert_eq!(diff.added, vec![(2, &new[2])]);
+        assert_eq!(diff.removed, vec![(2, &old[2])]);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            (diff.changed[0].old_index, diff.changed[0].new_index),
+            (1, 1)
+        );
+        assert_eq!(diff.changed[0].fields, vec![ChangedField::Text]);
+        assert_eq!(
+            diff.to_string(),
+            "- [2] NarrativeText: \"Costs stayed flat.\"\n\
+             + [2] NarrativeText: \"Something else entirely.\"\n\
+             ~ [1 -> 1] x2\n    \
+             text: \"Revenue grew by ten percent this year.\" -> \"Revenue grew by twelve percent this year.\"\n"
+        );
+    }
+}
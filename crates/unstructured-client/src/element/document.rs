@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::element::Element;
+
+/// A generic `(id, text, metadata)` document, as expected by most vector stores and RAG
+/// pipelines.
+///
+/// Converting an [Element] flattens its metadata into the `metadata` map using these keys,
+/// omitting values that aren't present:
+///
+/// | Key            | Source                                 |
+/// |----------------|----------------------------------------|
+/// | `element_type` | [Element::r#type], e.g. `"Title"`      |
+/// | `filename`     | [Element::filename]                    |
+/// | `filetype`     | [Element::filetype]                    |
+/// | `page_number`  | [Element::page_number]                 |
+/// | `parent_id`    | [Element::parent_id]                   |
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Document {
+    /// The element ID.
+    pub id: String,
+
+    /// The element text.
+    pub text: String,
+
+    /// Flattened element metadata.
+    pub metadata: BTreeMap<String, Value>,
+}
+
+impl Document {
+    pub const ELEMENT_TYPE_KEY: &'static str = "element_type";
+    pub const FILENAME_KEY: &'static str = "filename";
+    pub const FILETYPE_KEY: &'static str = "filetype";
+    pub const PAGE_NUMBER_KEY: &'static str = "page_number";
+    pub const PARENT_ID_KEY: &'static str = "parent_id";
+
+    fn metadata_of(element: &Element) -> BTreeMap<String, Value> {
+        let mut metadata = BTreeMap::new();
+        metadata.insert(
+            Self::ELEMENT_TYPE_KEY.to_string(),
+            Value::from(element.r#type.to_string()),
+        );
+
+        let mut insert = |key: &str, value: Option<Value>| {
+            if let Some(value) = value {
+                metadata.insert(key.to_string(), value);
+            }
+        };
+        insert(Self::FILENAME_KEY, element.filename().map(Value::from));
+        insert(Self::FILETYPE_KEY, element.filetype().map(Value::from));
+        insert(
+            Self::PAGE_NUMBER_KEY,
+            element.page_number().map(Value::from),
+        );
+        insert(
+            Self::PARENT_ID_KEY,
+            element.parent_id().map(|id| Value::from(id.as_str())),
+        );
+
+        metadata
+    }
+}
+
+impl From<&Element> for Document {
+    fn from(element: &Element) -> Self {
+        Document {
+            id: element.element_id.to_string(),
+            text: element.text.clone(),
+            metadata: Document::metadata_of(element),
+        }
+    }
+}
+
+impl From<Element> for Document {
+    fn from(element: Element) -> Self {
+        let metadata = Document::metadata_of(&element);
+        Document {
+            id: element.element_id.into(),
+            text: element.text,
+            metadata,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+    use serde_json::json;
+
+    fn fixture() -> ElementList {
+        let json_str = r#"
+        [
+            {"type": "NarrativeText", "element_id": "abc", "text": "Some text.",
+             "metadata": {"filetype": "application/pdf", "filename": "report.pdf",
+                          "page_number": 4, "parent_id": "def", "languages": ["eng"]}},
+            {"type": "Title", "element_id": "xyz", "text": "No metadata"}
+        ]
+        "#;
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    #[test]
+    fn test_from_element() {
+        let element = fixture().into_iter().next().unwrap();
+        let expected = Document {
+            id: "abc".to_string(),
+            text: "Some text.".to_string(),
+            metadata: BTreeMap::from([
+                ("element_type".to_string(), json!("NarrativeText")),
+                ("filename".to_string(), json!("report.pdf")),
+                ("filetype".to_string(), json!("application/pdf")),
+                ("page_number".to_string(), json!(4)),
+                ("parent_id".to_string(), json!("def")),
+            ]),
+        };
+
+        assert_eq!(Document::from(&element), expected);
+        assert_eq!(Document::from(element), expected);
+    }
+
+    #[test]
+    fn test_to_documents() {
+        let documents = fixture().to_documents();
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[1].id, "xyz");
+        assert_eq!(
+            documents[1].metadata,
+            BTreeMap::from([("element_type".to_string(), json!("Title"))])
+        );
+    }
+}
@@ -0,0 +1,233 @@
+use std::collections::HashMap;
+
+use crate::element::Element;
+
+/// A node in the element hierarchy built from `parent_id` metadata.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementNode {
+    /// Index of the element in the list the hierarchy was built from.
+    pub index: usize,
+
+    /// Child nodes, in document order.
+    pub children: Vec<ElementNode>,
+}
+
+impl ElementNode {
+    /// Pre-order depth-first traversal of this node and its descendants.
+    pub fn depth_first(&self) -> DepthFirst<'_> {
+        DepthFirst {
+            stack: vec![(0, self)],
+        }
+    }
+}
+
+/// Pre-order depth-first traversal of every tree in `forest`.
+pub fn depth_first(forest: &[ElementNode]) -> DepthFirst<'_> {
+    DepthFirst {
+        stack: forest.iter().rev().map(|node| (0, node)).collect(),
+    }
+}
+
+/// Depth-first iterator yielding each node along with its depth, roots having depth 0.
+#[derive(Debug, Clone)]
+pub struct DepthFirst<'a> {
+    stack: Vec<(usize, &'a ElementNode)>,
+}
+
+impl<'a> Iterator for DepthFirst<'a> {
+    type Item = (usize, &'a ElementNode);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.stack.pop()?;
+        self.stack
+            .extend(node.children.iter().rev().map(|child| (depth + 1, child)));
+        Some((depth, node))
+    }
+}
+
+pub(crate) fn build(elements: &[Element]) -> Vec<ElementNode> {
+    let mut index_of: HashMap<&str, usize> = HashMap::with_capacity(elements.len());
+    for (index, element) in elements.iter().enumerate() {
+        index_of.entry(element.element_id.as_str()).or_insert(index);
+    }
+
+    // Elements referring to an unknown parent are treated as roots.
+    let mut parents: Vec<Option<usize>> = elements
+        .iter()
+        .map(|element| index_of.get(element.parent_id()?.as_str()).copied())
+        .collect();
+
+    break_cycles(elements, &mut parents);
+
+    let mut children: Vec<Vec<usize>> = vec![Vec::new(); elements.len()];
+    let mut roots = Vec::new();
+    for (index, parent) in parents.iter().enumerate() {
+        match parent {
+            Some(parent) => children[*parent].push(index),
+            None => roots.push(index),
+        }
+    }
+
+    roots
+        .into_iter()
+        .map(|index| build_node(index, &children))
+        .collect()
+}
+
+fn build_node(index: usize, children: &[Vec<usize>]) -> ElementNode {
+    ElementNode {
+        index,
+        children: children[index]
+            .iter()
+            .map(|child| build_node(*child, children))
+            .collect(),
+    }
+}
+
+/// Detaches one element of every parent cycle from its parent, making it a root. The element
+/// detached is the one appearing first in the document.
+fn break_cycles(elements: &[Element], parents: &mut [Option<usize>]) {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        Unvisited,
+        InPath,
+        Done,
+    }
+
+    let mut state = vec![State::Unvisited; parents.len()];
+    for start in 0..parents.len() {
+        let mut path = Vec::new();
+        let mut current = Some(start);
+
+        while let Some(index) = current {
+            match state[index] {
+                State::Done => break,
+                State::InPath => {
+                    let cycle_start = path.iter().position(|i| *i == index).unwrap_or(0);
+                    let first = *path[cycle_start..].iter().min().unwrap_or(&index);
+                    tracing::warn!(
+                        "Parent cycle detected at element {}, treating it as a root.",
+                        elements[first].element_id
+                    );
+                    parents[first] = None;
+                    break;
+                }
+                State::Unvisited => {
+                    state[index] = State::InPath;
+                    path.push(index);
+                    current = parents[index];
+                }
+            }
+        }
+
+        for index in path {
+            state[index] = State::Done;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    fn element(id: &str, parent_id: Option<&str>) -> serde_json::Value {
+        serde_json::json!({
+            "type": "NarrativeText",
+            "element_id": id,
+            "text": id,
+            "metadata": {"filetype": "text/html", "parent_id": parent_id}
+        })
+    }
+
+    fn elements(values: Vec<serde_json::Value>) -> ElementList {
+        serde_json::from_value(serde_json::Value::Array(values)).unwrap()
+    }
+
+    fn flatten(forest: &[ElementNode]) -> Vec<(usize, usize)> {
+        depth_first(forest)
+            .map(|(depth, node)| (depth, node.index))
+            .collect()
+    }
+
+    #[test]
+    fn test_multi_level() {
+        let elements = elements(vec![
+            element("title", None),
+            element("section", Some("title")),
+            element("paragraph", Some("section")),
+            element("other", Some("title")),
+            element("second-title", None),
+            element("leaf", Some("second-title")),
+        ]);
+        let forest = build(&elements);
+
+        assert_eq!(forest.len(), 2);
+        assert_eq!(
+            flatten(&forest),
+            vec![(0, 0), (1, 1), (2, 2), (1, 3), (0, 4), (1, 5)]
+        );
+        assert_eq!(
+            forest[0]
+                .depth_first()
+                .map(|(_, n)| n.index)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn test_orphans_are_roots() {
+        let elements = elements(vec![
+            element("title", None),
+            element("orphan", Some("missing")),
+            element("child", Some("title")),
+        ]);
+        let forest = build(&elements);
+
+        assert_eq!(flatten(&forest), vec![(0, 0), (1, 2), (0, 1)]);
+    }
+
+    #[test]
+    fn test_cycle_is_broken() {
+        let elements = elements(vec![
+            element("a", Some("c")),
+            element("b", Some("a")),
+            element("c", Some("b")),
+            element("self", Some("self")),
+            element("d", Some("b")),
+        ]);
+        let forest = build(&elements);
+
+        assert_eq!(
+            flatten(&forest),
+            vec![(0, 0), (1, 1), (2, 2), (2, 4), (0, 3)]
+        );
+        assert_eq!(depth_first(&forest).count(), elements.len());
+    }
+
+    #[test]
+    fn test_apply_hierarchy_from_metadata() {
+        let elements = elements(vec![
+            element("paragraph", Some("section")),
+            element("title", None),
+            element("intro", None),
+            element("section", Some("title")),
+            element("note", Some("title")),
+            element("orphan", Some("missing")),
+        ]);
+        let ordered = elements.apply_hierarchy_from_metadata();
+
+        let ids: Vec<&str> = ordered.iter().map(|e| e.element_id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec!["title", "section", "paragraph", "note", "intro", "orphan"]
+        );
+        assert_eq!(ordered.apply_hierarchy_from_metadata(), ordered);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(build(&[]).is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// The kind of an [ElementId].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdKind {
+    /// A random UUID, assigned when `unique_element_ids` is set.
+    Uuid,
+
+    /// A hex encoded SHA-256 digest of the element, possibly truncated. This is the default, so
+    /// identical elements get identical IDs.
+    Sha256,
+
+    /// Anything else.
+    Other,
+}
+
+/// The ID of an element, as assigned by the API.
+///
+/// Serializes as a plain string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct ElementId(String);
+
+impl ElementId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the ID is a UUID or a SHA-256 digest.
+    ///
+    /// UUIDs are recognized in their hyphenated form only, as the API returns them. Digests are
+    /// 32 (truncated, as the API returns them) or 64 lowercase hex characters.
+    pub fn kind(&self) -> IdKind {
+        let is_hex = |s: &str| s.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f'));
+        if self.as_uuid().is_some() {
+            IdKind::Uuid
+        } else if matches!(self.0.len(), 32 | 64) && is_hex(&self.0) {
+            IdKind::Sha256
+        } else {
+            IdKind::Other
+        }
+    }
+
+    /// Parses the ID as a hyphenated UUID.
+    pub fn as_uuid(&self) -> Option<Uuid> {
+        if self.0.len() != 36 {
+            return None;
+        }
+        Uuid::try_parse(&self.0).ok()
+    }
+}
+
+impl fmt::Display for ElementId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for ElementId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for ElementId {
+    fn from(id: String) -> Self {
+        ElementId(id)
+    }
+}
+
+impl From<&str> for ElementId {
+    fn from(id: &str) -> Self {
+        ElementId(id.to_string())
+    }
+}
+
+impl From<ElementId> for String {
+    fn from(id: ElementId) -> Self {
+        id.0
+    }
+}
+
+impl PartialEq<str> for ElementId {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ElementId {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uuid_id() {
+        let id = ElementId::from("67e55044-10b1-426f-9247-bb680e5fe0c8");
+        assert_eq!(id.kind(), IdKind::Uuid);
+        assert_eq!(
+            id.as_uuid(),
+            Some(Uuid::from_u128(0x67e5504410b1426f9247bb680e5fe0c8))
+        );
+
+        let upper = ElementId::from("67E55044-10B1-426F-9247-BB680E5FE0C8");
+        assert_eq!(upper.kind(), IdKind::Uuid);
+    }
+
+    #[test]
+    fn test_sha256_id() {
+        let truncated = ElementId::from("5ca1c64ba66158642f2e8e6384e924a7");
+        assert_eq!(truncated.kind(), IdKind::Sha256);
+        assert_eq!(truncated.as_uuid(), None);
+
+        let full =
+            ElementId::from("b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9");
+        assert_eq!(full.kind(), IdKind::Sha256);
+    }
+
+    #[test]
+    fn test_other_id() {
+        for id in [
+            "",
+            "1",
+            "not-a-uuid",
+            // Uppercase hex and wrong lengths aren't produced by the API.
+            "5CA1C64BA66158642F2E8E6384E924A7",
+            "5ca1c64ba66158642f2e8e6384e924a",
+            // Non-hyphenated or braced UUIDs aren't either.
+            "67e5504410b1426f9247bb680e5fe0c8g",
+            "{67e55044-10b1-426f-9247-bb680e5fe0c8}",
+        ] {
+            let id = ElementId::from(id);
+            assert_eq!(id.kind(), IdKind::Other, "{id}");
+            assert_eq!(id.as_uuid(), None);
+        }
+    }
+
+    #[test]
+    fn test_serializes_as_string() {
+        let id: ElementId = serde_json::from_str(r#""abc""#).unwrap();
+        assert_eq!(id, "abc");
+        assert_eq!(serde_json::to_string(&id).unwrap(), r#""abc""#);
+    }
+}
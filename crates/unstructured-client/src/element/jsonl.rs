@@ -0,0 +1,184 @@
+use std::io::{BufRead, Write};
+
+use thiserror::Error;
+
+use crate::element::{Element, ElementList};
+use crate::error::Result;
+
+/// A line of JSON Lines that failed to deserialize as an element, see [read_jsonl].
+#[derive(Debug, Error)]
+#[error("Failed to parse element on line {line}: {source}")]
+pub struct JsonlLineError {
+    /// Line number, starting at 1 and counting blank lines.
+    pub line: usize,
+
+    /// The deserialization error.
+    pub source: serde_json::Error,
+
+    /// The text of the line.
+    pub raw: String,
+}
+
+/// Writes the elements to `writer` as [JSON Lines](https://jsonlines.org): one compact JSON
+/// object per element, each followed by a newline. Newlines within texts are escaped, so each
+/// element stays on its own line.
+pub fn write_jsonl<W: Write>(elements: &[Element], mut writer: W) -> Result<()> {
+    for element in elements {
+        serde_json::to_writer(&mut writer, element)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads elements written as [JSON Lines](https://jsonlines.org), e.g. by [write_jsonl].
+///
+/// Blank lines are skipped. Fails on the first line that isn't a valid element, with a
+/// [ClientError::JsonlParseFailed](crate::error::ClientError::JsonlParseFailed) giving its line
+/// number.
+pub fn read_jsonl<R: BufRead>(reader: R) -> Result<ElementList> {
+    let (elements, errors) = read_lines(reader, true)?;
+    match errors.into_iter().next() {
+        Some(error) => Err(error.into()),
+        None => Ok(elements),
+    }
+}
+
+/// [read_jsonl], skipping the lines that aren't valid elements.
+///
+/// Returns the elements that could be parsed, in order, and an error for each line that
+/// couldn't. Fails only when reading fails.
+pub fn read_jsonl_lenient<R: BufRead>(reader: R) -> Result<(ElementList, Vec<JsonlLineError>)> {
+    read_lines(reader, false)
+}
+
+fn read_lines<R: BufRead>(
+    reader: R,
+    stop_at_error: bool,
+) -> Result<(ElementList, Vec<JsonlLineError>)> {
+    let mut elements = ElementList::new();
+    let mut errors = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<Element>(&line) {
+            Ok(element) => elements.push(element),
+            Err(source) => {
+                errors.push(JsonlLineError {
+                    line: index + 1,
+                    source,
+                    raw: line,
+                });
+                if stop_at_error {
+                    break;
+                }
+            }
+        }
+    }
+    Ok((elements, errors))
+}
+
+/// Asynchronous version of [write_jsonl].
+#[cfg(feature = "tokio")]
+pub async fn write_jsonl_async<W>(elements: &[Element], writer: &mut W) -> Result<()>
+where
+    W: tokio::io::AsyncWrite + Unpin,
+{
+    use tokio::io::AsyncWriteExt;
+
+    let mut line = Vec::new();
+    for element in elements {
+        line.clear();
+        serde_json::to_writer(&mut line, element)?;
+        line.push(b'\n');
+        writer.write_all(&line).await?;
+    }
+    writer.flush().await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    fn fixture() -> ElementList {
+        serde_json::from_value(serde_json::json!([
+            {"type": "Title", "element_id": "1", "text": "Introduction",
+             "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "NarrativeText", "element_id": "2", "text": "First line.\nSecond line."}
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_write_jsonl() {
+        let elements = fixture();
+        let jsonl = elements.to_jsonl_string().unwrap();
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(jsonl.ends_with('\n'));
+        assert!(lines[1].contains(r#""text":"First line.\nSecond line.""#));
+        for (line, element) in lines.iter().zip(elements.iter()) {
+            assert_eq!(&serde_json::from_str::<Element>(line).unwrap(), element);
+        }
+
+        let mut buffer = Vec::new();
+        write_jsonl(&elements, &mut buffer).unwrap();
+        assert_eq!(buffer, jsonl.as_bytes());
+        assert_eq!(ElementList::default().to_jsonl_string().unwrap(), "");
+    }
+
+    #[test]
+    fn test_read_jsonl() {
+        let elements = fixture();
+        let jsonl = elements.to_jsonl_string().unwrap();
+        assert_eq!(read_jsonl(jsonl.as_bytes()).unwrap(), elements);
+
+        // Blank lines, CRLF line endings and a missing final newline are fine.
+        let spaced = format!(
+            "\n{}\r\n   \n{}",
+            jsonl.lines().next().unwrap(),
+            jsonl.lines().nth(1).unwrap()
+        );
+        assert_eq!(read_jsonl(spaced.as_bytes()).unwrap(), elements);
+        assert!(read_jsonl("".as_bytes()).unwrap().is_empty());
+        assert!(read_jsonl("\n\n".as_bytes()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_read_jsonl_corrupt_line() {
+        let jsonl = fixture().to_jsonl_string().unwrap();
+        let mut lines: Vec<&str> = jsonl.lines().collect();
+        lines.insert(1, "");
+        lines.insert(2, r#"{"type": "Title", "element_id": "#);
+        let corrupt = lines.join("\n");
+
+        match read_jsonl(corrupt.as_bytes()) {
+            Err(crate::error::ClientError::JsonlParseFailed(error)) => {
+                assert_eq!(error.line, 3);
+                assert_eq!(error.raw, lines[2]);
+            }
+            result => panic!("expected a parse error, got {result:?}"),
+        }
+
+        let (elements, errors) = read_jsonl_lenient(corrupt.as_bytes()).unwrap();
+        assert_eq!(elements, fixture());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].line, 3);
+        assert!(errors[0]
+            .to_string()
+            .starts_with("Failed to parse element on line 3"));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[tokio::test]
+    async fn test_write_jsonl_async() {
+        let elements = fixture();
+        let mut buffer = Vec::new();
+        write_jsonl_async(&elements, &mut buffer).await.unwrap();
+        assert_eq!(buffer, elements.to_jsonl_string().unwrap().as_bytes());
+    }
+}
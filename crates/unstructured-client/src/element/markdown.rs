@@ -0,0 +1,203 @@
+#[cfg(feature = "tables")]
+use crate::element::Table;
+use crate::element::{Element, ElementType};
+use crate::metadata::CommonMetadata;
+
+/// Renders `elements` as a Markdown document.
+///
+/// - `Title` elements become headings, with `category_depth` 0 mapping to `#`.
+/// - Consecutive `ListItem` elements form a single bullet list.
+/// - `CodeSnippet` elements become fenced code blocks.
+/// - `Table` elements are rendered from `text_as_html` with [Table::to_markdown] when it holds
+///   a table and the `tables` feature is enabled, and as their text otherwise.
+/// - `Image` elements are rendered as a data URI when `image_base64` is present, or as a
+///   placeholder otherwise.
+/// - `PageBreak` elements become thematic breaks.
+/// - All other elements are rendered as paragraphs.
+pub(crate) fn render(elements: &[Element]) -> String {
+    let mut blocks: Vec<String> = Vec::new();
+    let mut in_list = false;
+
+    for element in elements {
+        let common = element.common_metadata();
+
+        if matches!(
+            element.r#type,
+            ElementType::ListItem | ElementType::BulletedText
+        ) {
+            let item = format!("- {}", element.text.trim());
+            match blocks.last_mut() {
+                Some(list) if in_list => {
+                    list.push('\n');
+                    list.push_str(&item);
+                }
+                _ => blocks.push(item),
+            }
+            in_list = true;
+            continue;
+        }
+        in_list = false;
+
+        let block = match element.r#type {
+            ElementType::Title => {
+                let depth = common.and_then(|c| c.category_depth).unwrap_or(0);
+                let level = (depth as usize + 1).min(6);
+                format!("{} {}", "#".repeat(level), element.text.trim())
+            }
+            ElementType::CodeSnippet => format!("```\n{}\n```", element.text.trim_end()),
+            #[cfg(feature = "tables")]
+            ElementType::Table | ElementType::TableChunk => common
+                .and_then(table_from_metadata)
+                .unwrap_or_else(|| element.text.trim().to_string()),
+            ElementType::Image => render_image(element, common),
+            ElementType::PageBreak => "---".to_string(),
+            _ => element.text.trim().to_string(),
+        };
+
+        if !block.is_empty() {
+            blocks.push(block);
+        }
+    }
+
+    let mut markdown = blocks.join("\n\n");
+    if !markdown.is_empty() {
+        markdown.push('\n');
+    }
+    markdown
+}
+
+fn render_image(element: &Element, common: Option<&CommonMetadata>) -> String {
+    let alt = element.text.trim();
+    match common.and_then(|c| c.image_base64.as_deref()) {
+        Some(data) => {
+            let mime_type = common
+                .and_then(|c| c.image_mime_type.as_deref())
+                .unwrap_or("image/png");
+            format!("![{alt}](data:{mime_type};base64,{data})")
+        }
+        None if alt.is_empty() => "[Image]".to_string(),
+        None => format!("[Image: {alt}]"),
+    }
+}
+
+#[cfg(feature = "tables")]
+fn table_from_metadata(common: &CommonMetadata) -> Option<String> {
+    let markdown = Table::parse(common.text_as_html.as_deref()?)
+        .ok()?
+        .to_markdown();
+    (!markdown.is_empty()).then_some(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    fn fixture() -> ElementList {
+        let json_str = r#"
+        [
+            {"type": "Title", "element_id": "1", "text": "Annual Report",
+             "metadata": {"filetype": "application/pdf", "category_depth": 0}},
+            {"type": "NarrativeText", "element_id": "2", "text": "This year went well.",
+             "metadata": {"filetype": "application/pdf"}},
+            {"type": "Title", "element_id": "3", "text": "Highlights",
+             "metadata": {"filetype": "application/pdf", "category_depth": 1}},
+            {"type": "ListItem", "element_id": "4", "text": "Revenue grew"},
+            {"type": "ListItem", "element_id": "5", "text": "Costs fell"},
+            {"type": "Title", "element_id": "6", "text": "Details",
+             "metadata": {"filetype": "application/pdf", "category_depth": 2}},
+            {"type": "CodeSnippet", "element_id": "7", "text": "fn main() {}"},
+            {"type": "Table", "element_id": "8", "text": "Quarter Revenue Q1 10",
+             "metadata": {"filetype": "application/pdf",
+                          "text_as_html": "<table><thead><tr><th>Quarter</th><th>Revenue</th></tr></thead><tbody><tr><td>Q1</td><td><b>10</b> | 12</td></tr></tbody></table>"}},
+            {"type": "Image", "element_id": "9", "text": "Logo",
+             "metadata": {"filetype": "application/pdf", "image_base64": "aGVsbG8=", "image_mime_type": "image/jpeg"}},
+            {"type": "Image", "element_id": "10", "text": "Chart"},
+            {"type": "PageBreak", "element_id": "11", "text": ""},
+            {"type": "ListItem", "element_id": "12", "text": "Appendix item"}
+        ]
+        "#;
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        #[cfg(feature = "tables")]
+        let table = "| Quarter | Revenue |\n| --- | --- |\n| Q1 | 10 \\| 12 |";
+        #[cfg(not(feature = "tables"))]
+        let table = "Quarter Revenue Q1 10";
+        let expected = format!(
+            r#"# Annual Report
+
+This year went well.
+
+## Highlights
+
+- Revenue grew
+- Costs fell
+
+### Details
+
+```
+fn main() {{}}
+```
+
+{table}
+
+![Logo](data:image/jpeg;base64,aGVsbG8=)
+
+[Image: Chart]
+
+---
+
+- Appendix item
+"#
+        );
+        assert_eq!(render(&fixture()), expected);
+    }
+
+    #[test]
+    fn test_render_nested_titles() {
+        let json_str = r#"
+        [
+            {"type": "Title", "element_id": "1", "text": "Part",
+             "metadata": {"filetype": "text/html", "category_depth": 0}},
+            {"type": "Title", "element_id": "2", "text": "Chapter",
+             "metadata": {"filetype": "text/html", "category_depth": 1}},
+            {"type": "Title", "element_id": "3", "text": "Deep",
+             "metadata": {"filetype": "text/html", "category_depth": 9}},
+            {"type": "Title", "element_id": "4", "text": "No depth"}
+        ]
+        "#;
+        let elements: ElementList = serde_json::from_str(json_str).unwrap();
+        assert_eq!(
+            render(&elements),
+            "# Part\n\n## Chapter\n\n###### Deep\n\n# No depth\n"
+        );
+    }
+
+    #[cfg(feature = "tables")]
+    #[test]
+    fn test_render_spanned_table() {
+        let json_str = r#"[{"type": "Table", "element_id": "1", "text": "Wide C 1 2 3",
+            "metadata": {"filetype": "text/html",
+                         "text_as_html": "<table><tr><th colspan=2>Wide</th><th>C</th></tr><tr><td>1</td><td>2</td><td>3</td></tr></table>"}}]"#;
+        let elements: ElementList = serde_json::from_str(json_str).unwrap();
+        assert_eq!(
+            render(&elements),
+            "| Wide | Wide | C |\n| --- | --- | --- |\n| 1 | 2 | 3 |\n"
+        );
+    }
+
+    #[test]
+    fn test_render_table_without_html() {
+        let json_str = r#"[{"type": "Table", "element_id": "1", "text": "a b c"}]"#;
+        let elements: ElementList = serde_json::from_str(json_str).unwrap();
+        assert_eq!(render(&elements), "a b c\n");
+    }
+
+    #[test]
+    fn test_render_empty() {
+        assert_eq!(render(&[]), "");
+    }
+}
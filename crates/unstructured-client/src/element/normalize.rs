@@ -0,0 +1,189 @@
+//! Clean-up of artifacts that text extraction leaves in element texts, e.g. words split by
+//! hyphenation at line ends.
+
+use crate::element::{Element, ElementType};
+
+/// Options for [ElementList::normalize_text](crate::ElementList::normalize_text). All steps are
+/// enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizeOptions {
+    /// Apply [strip_control_chars].
+    pub strip_control_chars: bool,
+
+    /// Apply [fix_hyphenation].
+    pub fix_hyphenation: bool,
+
+    /// Apply [collapse_whitespace].
+    pub collapse_whitespace: bool,
+}
+
+impl Default for NormalizeOptions {
+    fn default() -> Self {
+        NormalizeOptions {
+            strip_control_chars: true,
+            fix_hyphenation: true,
+            collapse_whitespace: true,
+        }
+    }
+}
+
+/// Words that follow a hyphen in suspended compounds such as "pre- and post-processing".
+const CONJUNCTIONS: &[&str] = &["and", "or", "nor", "to"];
+
+/// Joins words split by hyphenation at a line end, e.g. `"infor-\nmation"` becomes
+/// `"information"`, and removes soft hyphens (U+00AD).
+///
+/// A hyphen followed by whitespace is only removed when the next fragment starts with a
+/// lowercase letter, so that e.g. `"Franco-\nGerman"` is kept as is. Conjunctions after the
+/// hyphen are left alone too, as in `"pre- and post-processing"`. Hyphens not followed by
+/// whitespace are never touched.
+pub fn fix_hyphenation(text: &str) -> String {
+    let chars: Vec<char> = text.chars().filter(|&c| c != '\u{ad}').collect();
+    let mut fixed = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        fixed.push(c);
+        i += 1;
+        if !c.is_alphabetic() || chars.get(i) != Some(&'-') {
+            continue;
+        }
+
+        // A letter followed by a hyphen, whitespace and a lowercase word.
+        let mut start = i + 1;
+        while chars.get(start).is_some_and(|c| c.is_whitespace()) {
+            start += 1;
+        }
+        if start == i + 1 || !chars.get(start).is_some_and(|c| c.is_lowercase()) {
+            continue;
+        }
+        let mut end = start;
+        while chars.get(end).is_some_and(|c| c.is_alphabetic()) {
+            end += 1;
+        }
+
+        let word: String = chars[start..end].iter().collect();
+        if CONJUNCTIONS.contains(&word.as_str()) {
+            fixed.extend(&chars[i..end]);
+        } else {
+            fixed.push_str(&word);
+        }
+        i = end;
+    }
+    fixed
+}
+
+/// Replaces each run of whitespace, including line breaks, with a single space, and trims the
+/// text.
+pub fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Removes control characters other than line breaks and tabs, as well as zero width spaces
+/// and byte order marks.
+pub fn strip_control_chars(text: &str) -> String {
+    text.chars()
+        .filter(|&c| match c {
+            '\n' | '\r' | '\t' => true,
+            '\u{200b}' | '\u{feff}' => false,
+            c => !c.is_control(),
+        })
+        .collect()
+}
+
+pub(crate) fn normalize(elements: &mut [Element], options: &NormalizeOptions) {
+    for element in elements {
+        if element.r#type == ElementType::CodeSnippet {
+            continue;
+        }
+
+        if options.strip_control_chars {
+            element.text = strip_control_chars(&element.text);
+        }
+        if options.fix_hyphenation {
+            element.text = fix_hyphenation(&element.text);
+        }
+        if options.collapse_whitespace {
+            element.text = collapse_whitespace(&element.text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    #[test]
+    fn test_fix_hyphenation() {
+        for (before, after) in [
+            ("infor-\nmation", "information"),
+            ("infor- mation retrieval", "information retrieval"),
+            ("the docu-\r\n  ment is long", "the document is long"),
+            (
+                "Zusammen-\nfassung über Ämter",
+                "Zusammenfassung über Ämter",
+            ),
+            ("soft\u{ad}hyphen", "softhyphen"),
+            ("Franco-\nGerman relations", "Franco-\nGerman relations"),
+            ("pre- and post-processing", "pre- and post-processing"),
+            ("well-known", "well-known"),
+            ("pages 10- 12", "pages 10- 12"),
+            ("a -\nb", "a -\nb"),
+        ] {
+            assert_eq!(fix_hyphenation(before), after, "{before:?}");
+        }
+    }
+
+    #[test]
+    fn test_collapse_whitespace() {
+        for (before, after) in [
+            ("  two   spaces  ", "two spaces"),
+            ("line\nbreak\t\ttab", "line break tab"),
+            ("non\u{a0}breaking", "non breaking"),
+            ("", ""),
+        ] {
+            assert_eq!(collapse_whitespace(before), after, "{before:?}");
+        }
+    }
+
+    #[test]
+    fn test_strip_control_chars() {
+        for (before, after) in [
+            ("bell\u{7}", "bell"),
+            ("\u{feff}bom", "bom"),
+            ("zero\u{200b}width", "zerowidth"),
+            ("form\u{c}feed\0", "formfeed"),
+            ("keep\nlines\tand tabs", "keep\nlines\tand tabs"),
+        ] {
+            assert_eq!(strip_control_chars(before), after, "{before:?}");
+        }
+    }
+
+    #[test]
+    fn test_normalize_text() {
+        let mut elements: ElementList = serde_json::from_value(serde_json::json!([
+            {"type": "NarrativeText", "element_id": "1",
+             "text": "  The infor-\nmation  was\u{7} retrieved. "},
+            {"type": "CodeSnippet", "element_id": "2", "text": "let  x = a-\n    b;"},
+            {"type": "Title", "element_id": "3", "text": "Franco-\nGerman   Ties"}
+        ]))
+        .unwrap();
+
+        let mut only_hyphenation = elements.clone();
+        only_hyphenation.normalize_text(&NormalizeOptions {
+            strip_control_chars: false,
+            fix_hyphenation: true,
+            collapse_whitespace: false,
+        });
+        assert_eq!(
+            only_hyphenation[0].text,
+            "  The information  was\u{7} retrieved. "
+        );
+
+        elements.normalize_text(&NormalizeOptions::default());
+        assert_eq!(elements[0].text, "The information was retrieved.");
+        assert_eq!(elements[1].text, "let  x = a-\n    b;");
+        assert_eq!(elements[2].text, "Franco- German Ties");
+    }
+}
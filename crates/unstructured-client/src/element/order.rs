@@ -0,0 +1,162 @@
+use std::cmp::Ordering;
+
+use crate::element::Element;
+use crate::metadata::CoordinateSystem;
+
+/// Position of an element on its page, used as its reading order sort key.
+#[derive(Debug, Clone, Copy)]
+struct Position {
+    page: u32,
+    top: f64,
+    left: f64,
+    index: usize,
+}
+
+impl Position {
+    fn cmp(&self, other: &Position) -> Ordering {
+        self.page
+            .cmp(&other.page)
+            .then(self.top.total_cmp(&other.top))
+            .then(self.left.total_cmp(&other.left))
+            .then(self.index.cmp(&other.index))
+    }
+}
+
+/// The top and left edges of the element's bounding box, with the top increasing down the page
+/// regardless of the coordinate system.
+fn top_left(element: &Element) -> Option<(f64, f64)> {
+    let coordinates = element.common_metadata()?.coordinates.as_ref()?;
+    let bbox = coordinates.bounding_box()?;
+    let system = coordinates
+        .system
+        .as_ref()
+        .unwrap_or(&CoordinateSystem::PixelSpace);
+    let top = if matches!(system, CoordinateSystem::Unknown(_)) || system.is_screen_oriented() {
+        bbox.y0
+    } else {
+        -bbox.y1
+    };
+    Some((top, bbox.x0))
+}
+
+/// Sorts `elements` into reading order, see
+/// [ElementList::sort_reading_order](crate::ElementList::sort_reading_order).
+pub(crate) fn sort(elements: &mut Vec<Element>) {
+    let mut positions = Vec::with_capacity(elements.len());
+    let mut previous: Option<Position> = None;
+
+    for (index, element) in elements.iter().enumerate() {
+        let page = element
+            .page_number()
+            .or(previous.map(|p| p.page))
+            .unwrap_or_default();
+        let (top, left) = match (top_left(element), previous) {
+            (Some(top_left), _) => top_left,
+            (None, Some(p)) if p.page == page => (p.top, p.left),
+            (None, _) => (f64::NEG_INFINITY, f64::NEG_INFINITY),
+        };
+        let position = Position {
+            page,
+            top,
+            left,
+            index,
+        };
+        positions.push(position);
+        previous = Some(position);
+    }
+
+    let mut keyed: Vec<(Position, Element)> =
+        positions.into_iter().zip(elements.drain(..)).collect();
+    keyed.sort_by(|(a, _), (b, _)| a.cmp(b));
+    elements.extend(keyed.into_iter().map(|(_, element)| element));
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementList;
+
+    fn ids(elements: &ElementList) -> Vec<&str> {
+        elements.iter().map(|e| e.element_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_sort_reading_order() {
+        // Page 2 comes first, and the y positions on page 1 are interleaved.
+        let json_str = r#"
+        [
+            {"type": "NarrativeText", "element_id": "p2-top", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 2,
+                          "coordinates": {"points": [[10, 10], [10, 20], [50, 20], [50, 10]], "system": "PixelSpace"}}},
+            {"type": "NarrativeText", "element_id": "bottom", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 1,
+                          "coordinates": {"points": [[10, 300], [10, 320], [50, 320], [50, 300]], "system": "PixelSpace"}}},
+            {"type": "Image", "element_id": "after-bottom", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "Title", "element_id": "top", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 1,
+                          "coordinates": {"points": [[10, 10], [10, 30], [90, 30], [90, 10]], "system": "PixelSpace"}}},
+            {"type": "NarrativeText", "element_id": "middle-right", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 1,
+                          "coordinates": {"points": [[60, 100], [60, 120], [90, 120], [90, 100]], "system": "PixelSpace"}}},
+            {"type": "NarrativeText", "element_id": "middle-left", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 1,
+                          "coordinates": {"points": [[10, 100], [10, 120], [50, 120], [50, 100]], "system": "PixelSpace"}}},
+            {"type": "NarrativeText", "element_id": "middle-left-again", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 1,
+                          "coordinates": {"points": [[10, 100], [10, 110], [20, 110], [20, 100]], "system": "PixelSpace"}}},
+            {"type": "PageBreak", "element_id": "no-page", "text": ""}
+        ]
+        "#;
+        let mut elements: ElementList = serde_json::from_str(json_str).unwrap();
+        elements.sort_reading_order();
+
+        assert_eq!(
+            ids(&elements),
+            vec![
+                "top",
+                "middle-left",
+                "middle-left-again",
+                "no-page",
+                "middle-right",
+                "bottom",
+                "after-bottom",
+                "p2-top",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_reading_order_cartesian() {
+        // y points up, so the element with the higher y is read first.
+        let json_str = r#"
+        [
+            {"type": "NarrativeText", "element_id": "low", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 1,
+                          "coordinates": {"points": [[0, 0.1], [0, 0.2], [0.5, 0.2], [0.5, 0.1]],
+                                          "system": "RelativeCoordinateSystem"}}},
+            {"type": "Title", "element_id": "high", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 1,
+                          "coordinates": {"points": [[0, 0.8], [0, 0.9], [0.5, 0.9], [0.5, 0.8]],
+                                          "system": "RelativeCoordinateSystem"}}}
+        ]
+        "#;
+        let mut elements: ElementList = serde_json::from_str(json_str).unwrap();
+        elements.sort_reading_order();
+        assert_eq!(ids(&elements), vec!["high", "low"]);
+    }
+
+    #[test]
+    fn test_sort_reading_order_without_coordinates_is_stable() {
+        let json_str = r#"
+        [
+            {"type": "Title", "element_id": "a", "text": "", "metadata": {"filetype": "application/pdf", "page_number": 2}},
+            {"type": "Title", "element_id": "b", "text": "", "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "Title", "element_id": "c", "text": ""},
+            {"type": "Title", "element_id": "d", "text": "", "metadata": {"filetype": "application/pdf", "page_number": 1}}
+        ]
+        "#;
+        let mut elements: ElementList = serde_json::from_str(json_str).unwrap();
+        elements.sort_reading_order();
+        assert_eq!(ids(&elements), vec!["b", "c", "d", "a"]);
+    }
+}
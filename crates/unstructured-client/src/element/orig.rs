@@ -0,0 +1,83 @@
+use std::io::Read;
+
+use base64::Engine;
+use flate2::read::{GzDecoder, ZlibDecoder};
+
+use crate::element::ElementList;
+use crate::error::{ClientError, Result};
+
+/// Decodes the `orig_elements` metadata of a chunk: base64 encoded, zlib (or gzip) compressed
+/// JSON array of elements.
+pub(crate) fn decode(encoded: &str) -> Result<ElementList> {
+    let compressed = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| ClientError::OrigElementsDecodeFailed(e.to_string()))?;
+
+    let mut json = Vec::new();
+    if compressed.starts_with(&[0x1f, 0x8b]) {
+        GzDecoder::new(compressed.as_slice()).read_to_end(&mut json)
+    } else {
+        ZlibDecoder::new(compressed.as_slice()).read_to_end(&mut json)
+    }
+    .map_err(|e| ClientError::OrigElementsDecodeFailed(e.to_string()))?;
+
+    serde_json::from_slice(&json).map_err(|e| ClientError::OrigElementsDecodeFailed(e.to_string()))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::io::Write;
+
+    use super::*;
+    use flate2::write::{GzEncoder, ZlibEncoder};
+    use flate2::Compression;
+
+    /// Encodes `elements` the way the API encodes `orig_elements`.
+    pub(crate) fn encode(elements: &serde_json::Value) -> String {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(elements.to_string().as_bytes()).unwrap();
+        base64::engine::general_purpose::STANDARD.encode(encoder.finish().unwrap())
+    }
+
+    fn elements() -> serde_json::Value {
+        serde_json::json!([
+            {"type": "Title", "element_id": "a", "text": "Title",
+             "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "NarrativeText", "element_id": "b", "text": "Body",
+             "metadata": {"filetype": "application/pdf", "page_number": 2}}
+        ])
+    }
+
+    #[test]
+    fn test_decode_zlib() -> Result<()> {
+        let decoded = decode(&encode(&elements()))?;
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[1].element_id, "b");
+        assert_eq!(decoded[1].page_number(), Some(2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_gzip() -> Result<()> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(elements().to_string().as_bytes())?;
+        let encoded = base64::engine::general_purpose::STANDARD.encode(encoder.finish()?);
+
+        assert_eq!(decode(&encoded)?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        for encoded in [
+            "not base64!",
+            "aGVsbG8=",
+            &encode(&serde_json::json!({"a": 1})),
+        ] {
+            assert!(matches!(
+                decode(encoded),
+                Err(ClientError::OrigElementsDecodeFailed(_))
+            ));
+        }
+    }
+}
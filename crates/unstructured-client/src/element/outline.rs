@@ -0,0 +1,175 @@
+use std::ops::Range;
+
+use crate::element::{Element, ElementType};
+
+/// A section of the document outline, see [ElementList::outline](crate::ElementList::outline).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Section {
+    /// Text of the title that starts the section.
+    pub title: String,
+
+    /// Depth of the title, 0 for top-level sections.
+    pub depth: u32,
+
+    /// Page number of the title, if known.
+    pub page: Option<u32>,
+
+    /// Indices of the elements in the section, starting with its title and including the
+    /// elements of its subsections.
+    pub element_range: Range<usize>,
+
+    /// Subsections, in document order.
+    pub children: Vec<Section>,
+}
+
+pub(crate) fn build(elements: &[Element]) -> Vec<Section> {
+    let mut roots = Vec::new();
+    // Sections that haven't ended yet, each one a subsection of the one before it.
+    let mut open: Vec<Section> = Vec::new();
+
+    for (index, element) in elements.iter().enumerate() {
+        if element.r#type != ElementType::Title {
+            continue;
+        }
+
+        // Titles without a depth are treated as siblings of the title before them.
+        let depth = element
+            .common_metadata()
+            .and_then(|metadata| metadata.category_depth)
+            .or_else(|| open.last().map(|section| section.depth))
+            .unwrap_or_default();
+
+        while open.last().is_some_and(|section| section.depth >= depth) {
+            close(&mut open, &mut roots, index);
+        }
+        open.push(Section {
+            title: element.text.clone(),
+            depth,
+            page: element.page_number(),
+            element_range: index..index,
+            children: Vec::new(),
+        });
+    }
+
+    while !open.is_empty() {
+        close(&mut open, &mut roots, elements.len());
+    }
+    roots
+}
+
+/// Ends the innermost open section before the element at `end`.
+fn close(open: &mut Vec<Section>, roots: &mut Vec<Section>, end: usize) {
+    let Some(mut section) = open.pop() else {
+        return;
+    };
+    section.element_range.end = end;
+    match open.last_mut() {
+        Some(parent) => parent.children.push(section),
+        None => roots.push(section),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    fn title(text: &str, depth: Option<u32>, page: u32) -> serde_json::Value {
+        let mut metadata = serde_json::json!({"filetype": "application/pdf", "page_number": page});
+        if let Some(depth) = depth {
+            metadata["category_depth"] = depth.into();
+        }
+        serde_json::json!({"type": "Title", "element_id": text, "text": text, "metadata": metadata})
+    }
+
+    fn body(page: u32) -> serde_json::Value {
+        serde_json::json!({"type": "NarrativeText", "element_id": "body", "text": "…",
+                           "metadata": {"filetype": "application/pdf", "page_number": page}})
+    }
+
+    /// (title, depth, element range) of every section, in pre-order.
+    fn flatten(sections: &[Section]) -> Vec<(&str, u32, Range<usize>)> {
+        sections
+            .iter()
+            .flat_map(|section| {
+                std::iter::once((
+                    section.title.as_str(),
+                    section.depth,
+                    section.element_range.clone(),
+                ))
+                .chain(flatten(&section.children))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_outline() {
+        let elements: ElementList = serde_json::from_value(serde_json::json!([
+            body(1),
+            title("Introduction", Some(0), 1),
+            body(1),
+            title("Background", Some(1), 1),
+            body(1),
+            title("Prior work", Some(2), 2),
+            body(2),
+            title("Motivation", Some(1), 2),
+            title("Method", Some(0), 3),
+            body(3),
+            body(4)
+        ]))
+        .unwrap();
+
+        let outline = elements.outline();
+        assert_eq!(outline.len(), 2);
+        assert_eq!(
+            flatten(&outline),
+            vec![
+                ("Introduction", 0, 1..8),
+                ("Background", 1, 3..7),
+                ("Prior work", 2, 5..7),
+                ("Motivation", 1, 7..8),
+                ("Method", 0, 8..11),
+            ]
+        );
+        assert_eq!(outline[0].children[0].children[0].page, Some(2));
+        assert_eq!(outline[1].page, Some(3));
+    }
+
+    #[test]
+    fn test_outline_irregular_depths() {
+        let elements: ElementList = serde_json::from_value(serde_json::json!([
+            // Starts below the top level, then skips a level and regresses.
+            title("A", Some(1), 1),
+            title("B", Some(3), 1),
+            body(1),
+            title("C", Some(2), 1),
+            title("D", None, 1),
+            title("E", Some(0), 2),
+            body(2)
+        ]))
+        .unwrap();
+
+        assert_eq!(
+            flatten(&elements.outline()),
+            vec![
+                ("A", 1, 0..5),
+                ("B", 3, 1..3),
+                ("C", 2, 3..4),
+                ("D", 2, 4..5),
+                ("E", 0, 5..7),
+            ]
+        );
+
+        let untitled: ElementList = serde_json::from_value(serde_json::json!([body(1)])).unwrap();
+        assert!(untitled.outline().is_empty());
+        let no_depths: ElementList = serde_json::from_value(serde_json::json!([
+            title("X", None, 1),
+            title("Y", None, 1)
+        ]))
+        .unwrap();
+        assert_eq!(
+            flatten(&no_depths.outline()),
+            vec![("X", 0, 0..1), ("Y", 0, 1..2)]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+use crate::element::{Element, ElementList, ElementType};
+use crate::metadata::{CommonMetadata, Metadata};
+
+pub(crate) fn split(elements: &[Element], keep_breaks: bool) -> Vec<ElementList> {
+    let mut pages = Vec::new();
+    let mut page = ElementList::default();
+    for element in elements {
+        let is_break = element.r#type == ElementType::PageBreak;
+        if !is_break || keep_breaks {
+            page.push(element.clone());
+        }
+        if is_break {
+            pages.push(std::mem::take(&mut page));
+        }
+    }
+
+    // A break at the end of the document doesn't start another page.
+    let ends_with_break = elements
+        .last()
+        .is_some_and(|element| element.r#type == ElementType::PageBreak);
+    if !ends_with_break && !elements.is_empty() {
+        pages.push(page);
+    }
+    pages
+}
+
+pub(crate) fn infer_page_numbers(elements: &mut [Element], starting_page: u32) {
+    let mut page_number = starting_page;
+    for element in elements {
+        if element.page_number().is_none() {
+            element
+                .metadata
+                .get_or_insert_with(|| Metadata::UnknownFormat(CommonMetadata::default()))
+                .set_page_number(page_number);
+        }
+        if element.r#type == ElementType::PageBreak {
+            page_number += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elements(types: &str) -> ElementList {
+        // One element per character: `|` is a page break, any other character a paragraph with
+        // that character as its text.
+        let elements: Vec<_> = types
+            .chars()
+            .map(|c| {
+                let r#type = if c == '|' { "PageBreak" } else { "NarrativeText" };
+                serde_json::json!({"type": r#type, "element_id": c.to_string(), "text": c.to_string(),
+                                   "metadata": {"filetype": "text/html"}})
+            })
+            .collect();
+        serde_json::from_value(serde_json::Value::Array(elements)).unwrap()
+    }
+
+    fn texts(pages: &[ElementList]) -> Vec<String> {
+        pages
+            .iter()
+            .map(|page| page.iter().map(|element| element.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_at_page_breaks() {
+        for (input, expected) in [
+            ("ab|c", vec!["ab", "c"]),
+            ("abc", vec!["abc"]),
+            ("|ab", vec!["", "ab"]),
+            ("ab|", vec!["ab"]),
+            ("a||b", vec!["a", "", "b"]),
+            ("|", vec![""]),
+            ("", vec![]),
+        ] {
+            let pages = elements(input).split_at_page_breaks(false);
+            assert_eq!(texts(&pages), expected, "{input:?}");
+        }
+
+        let pages = elements("a||b|").split_at_page_breaks(true);
+        assert_eq!(texts(&pages), vec!["a|", "|", "b|"]);
+    }
+
+    #[test]
+    fn test_infer_page_numbers() {
+        let mut list = elements("|a|b||c|");
+        list.infer_page_numbers(1);
+        let page_numbers: Vec<_> = list.iter().map(Element::page_number).collect();
+        assert_eq!(
+            page_numbers,
+            [1, 2, 2, 3, 3, 4, 5, 5].map(Some).to_vec(),
+            "{list:?}"
+        );
+
+        // Page numbers that are already set are kept, and elements without metadata get some.
+        let mut list: ElementList = serde_json::from_value(serde_json::json!([
+            {"type": "Title", "element_id": "1", "text": "",
+             "metadata": {"filetype": "application/pdf", "page_number": 7}},
+            {"type": "PageBreak", "element_id": "2", "text": ""},
+            {"type": "NarrativeText", "element_id": "3", "text": "",
+             "metadata": {"filetype": "application/pdf"}}
+        ]))
+        .unwrap();
+        list.infer_page_numbers(10);
+        let page_numbers: Vec<_> = list.iter().map(Element::page_number).collect();
+        assert_eq!(page_numbers, vec![Some(7), Some(10), Some(11)]);
+        assert_eq!(list[2].as_paged().unwrap().page_number, Some(11));
+    }
+}
@@ -0,0 +1,289 @@
+use std::ops::Range;
+
+use crate::element::Element;
+
+/// Output format of [Element::to_rich_text].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RichTextFormat {
+    /// `**bold**` and `_italic_`.
+    Markdown,
+
+    /// `<b>bold</b>` and `<i>italic</i>`, with the rest of the text escaped.
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Emphasis {
+    Bold,
+    Italic,
+}
+
+impl Emphasis {
+    /// Parses an `emphasized_text_tags` entry such as `b` or `<em>`.
+    fn from_tag(tag: &str) -> Option<Emphasis> {
+        let tag = tag.trim().trim_start_matches('<').trim_end_matches('>');
+        match tag.to_ascii_lowercase().as_str() {
+            "b" | "strong" => Some(Emphasis::Bold),
+            "i" | "em" => Some(Emphasis::Italic),
+            _ => None,
+        }
+    }
+
+    fn markers(self, format: RichTextFormat) -> (&'static str, &'static str) {
+        match (format, self) {
+            (RichTextFormat::Markdown, Emphasis::Bold) => ("**", "**"),
+            (RichTextFormat::Markdown, Emphasis::Italic) => ("_", "_"),
+            (RichTextFormat::Html, Emphasis::Bold) => ("<b>", "</b>"),
+            (RichTextFormat::Html, Emphasis::Italic) => ("<i>", "</i>"),
+        }
+    }
+}
+
+/// An emphasized run located in the text, as a byte range.
+#[derive(Debug, Clone)]
+struct Span {
+    range: Range<usize>,
+    emphasis: Emphasis,
+}
+
+impl Span {
+    /// Whether the spans overlap without one containing the other, which can't be rendered.
+    fn crosses(&self, other: &Span) -> bool {
+        let (a, b) = (&self.range, &other.range);
+        let disjoint = a.end <= b.start || b.end <= a.start;
+        let nested =
+            (a.start <= b.start && b.end <= a.end) || (b.start <= a.start && a.end <= b.end);
+        !disjoint && !nested
+    }
+
+    fn conflicts(&self, placed: &[Span]) -> bool {
+        placed.iter().any(|span| {
+            self.crosses(span) || (span.range == self.range && span.emphasis == self.emphasis)
+        })
+    }
+}
+
+/// The emphasized runs of the element, as `(content, tag)` pairs.
+fn runs(element: &Element) -> Vec<(&str, &str)> {
+    let Some(metadata) = element.common_metadata() else {
+        return Vec::new();
+    };
+    metadata
+        .emphasized_text_contents
+        .iter()
+        .flatten()
+        .zip(metadata.emphasized_text_tags.iter().flatten())
+        .map(|(content, tag)| (content.as_str(), tag.as_str()))
+        .collect()
+}
+
+/// Locates the runs in `text`.
+///
+/// Runs are listed in document order, so each one is searched for from the start of the run
+/// before it, and only then from the start of the text. Occurrences that would partially
+/// overlap a run that was already located are skipped, as are occurrences already located with
+/// the same emphasis; this way a repeated bold substring matches its occurrences in order,
+/// while a bold and an italic run of the same text mark a single bold italic occurrence.
+fn locate(text: &str, runs: &[(&str, &str)]) -> Vec<Span> {
+    let mut placed: Vec<Span> = Vec::new();
+    let mut cursor = 0;
+
+    for &(content, tag) in runs {
+        let (content, Some(emphasis)) = (content.trim(), Emphasis::from_tag(tag)) else {
+            continue;
+        };
+        if content.is_empty() {
+            continue;
+        }
+
+        let candidates = text[cursor..]
+            .match_indices(content)
+            .map(|(start, _)| cursor + start)
+            .chain(
+                text.match_indices(content)
+                    .map(|(start, _)| start)
+                    .take_while(|&start| start < cursor),
+            );
+        let found = candidates
+            .map(|start| Span {
+                range: start..start + content.len(),
+                emphasis,
+            })
+            .find(|span| !span.conflicts(&placed));
+        if let Some(span) = found {
+            cursor = span.range.start;
+            placed.push(span);
+        }
+    }
+    placed
+}
+
+fn render_runs(text: &str, runs: &[(&str, &str)], format: RichTextFormat) -> String {
+    let mut spans = locate(text, runs);
+    // Outer spans open first, so that nested spans close before them.
+    spans.sort_by(|a, b| {
+        a.range
+            .start
+            .cmp(&b.range.start)
+            .then(b.range.end.cmp(&a.range.end))
+    });
+
+    let escape = |text: &str| match format {
+        RichTextFormat::Markdown => text.to_string(),
+        RichTextFormat::Html => text
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+    };
+
+    let mut output = String::with_capacity(text.len());
+    let mut open: Vec<&Span> = Vec::new();
+    let mut position = 0;
+    let mut next = spans.iter().peekable();
+    loop {
+        // The next boundary: either the end of the innermost open span, or the next start.
+        let close_at = open.last().map(|span| span.range.end);
+        let open_at = next.peek().map(|span| span.range.start);
+        let boundary = match (close_at, open_at) {
+            (Some(close), Some(start)) => close.min(start),
+            (Some(close), None) => close,
+            (None, Some(start)) => start,
+            (None, None) => break,
+        };
+
+        output.push_str(&escape(&text[position..boundary]));
+        position = boundary;
+        if close_at == Some(boundary) {
+            let span = open.pop().expect("an open span ends here");
+            output.push_str(span.emphasis.markers(format).1);
+        } else {
+            let span = next.next().expect("a span starts here");
+            output.push_str(span.emphasis.markers(format).0);
+            open.push(span);
+        }
+    }
+    output.push_str(&escape(&text[position..]));
+    output
+}
+
+pub(crate) fn render(element: &Element, format: RichTextFormat) -> String {
+    render_runs(&element.text, &runs(element), format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MARKDOWN: RichTextFormat = RichTextFormat::Markdown;
+    const HTML: RichTextFormat = RichTextFormat::Html;
+
+    #[test]
+    fn test_render_runs() {
+        let text = "Read the terms before signing.";
+        assert_eq!(
+            render_runs(text, &[("terms", "b"), ("signing", "i")], MARKDOWN),
+            "Read the **terms** before _signing_."
+        );
+        assert_eq!(
+            render_runs(text, &[("terms", "<strong>"), ("signing", "em")], HTML),
+            "Read the <b>terms</b> before <i>signing</i>."
+        );
+        assert_eq!(
+            render_runs("a < b & c", &[("b", "b")], HTML),
+            "a &lt; <b>b</b> &amp; c"
+        );
+        assert_eq!(render_runs("", &[], MARKDOWN), "");
+    }
+
+    #[test]
+    fn test_render_overlapping_runs() {
+        let text = "This is very important indeed.";
+        assert_eq!(
+            render_runs(
+                text,
+                &[("very important", "b"), ("important", "i")],
+                MARKDOWN
+            ),
+            "This is **very _important_** indeed."
+        );
+        assert_eq!(
+            render_runs(text, &[("important", "i"), ("very important", "b")], HTML),
+            "This is <b>very <i>important</i></b> indeed."
+        );
+        // Runs that cross each other can't be nested, so the second one is left out.
+        assert_eq!(
+            render_runs(text, &[("is very", "b"), ("very important", "i")], MARKDOWN),
+            "This **is very** important indeed."
+        );
+    }
+
+    #[test]
+    fn test_render_repeated_runs() {
+        let text = "Note: one note, and another note.";
+        // Repeats with the same emphasis are matched in order, from the previous run onwards.
+        assert_eq!(
+            render_runs(text, &[("note", "b"), ("note", "b")], MARKDOWN),
+            "Note: one **note**, and another **note**."
+        );
+        // With a different emphasis, the same occurrence is both bold and italic.
+        assert_eq!(
+            render_runs(text, &[("note", "b"), ("note", "i")], MARKDOWN),
+            "Note: one **_note_**, and another note."
+        );
+        // A run listed after a later one is still found before it.
+        assert_eq!(
+            render_runs(text, &[("another", "i"), ("one", "b")], MARKDOWN),
+            "Note: **one** note, and _another_ note."
+        );
+    }
+
+    #[test]
+    fn test_render_unlocatable_runs() {
+        let text = "Plain text.";
+        assert_eq!(
+            render_runs(
+                text,
+                &[("missing", "b"), ("", "i"), ("text", "u")],
+                MARKDOWN
+            ),
+            text
+        );
+        assert_eq!(
+            render_runs(text, &[(" text ", "b")], MARKDOWN),
+            "Plain **text**."
+        );
+    }
+
+    #[test]
+    fn test_to_rich_text() {
+        let element: Element = serde_json::from_value(serde_json::json!({
+            "type": "NarrativeText", "element_id": "1", "text": "An important point.",
+            "metadata": {"filetype": "text/html", "emphasized_text_contents": "important",
+                         "emphasized_text_tags": "b"}
+        }))
+        .unwrap();
+        assert_eq!(
+            element.to_rich_text(RichTextFormat::Markdown),
+            "An **important** point."
+        );
+
+        let element: Element = serde_json::from_value(serde_json::json!({
+            "type": "NarrativeText", "element_id": "3", "text": "Bold, then italic.",
+            "metadata": {"filetype": "text/html",
+                         "emphasized_text_contents": ["Bold", "italic"],
+                         "emphasized_text_tags": ["b", "i"]}
+        }))
+        .unwrap();
+        assert_eq!(
+            element.to_rich_text(RichTextFormat::Html),
+            "<b>Bold</b>, then <i>italic</i>."
+        );
+
+        let plain: Element = serde_json::from_value(serde_json::json!({
+            "type": "NarrativeText", "element_id": "2", "text": "x < y"
+        }))
+        .unwrap();
+        assert_eq!(plain.to_rich_text(RichTextFormat::Markdown), "x < y");
+        assert_eq!(plain.to_rich_text(RichTextFormat::Html), "x &lt; y");
+    }
+}
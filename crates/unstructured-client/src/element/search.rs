@@ -0,0 +1,234 @@
+use std::ops::Range;
+
+#[cfg(feature = "regex")]
+use regex::Regex;
+
+use crate::element::Element;
+
+/// A match of a search pattern in the text of an element.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    /// Index of the element in the list.
+    pub index: usize,
+
+    /// Byte range of the match within the element text.
+    pub range: Range<usize>,
+
+    /// The matched text.
+    pub matched: String,
+
+    /// Page number of the element, if known.
+    pub page_number: Option<u32>,
+}
+
+impl SearchHit {
+    fn new(index: usize, element: &Element, range: Range<usize>) -> Self {
+        SearchHit {
+            index,
+            matched: element.text[range.clone()].to_string(),
+            range,
+            page_number: element.page_number(),
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+pub(crate) fn regex(elements: &[Element], pattern: &Regex) -> Vec<SearchHit> {
+    elements
+        .iter()
+        .enumerate()
+        .flat_map(|(index, element)| {
+            pattern
+                .find_iter(&element.text)
+                .map(move |m| SearchHit::new(index, element, m.range()))
+        })
+        .collect()
+}
+
+/// The byte length of the match of `needle` at the start of `text`, if any. Case is compared
+/// per character, so that e.g. `Ü` and `ü` are equal.
+fn match_len(text: &str, needle: &str, ignore_case: bool) -> Option<usize> {
+    let mut chars = text.char_indices();
+    for expected in needle.chars() {
+        let (_, c) = chars.next()?;
+        if c != expected && !(ignore_case && c.to_lowercase().eq(expected.to_lowercase())) {
+            return None;
+        }
+    }
+    Some(chars.next().map_or(text.len(), |(end, _)| end))
+}
+
+/// The byte ranges of the occurrences of `needle` in `text`, including overlapping ones.
+fn occurrences<'a>(
+    text: &'a str,
+    needle: &'a str,
+    ignore_case: bool,
+) -> impl Iterator<Item = Range<usize>> + 'a {
+    text.char_indices()
+        .filter(move |_| !needle.is_empty())
+        .filter_map(move |(start, _)| {
+            match_len(&text[start..], needle, ignore_case).map(|len| start..start + len)
+        })
+}
+
+/// Whether `text` contains `needle`. An empty `needle` is never contained.
+pub(crate) fn contains(text: &str, needle: &str, ignore_case: bool) -> bool {
+    occurrences(text, needle, ignore_case).next().is_some()
+}
+
+pub(crate) fn substring(elements: &[Element], needle: &str, ignore_case: bool) -> Vec<SearchHit> {
+    elements
+        .iter()
+        .enumerate()
+        .flat_map(|(index, element)| {
+            occurrences(&element.text, needle, ignore_case)
+                .map(move |range| SearchHit::new(index, element, range))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    fn fixture() -> ElementList {
+        let json_str = r#"
+        [
+            {"type": "NarrativeText", "element_id": "1", "text": "Pay NL91 ABNA 0417 or NL20 INGB 0001.",
+             "metadata": {"filetype": "application/pdf", "page_number": 3}},
+            {"type": "Title", "element_id": "2", "text": "No accounts here"},
+            {"type": "NarrativeText", "element_id": "3", "text": "Banana, BANANA and bananas",
+             "metadata": {"filetype": "application/pdf", "page_number": 4}}
+        ]
+        "#;
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_search_multiple_matches() {
+        let pattern = Regex::new(r"NL\d{2} [A-Z]{4} \d{4}").unwrap();
+        let hits = fixture().search(&pattern);
+
+        assert_eq!(
+            hits,
+            vec![
+                SearchHit {
+                    index: 0,
+                    range: 4..18,
+                    matched: "NL91 ABNA 0417".to_string(),
+                    page_number: Some(3),
+                },
+                SearchHit {
+                    index: 0,
+                    range: 22..36,
+                    matched: "NL20 INGB 0001".to_string(),
+                    page_number: Some(3),
+                },
+            ]
+        );
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_regex_search_is_not_overlapping() {
+        let pattern = Regex::new(r"\d+").unwrap();
+        let hits = fixture().search(&pattern);
+        let matched: Vec<&str> = hits.iter().map(|hit| hit.matched.as_str()).collect();
+        assert_eq!(matched, vec!["91", "0417", "20", "0001"]);
+    }
+
+    #[test]
+    fn test_substring_search() {
+        let elements = fixture();
+
+        let hits = elements.search_text("banana", false);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].index, 2);
+        assert_eq!(hits[0].range, 19..25);
+
+        let hits = elements.search_text("banana", true);
+        let matched: Vec<&str> = hits.iter().map(|hit| hit.matched.as_str()).collect();
+        assert_eq!(matched, vec!["Banana", "BANANA", "banana"]);
+        assert!(hits.iter().all(|hit| hit.page_number == Some(4)));
+    }
+
+    #[test]
+    fn test_substring_search_overlapping() {
+        let elements = fixture();
+        let hits = elements.search_text("ana", true);
+        let starts: Vec<usize> = hits.iter().map(|hit| hit.range.start).collect();
+        assert_eq!(starts, vec![1, 3, 9, 11, 20, 22]);
+        assert!(elements.search_text("", true).is_empty());
+    }
+
+    #[test]
+    fn test_substring_search_non_ascii() {
+        let elements: ElementList = serde_json::from_str(
+            r#"[{"type": "Title", "element_id": "1", "text": "Ünïcode ünïcode"}]"#,
+        )
+        .unwrap();
+        let hits = elements.search_text("ÜNÏ", true);
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[1].matched, "ünï");
+        assert_eq!(&elements[0].text[hits[1].range.clone()], "ünï");
+    }
+
+    #[test]
+    fn test_find_by_text() {
+        let elements = fixture();
+        fn id(element: Option<&Element>) -> Option<&str> {
+            element.map(|e| e.element_id.as_str())
+        }
+
+        assert_eq!(id(elements.find_by_text("accounts")), Some("2"));
+        assert_eq!(id(elements.find_by_text("NO ACCOUNTS")), Some("2"));
+        assert_eq!(
+            id(elements.find_by_text_case_sensitive("NO ACCOUNTS")),
+            None
+        );
+        assert_eq!(
+            id(elements.find_by_text_case_sensitive("No accounts")),
+            Some("2")
+        );
+        assert_eq!(id(elements.find_by_text("")), None);
+
+        let ids: Vec<&str> = elements
+            .containing_text("n", true)
+            .map(|e| e.element_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["1", "2", "3"]);
+        assert_eq!(elements.containing_text("L", false).count(), 1);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_matching() {
+        let elements = fixture();
+        let pattern = Regex::new(r"(?i)^banana\b").unwrap();
+        let ids: Vec<&str> = elements
+            .matching(&pattern)
+            .map(|e| e.element_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["3"]);
+    }
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn test_matching_regex() {
+        let elements = fixture();
+        let ids = |pattern: &str| -> Vec<String> {
+            elements
+                .matching_regex(pattern)
+                .unwrap()
+                .iter()
+                .map(|e| e.element_id.to_string())
+                .collect()
+        };
+        assert_eq!(ids(r"^banana\b"), vec!["3"]);
+        assert_eq!(ids(r"(?-i)^banana\b"), Vec::<String>::new());
+        assert_eq!(ids(r"nl\d{2}"), vec!["1"]);
+        assert!(elements.matching_regex("(unclosed").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use rusqlite::{params, Connection};
+
+use crate::element::Element;
+use crate::error::Result;
+
+/// Quotes `name` as an SQL identifier, so that any table name can be used.
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+pub(crate) fn insert(elements: &[Element], conn: &Connection, table: &str) -> Result<usize> {
+    let table = quote_identifier(table);
+    // Rolled back on drop, so that a failing insert leaves no partial rows behind.
+    let transaction = conn.unchecked_transaction()?;
+    transaction.execute(
+        &format!(
+            "CREATE TABLE IF NOT EXISTS {table} (
+                element_id TEXT PRIMARY KEY,
+                type TEXT,
+                text TEXT,
+                page_number INTEGER,
+                filename TEXT,
+                metadata_json TEXT
+            )"
+        ),
+        (),
+    )?;
+
+    let mut rows = 0;
+    {
+        let mut statement = transaction.prepare(&format!(
+            "INSERT INTO {table} (element_id, type, text, page_number, filename, metadata_json)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)"
+        ))?;
+        for element in elements {
+            let metadata_json = element
+                .metadata
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()?;
+            rows += statement.execute(params![
+                element.element_id.as_str(),
+                element.r#type.to_string(),
+                element.text,
+                element.page_number(),
+                element
+                    .common_metadata()
+                    .and_then(|metadata| metadata.filename.as_deref()),
+                metadata_json,
+            ])?;
+        }
+    }
+    transaction.commit()?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    fn elements() -> ElementList {
+        serde_json::from_value(serde_json::json!([
+            {"type": "Title", "element_id": "1", "text": "Report",
+             "metadata": {"filetype": "application/pdf", "filename": "report.pdf", "page_number": 1}},
+            {"type": "NarrativeText", "element_id": "2", "text": "Body"}
+        ]))
+        .unwrap()
+    }
+
+    /// A row of the table: element_id, type, text, page_number, filename and metadata_json.
+    type Row = (
+        String,
+        String,
+        String,
+        Option<u32>,
+        Option<String>,
+        Option<String>,
+    );
+
+    #[test]
+    fn test_to_sqlite() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        assert_eq!(elements().to_sqlite(&conn, "elements")?, 2);
+
+        let rows: Vec<Row> = conn
+            .prepare("SELECT * FROM elements ORDER BY element_id")?
+            .query_map((), |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                    row.get(5)?,
+                ))
+            })?
+            .collect::<rusqlite::Result<_>>()?;
+        assert_eq!(rows[0].0, "1");
+        assert_eq!(rows[0].1, "Title");
+        assert_eq!(rows[0].3, Some(1));
+        assert_eq!(rows[0].4.as_deref(), Some("report.pdf"));
+        let metadata: serde_json::Value = serde_json::from_str(rows[0].5.as_ref().unwrap())?;
+        assert_eq!(metadata["filetype"], "application/pdf");
+        assert_eq!(
+            rows[1],
+            (
+                "2".into(),
+                "NarrativeText".into(),
+                "Body".into(),
+                None,
+                None,
+                None
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_sqlite_existing_table() -> Result<()> {
+        let conn = Connection::open_in_memory()?;
+        let table = r#"my "elements""#;
+        let first: ElementList = elements().iter().take(1).cloned().collect();
+        assert_eq!(first.to_sqlite(&conn, table)?, 1);
+
+        // Inserting an element that is already stored fails, and inserts none of the others.
+        assert!(elements().to_sqlite(&conn, table).is_err());
+        let count: usize =
+            conn.query_row(r#"SELECT COUNT(*) FROM "my ""elements""""#, (), |row| {
+                row.get(0)
+            })?;
+        assert_eq!(count, 1);
+        assert_eq!(ElementList::default().to_sqlite(&conn, table)?, 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::element::{Element, ElementType};
+
+/// Summary statistics of an element list, see
+/// [ElementList::stats](crate::ElementList::stats).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementStats {
+    /// Number of elements per type, in order of first appearance.
+    pub type_counts: Vec<(ElementType, usize)>,
+
+    /// Number of characters in all element texts.
+    pub total_chars: usize,
+
+    /// Number of characters per page. Elements without a page number aren't counted.
+    pub chars_per_page: BTreeMap<u32, usize>,
+
+    /// Number of [ElementType::Table] elements.
+    pub table_count: usize,
+
+    /// Distribution of the element lengths, when the list has been chunked (i.e. contains
+    /// [ElementType::CompositeElement] or [ElementType::TableChunk] elements).
+    pub chunk_lengths: Option<LengthDistribution>,
+}
+
+/// Distribution of text lengths in characters.
+///
+/// Percentiles use the nearest-rank method, so they are always one of the observed lengths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LengthDistribution {
+    pub count: usize,
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    pub p50: usize,
+    pub p95: usize,
+}
+
+impl LengthDistribution {
+    /// Returns `None` when there are no lengths.
+    pub fn new(mut lengths: Vec<usize>) -> Option<Self> {
+        if lengths.is_empty() {
+            return None;
+        }
+        lengths.sort_unstable();
+
+        let count = lengths.len();
+        Some(LengthDistribution {
+            count,
+            min: lengths[0],
+            max: lengths[count - 1],
+            mean: lengths.iter().sum::<usize>() as f64 / count as f64,
+            p50: percentile(&lengths, 50),
+            p95: percentile(&lengths, 95),
+        })
+    }
+}
+
+/// Nearest-rank percentile of a non-empty, sorted slice.
+fn percentile(sorted: &[usize], p: usize) -> usize {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+pub(crate) fn compute(elements: &[Element]) -> ElementStats {
+    let mut type_counts: Vec<(ElementType, usize)> = Vec::new();
+    let mut total_chars = 0;
+    let mut chars_per_page = BTreeMap::new();
+
+    for element in elements {
+        match type_counts.iter_mut().find(|(t, _)| *t == element.r#type) {
+            Some((_, count)) => *count += 1,
+            None => type_counts.push((element.r#type.clone(), 1)),
+        }
+
+        let chars = element.char_count();
+        total_chars += chars;
+        if let Some(page_number) = element.page_number() {
+            *chars_per_page.entry(page_number).or_default() += chars;
+        }
+    }
+
+    let count_of = |element_type| {
+        type_counts
+            .iter()
+            .find(|(t, _)| *t == element_type)
+            .map_or(0, |(_, count)| *count)
+    };
+    let chunked =
+        count_of(ElementType::CompositeElement) > 0 || count_of(ElementType::TableChunk) > 0;
+    let chunk_lengths = if chunked {
+        LengthDistribution::new(elements.iter().map(Element::char_count).collect())
+    } else {
+        None
+    };
+
+    ElementStats {
+        table_count: count_of(ElementType::Table),
+        type_counts,
+        total_chars,
+        chars_per_page,
+        chunk_lengths,
+    }
+}
+
+/// A compact, aligned table, e.g.
+///
+/// ```text
+/// Elements        3
+/// Characters     42
+/// Tables          0
+///   Title         1
+///   NarrativeText 2
+/// Page 1         42
+/// ```
+impl fmt::Display for ElementStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let element_count: usize = self.type_counts.iter().map(|(_, count)| count).sum();
+
+        let mut rows = vec![
+            ("Elements".to_string(), element_count.to_string()),
+            ("Characters".to_string(), self.total_chars.to_string()),
+            ("Tables".to_string(), self.table_count.to_string()),
+        ];
+        rows.extend(
+            self.type_counts
+                .iter()
+                .map(|(t, count)| (format!("  {t}"), count.to_string())),
+        );
+        rows.extend(
+            self.chars_per_page
+                .iter()
+                .map(|(page, chars)| (format!("Page {page}"), chars.to_string())),
+        );
+        if let Some(chunks) = &self.chunk_lengths {
+            rows.extend([
+                ("Chunks".to_string(), chunks.count.to_string()),
+                ("  min".to_string(), chunks.min.to_string()),
+                ("  p50".to_string(), chunks.p50.to_string()),
+                ("  p95".to_string(), chunks.p95.to_string()),
+                ("  max".to_string(), chunks.max.to_string()),
+                ("  mean".to_string(), format!("{:.1}", chunks.mean)),
+            ]);
+        }
+
+        let label_width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        let value_width = rows.iter().map(|(_, value)| value.len()).max().unwrap_or(0);
+        for (label, value) in rows {
+            writeln!(f, "{label:<label_width$} {value:>value_width$}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    fn element(r#type: &str, text: &str, page_number: Option<u32>) -> serde_json::Value {
+        serde_json::json!({
+            "type": r#type,
+            "element_id": "id",
+            "text": text,
+            "metadata": {"filetype": "application/pdf", "page_number": page_number},
+        })
+    }
+
+    #[test]
+    fn test_stats() {
+        let elements: ElementList = serde_json::from_value(serde_json::json!([
+            element("Title", "Intro", Some(1)),
+            element("NarrativeText", "Some text here.", Some(1)),
+            element("Table", "a b", Some(2)),
+            element("NarrativeText", "More", None),
+        ]))
+        .unwrap();
+
+        let stats = elements.stats();
+        assert_eq!(
+            stats.type_counts,
+            vec![
+                (ElementType::Title, 1),
+                (ElementType::NarrativeText, 2),
+                (ElementType::Table, 1),
+            ]
+        );
+        assert_eq!(stats.total_chars, 27);
+        assert_eq!(stats.chars_per_page, BTreeMap::from([(1, 20), (2, 3)]));
+        assert_eq!(stats.table_count, 1);
+        assert_eq!(stats.chunk_lengths, None);
+
+        assert_eq!(
+            stats.to_string(),
+            "\
+Elements         4
+Characters      27
+Tables           1
+  Title          1
+  NarrativeText  2
+  Table          1
+Page 1          20
+Page 2           3
+"
+        );
+    }
+
+    #[test]
+    fn test_chunk_lengths() {
+        let texts: Vec<String> = (1..=20).map(|n| "x".repeat(n * 10)).collect();
+        let mut chunks: Vec<_> = texts
+            .iter()
+            .map(|text| element("CompositeElement", text, Some(1)))
+            .collect();
+        chunks.push(element("Table", "", Some(2)));
+        let elements: ElementList = serde_json::from_value(chunks.into()).unwrap();
+
+        let lengths = elements.stats().chunk_lengths.unwrap();
+        assert_eq!(
+            lengths,
+            LengthDistribution {
+                count: 21,
+                min: 0,
+                max: 200,
+                mean: 100.0,
+                p50: 100,
+                p95: 190,
+            }
+        );
+    }
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(&[7], 50), 7);
+        assert_eq!(percentile(&[7], 95), 7);
+        assert_eq!(percentile(&[1, 2, 3, 4], 50), 2);
+        assert_eq!(percentile(&[1, 2, 3, 4], 95), 4);
+        assert_eq!(LengthDistribution::new(vec![]), None);
+    }
+}
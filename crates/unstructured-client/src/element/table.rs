@@ -0,0 +1,537 @@
+use tl::{HTMLTag, NodeHandle, Parser, ParserOptions};
+
+use crate::error::{ClientError, Result};
+
+/// Upper bound for `colspan` values, as per the HTML specification.
+const MAX_COLSPAN: usize = 1000;
+
+/// A single table cell.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Cell {
+    /// Text content of the cell, with nested markup stripped and whitespace collapsed.
+    pub text: String,
+
+    /// Whether this is a header cell, i.e. a `<th>` or any cell inside `<thead>`.
+    pub is_header: bool,
+
+    /// Whether this position is covered by the `rowspan`/`colspan` of another cell. The `text` of
+    /// a spanned cell is a copy of the text of the spanning cell.
+    pub spanned: bool,
+}
+
+/// A table parsed from HTML, with row and column spans expanded so that every row has the same
+/// number of cells.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Table {
+    /// All rows of the table, including the header rows.
+    pub rows: Vec<Vec<Cell>>,
+
+    /// Number of leading rows that form the table header.
+    pub header_rows: usize,
+}
+
+/// How line breaks inside cells are rendered by [Table::to_markdown_with].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineBreaks {
+    /// Replace line breaks with a space.
+    #[default]
+    Space,
+
+    /// Replace line breaks with `<br>`, which GitHub renders as a line break.
+    Br,
+}
+
+/// Options for [Table::to_markdown_with].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MarkdownTableOptions {
+    pub line_breaks: LineBreaks,
+}
+
+/// A cell as it appears in the markup, before span expansion.
+struct SourceCell {
+    text: String,
+    is_header: bool,
+    rowspan: usize,
+    colspan: usize,
+}
+
+struct SourceRow {
+    in_head: bool,
+    cells: Vec<SourceCell>,
+}
+
+impl Table {
+    /// Parses the first-level rows and cells out of `html`.
+    ///
+    /// Parsing is best-effort: malformed markup yields whatever rows and cells could be
+    /// recognized rather than an error. Leading rows inside `<thead>`, or consisting only of
+    /// `<th>` cells, are counted as header rows.
+    pub fn parse(html: &str) -> Result<Self> {
+        let dom = tl::parse(html, ParserOptions::default())
+            .map_err(|e| ClientError::HtmlParseFailed(e.to_string()))?;
+        let parser = dom.parser();
+
+        let mut source = Vec::new();
+        for handle in dom.children() {
+            collect_rows(*handle, parser, false, &mut source);
+        }
+
+        let header_rows = source
+            .iter()
+            .take_while(|row| row.in_head || row.cells.iter().all(|cell| cell.is_header))
+            .count();
+
+        Ok(Table {
+            rows: expand_spans(&source),
+            header_rows,
+        })
+    }
+
+    /// Number of columns in the table.
+    pub fn width(&self) -> usize {
+        self.rows.first().map(Vec::len).unwrap_or_default()
+    }
+
+    /// The header rows of the table.
+    pub fn header(&self) -> &[Vec<Cell>] {
+        &self.rows[..self.header_rows]
+    }
+
+    /// The rows following the header.
+    pub fn body(&self) -> &[Vec<Cell>] {
+        &self.rows[self.header_rows..]
+    }
+
+    /// Renders the table as a GitHub-flavored Markdown table, see
+    /// [to_markdown_with](Self::to_markdown_with).
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(&MarkdownTableOptions::default())
+    }
+
+    /// Renders the table as a GitHub-flavored Markdown table, without a trailing newline.
+    ///
+    /// Markdown tables have exactly one header row, so several header rows are combined: each
+    /// column gets the texts of its header cells joined with ` / `, skipping repeats (e.g. from a
+    /// `rowspan`). Without header rows the first row is used as the header. In the body, the
+    /// text of a cell spanning several rows or columns only appears in its first position, and
+    /// the positions it covers are left empty. Pipes are escaped. An empty table renders as an
+    /// empty string.
+    pub fn to_markdown_with(&self, options: &MarkdownTableOptions) -> String {
+        let width = self.width();
+        if width == 0 {
+            return String::new();
+        }
+
+        let (header, body) = match self.header_rows {
+            0 => self.rows.split_at(1),
+            n => self.rows.split_at(n),
+        };
+
+        let header_texts: Vec<String> = (0..width)
+            .map(|c| {
+                let mut texts: Vec<&str> = Vec::new();
+                for row in header {
+                    let text = row[c].text.as_str();
+                    if !text.is_empty() && texts.last() != Some(&text) {
+                        texts.push(text);
+                    }
+                }
+                texts.join(" / ")
+            })
+            .collect();
+
+        let render_row = |texts: Vec<&str>| {
+            let cells: Vec<String> = texts
+                .into_iter()
+                .map(|text| markdown_cell(text, options.line_breaks))
+                .collect();
+            format!("| {} |", cells.join(" | "))
+        };
+
+        let mut lines = vec![
+            render_row(header_texts.iter().map(String::as_str).collect()),
+            format!("|{}", " --- |".repeat(width)),
+        ];
+        lines.extend(body.iter().map(|row| {
+            render_row(
+                row.iter()
+                    .map(|cell| if cell.spanned { "" } else { cell.text.as_str() })
+                    .collect(),
+            )
+        }));
+        lines.join("\n")
+    }
+}
+
+fn markdown_cell(text: &str, line_breaks: LineBreaks) -> String {
+    let separator = match line_breaks {
+        LineBreaks::Space => " ",
+        LineBreaks::Br => "<br>",
+    };
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+        .replace('|', "\\|")
+}
+
+fn tag_name(tag: &HTMLTag) -> String {
+    tag.name().as_utf8_str().to_ascii_lowercase()
+}
+
+fn children(tag: &HTMLTag) -> Vec<NodeHandle> {
+    tag.children().top().as_slice().to_vec()
+}
+
+fn collect_rows(handle: NodeHandle, parser: &Parser, in_head: bool, rows: &mut Vec<SourceRow>) {
+    let Some(tag) = handle.get(parser).and_then(|node| node.as_tag()) else {
+        return;
+    };
+
+    let in_head = match tag_name(tag).as_str() {
+        "tr" => {
+            let mut cells = Vec::new();
+            for child in children(tag) {
+                collect_cells(child, parser, in_head, &mut cells);
+            }
+            if !cells.is_empty() {
+                rows.push(SourceRow { in_head, cells });
+            }
+            return;
+        }
+        "thead" => true,
+        "tbody" | "tfoot" => false,
+        _ => in_head,
+    };
+
+    for child in children(tag) {
+        collect_rows(child, parser, in_head, rows);
+    }
+}
+
+fn collect_cells(handle: NodeHandle, parser: &Parser, in_head: bool, cells: &mut Vec<SourceCell>) {
+    let Some(tag) = handle.get(parser).and_then(|node| node.as_tag()) else {
+        return;
+    };
+
+    let name = tag_name(tag);
+    match name.as_str() {
+        "td" | "th" => {
+            let span = |attribute: &str| {
+                tag.attributes()
+                    .get(attribute)
+                    .flatten()
+                    .and_then(|value| value.as_utf8_str().trim().parse::<usize>().ok())
+            };
+            cells.push(SourceCell {
+                text: normalize_cell(&cell_text(tag, parser)),
+                is_header: in_head || name == "th",
+                rowspan: span("rowspan").unwrap_or(1),
+                colspan: span("colspan").unwrap_or(1).clamp(1, MAX_COLSPAN),
+            });
+        }
+        // Don't descend into nested rows or tables
+        "tr" | "table" => {}
+        // Tolerate wrappers around cells in malformed markup
+        _ => {
+            for child in children(tag) {
+                collect_cells(child, parser, in_head, cells);
+            }
+        }
+    }
+}
+
+/// Text of all nodes below `tag`, separating line breaks and block elements by whitespace.
+fn cell_text(tag: &HTMLTag, parser: &Parser) -> String {
+    let mut text = String::new();
+    for child in children(tag) {
+        match child.get(parser) {
+            Some(tl::Node::Raw(raw)) => text.push_str(&raw.as_utf8_str()),
+            Some(tl::Node::Tag(child)) => {
+                let block = matches!(tag_name(child).as_str(), "br" | "p" | "div" | "li");
+                if block {
+                    text.push(' ');
+                }
+                text.push_str(&cell_text(child, parser));
+                if block {
+                    text.push(' ');
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Places each cell in a grid, copying it into every position covered by its spans. A `rowspan`
+/// of 0, or one reaching past the last row, spans to the end of the table.
+fn expand_spans(source: &[SourceRow]) -> Vec<Vec<Cell>> {
+    let height = source.len();
+    let mut grid: Vec<Vec<Option<Cell>>> = vec![Vec::new(); height];
+
+    for (r, row) in source.iter().enumerate() {
+        let mut c = 0;
+        for cell in &row.cells {
+            while grid[r].get(c).is_some_and(Option::is_some) {
+                c += 1;
+            }
+
+            let rowspan = match cell.rowspan {
+                0 => height - r,
+                n => n.min(height - r),
+            };
+            for i in 0..rowspan {
+                let target = &mut grid[r + i];
+                if target.len() < c + cell.colspan {
+                    target.resize(c + cell.colspan, None);
+                }
+                for j in 0..cell.colspan {
+                    target[c + j] = Some(Cell {
+                        text: cell.text.clone(),
+                        is_header: cell.is_header,
+                        spanned: i > 0 || j > 0,
+                    });
+                }
+            }
+            c += cell.colspan;
+        }
+    }
+
+    let width = grid.iter().map(Vec::len).max().unwrap_or_default();
+    grid.into_iter()
+        .map(|mut row| {
+            row.resize(width, None);
+            row.into_iter().map(Option::unwrap_or_default).collect()
+        })
+        .collect()
+}
+
+/// Collapses whitespace and decodes the common HTML entities in the text of a table cell.
+fn normalize_cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::Element;
+
+    fn texts(table: &Table) -> Vec<Vec<&str>> {
+        table
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|cell| cell.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_simple() -> Result<()> {
+        let table = Table::parse(
+            "<table><tr><td>a</td><td>b</td></tr><tr><td>c</td><td>d</td></tr></table>",
+        )?;
+        assert_eq!(texts(&table), vec![vec!["a", "b"], vec!["c", "d"]]);
+        assert_eq!(table.header_rows, 0);
+        assert_eq!(table.width(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_thead_tbody() -> Result<()> {
+        let table = Table::parse(
+            "<table><thead><tr><td>Name</td><td>Age</td></tr></thead>\
+             <tbody><tr><td>Ann</td><td>31</td></tr><tr><td>Bob</td><td>42</td></tr></tbody></table>",
+        )?;
+        assert_eq!(table.header_rows, 1);
+        assert!(table.header()[0].iter().all(|cell| cell.is_header));
+        assert_eq!(table.body().len(), 2);
+        assert!(table.body()[0].iter().all(|cell| !cell.is_header));
+        assert_eq!(table.body()[1][0].text, "Bob");
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_th_header() -> Result<()> {
+        let table = Table::parse(
+            "<table><tr><th>Key</th><th>Value</th></tr><tr><th>a</th><td>1</td></tr></table>",
+        )?;
+        assert_eq!(table.header_rows, 1);
+        assert!(table.rows[1][0].is_header);
+        assert!(!table.rows[1][1].is_header);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_colspan_rowspan() -> Result<()> {
+        let table = Table::parse(
+            "<table>\
+             <tr><th colspan=\"2\">Wide</th><th>C</th></tr>\
+             <tr><td rowspan=\"2\">Tall</td><td>1</td><td>2</td></tr>\
+             <tr><td>3</td><td>4</td></tr>\
+             </table>",
+        )?;
+        assert_eq!(
+            texts(&table),
+            vec![
+                vec!["Wide", "Wide", "C"],
+                vec!["Tall", "1", "2"],
+                vec!["Tall", "3", "4"],
+            ]
+        );
+        assert!(!table.rows[0][0].spanned);
+        assert!(table.rows[0][1].spanned);
+        assert!(table.rows[2][0].spanned);
+        assert!(!table.rows[2][1].spanned);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_nested_markup() -> Result<()> {
+        let table = Table::parse(
+            "<table><tr><td><b>Bold</b> and <i>italic</i></td>\
+             <td><p>Line&nbsp;one<br>line   two</p></td><td>A &amp; B</td></tr></table>",
+        )?;
+        assert_eq!(
+            texts(&table),
+            vec![vec!["Bold and italic", "Line one line two", "A & B"]]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_ragged_and_malformed() -> Result<()> {
+        let table = Table::parse("<table><tr><td>a</td><td>b</td><tr><td>c</td></table")?;
+        assert_eq!(table.width(), 2);
+        assert!(table.rows.iter().all(|row| row.len() == 2));
+        assert_eq!(table.rows[0][0].text, "a");
+
+        let table = Table::parse("not a table")?;
+        assert!(table.rows.is_empty());
+        assert_eq!(table.width(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_element_parse_table() -> Result<()> {
+        let element: Element = serde_json::from_str(
+            r#"{"type": "Table", "element_id": "1", "text": "a b",
+                "metadata": {"filetype": "application/pdf",
+                             "text_as_html": "<table><tr><td>a</td><td>b</td></tr></table>"}}"#,
+        )
+        .unwrap();
+        let table = element.parse_table()?.expect("Table expected");
+        assert_eq!(texts(&table), vec![vec!["a", "b"]]);
+
+        let element: Element =
+            serde_json::from_str(r#"{"type": "Table", "element_id": "2", "text": "a b"}"#).unwrap();
+        assert_eq!(element.parse_table()?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_markdown_with_header() -> Result<()> {
+        let table = Table::parse(
+            "<table><thead><tr><th>Name</th><th>Age</th></tr></thead>\
+             <tbody><tr><td>Ann</td><td>31</td></tr><tr><td>Bob | Jr.</td><td></td></tr></tbody></table>",
+        )?;
+        assert_eq!(
+            table.to_markdown(),
+            "| Name | Age |\n\
+             | --- | --- |\n\
+             | Ann | 31 |\n\
+             | Bob \\| Jr. |  |"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_markdown_without_header() -> Result<()> {
+        let table = Table::parse(
+            "<table><tr><td>a</td><td>b</td></tr><tr><td>c</td><td>d</td></tr></table>",
+        )?;
+        assert_eq!(table.to_markdown(), "| a | b |\n| --- | --- |\n| c | d |");
+
+        let table = Table::parse("<table><tr><td>only</td></tr></table>")?;
+        assert_eq!(table.to_markdown(), "| only |\n| --- |");
+
+        assert_eq!(Table::default().to_markdown(), "");
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_markdown_spans() -> Result<()> {
+        let table = Table::parse(
+            "<table>\
+             <tr><th rowspan=\"2\">Region</th><th colspan=\"2\">Revenue</th></tr>\
+             <tr><th>Q1</th><th>Q2</th></tr>\
+             <tr><td rowspan=\"2\">North</td><td>1</td><td>2</td></tr>\
+             <tr><td>3</td><td>4</td></tr>\
+             <tr><td colspan=\"3\">Total 10</td></tr>\
+             </table>",
+        )?;
+        assert_eq!(table.header_rows, 2);
+        assert_eq!(
+            table.to_markdown(),
+            "| Region | Revenue / Q1 | Revenue / Q2 |\n\
+             | --- | --- | --- |\n\
+             | North | 1 | 2 |\n\
+             |  | 3 | 4 |\n\
+             | Total 10 |  |  |"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_markdown_line_breaks() {
+        let cell = |text: &str| Cell {
+            text: text.to_string(),
+            ..Cell::default()
+        };
+        let table = Table {
+            rows: vec![
+                vec![cell("Key"), cell("Value")],
+                vec![cell("address"), cell("1 Main St\n  Springfield\n")],
+            ],
+            header_rows: 1,
+        };
+
+        assert_eq!(
+            table.to_markdown(),
+            "| Key | Value |\n| --- | --- |\n| address | 1 Main St Springfield |"
+        );
+        let options = MarkdownTableOptions {
+            line_breaks: LineBreaks::Br,
+        };
+        assert_eq!(
+            table.to_markdown_with(&options),
+            "| Key | Value |\n| --- | --- |\n| address | 1 Main St<br>Springfield |"
+        );
+    }
+
+    #[test]
+    fn test_element_table_markdown() -> Result<()> {
+        let element: Element = serde_json::from_str(
+            r#"{"type": "Table", "element_id": "1", "text": "a b",
+                "metadata": {"filetype": "application/pdf",
+                             "text_as_html": "<table><tr><th>a</th><th>b</th></tr><tr><td>1</td><td>2</td></tr></table>"}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            element.table_markdown()?.as_deref(),
+            Some("| a | b |\n| --- | --- |\n| 1 | 2 |")
+        );
+
+        let element: Element =
+            serde_json::from_str(r#"{"type": "Table", "element_id": "2", "text": "a b"}"#).unwrap();
+        assert_eq!(element.table_markdown()?, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use tiktoken_rs::tokenizer::{get_tokenizer, Tokenizer};
+use tiktoken_rs::CoreBPE;
+
+use crate::error::{ClientError, Result};
+
+/// Tokenizers are expensive to initialize, so each one is loaded once and shared.
+static TOKENIZERS: OnceLock<Mutex<HashMap<Tokenizer, Arc<CoreBPE>>>> = OnceLock::new();
+
+/// The tokenizer used by `model`, e.g. `gpt-4o` or `text-embedding-3-small`.
+pub(crate) fn tokenizer(model: &str) -> Result<Arc<CoreBPE>> {
+    let tokenizer = get_tokenizer(model)
+        .ok_or_else(|| ClientError::TokenizerFailed(format!("Unknown model '{model}'")))?;
+
+    let mut cache = TOKENIZERS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(bpe) = cache.get(&tokenizer) {
+        return Ok(bpe.clone());
+    }
+
+    let bpe = Arc::new(
+        tiktoken_rs::get_bpe_from_tokenizer(tokenizer)
+            .map_err(|e| ClientError::TokenizerFailed(e.to_string()))?,
+    );
+    cache.insert(tokenizer, bpe.clone());
+    Ok(bpe)
+}
+
+/// Number of tokens in `text`. Special tokens are counted as ordinary text.
+pub(crate) fn count(bpe: &CoreBPE, text: &str) -> usize {
+    bpe.encode_ordinary(text).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::element::ElementList;
+
+    fn fixture() -> ElementList {
+        let json_str = r#"
+        [
+            {"type": "CompositeElement", "element_id": "1", "text": "hello world"},
+            {"type": "CompositeElement", "element_id": "2", "text": "Hello, world!"},
+            {"type": "CompositeElement", "element_id": "3", "text": ""},
+            {"type": "CompositeElement", "element_id": "4", "text": "<|endoftext|>"}
+        ]
+        "#;
+        serde_json::from_str(json_str).unwrap()
+    }
+
+    #[test]
+    fn test_token_count() -> Result<()> {
+        let elements = fixture();
+        assert_eq!(elements[0].token_count("gpt-4")?, 2);
+        assert_eq!(elements[1].token_count("gpt-4")?, 4);
+        assert_eq!(elements[1].token_count("gpt-4o")?, 4);
+        assert_eq!(elements[2].token_count("gpt-4")?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_counts() -> Result<()> {
+        let counts = fixture().token_counts("gpt-4")?;
+        assert_eq!(counts[..3], [2, 4, 0]);
+        assert!(counts[3] > 1, "Special tokens are encoded as text");
+        Ok(())
+    }
+
+    #[test]
+    fn test_over_token_budget() -> Result<()> {
+        let elements = fixture();
+        assert_eq!(elements.over_token_budget("gpt-4", 3)?, vec![1, 3]);
+        assert!(elements.over_token_budget("gpt-4", 100)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_model() {
+        let result = fixture().token_counts("not-a-model");
+        assert!(matches!(result, Err(ClientError::TokenizerFailed(_))));
+    }
+
+    #[test]
+    fn test_tokenizer_is_cached() -> Result<()> {
+        let a = tokenizer("gpt-4")?;
+        let b = tokenizer("gpt-3.5-turbo")?;
+        assert!(Arc::ptr_eq(&a, &b));
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use std::ops::Range;
+
+use crate::element::Element;
+
+/// Separator between element texts in [ElementList::to_text](crate::ElementList::to_text).
+pub(crate) const TEXT_SEPARATOR: &str = "\n\n";
+
+/// A character range of the document text, see
+/// [ElementList::char_window](crate::ElementList::char_window).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowSlice {
+    /// The text in the window, including separators between elements.
+    pub text: String,
+
+    /// The elements the text came from, in order.
+    pub parts: Vec<WindowPart>,
+}
+
+/// The part of an element's text that falls within a [WindowSlice].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowPart {
+    /// Index of the element in the list.
+    pub index: usize,
+
+    /// Character range within the element text. Counts characters, not bytes.
+    pub range: Range<usize>,
+
+    /// Page number of the element, if known.
+    pub page_number: Option<u32>,
+}
+
+impl WindowSlice {
+    /// The distinct page numbers of the elements in the window, in order.
+    pub fn page_numbers(&self) -> Vec<u32> {
+        let mut page_numbers: Vec<u32> = Vec::new();
+        for page_number in self.parts.iter().filter_map(|part| part.page_number) {
+            if !page_numbers.contains(&page_number) {
+                page_numbers.push(page_number);
+            }
+        }
+        page_numbers
+    }
+}
+
+/// The elements that contribute to the document text, i.e. those with a non-empty text.
+pub(crate) fn texts(elements: &[Element]) -> impl Iterator<Item = (usize, &Element)> {
+    elements
+        .iter()
+        .enumerate()
+        .filter(|(_, element)| !element.text.is_empty())
+}
+
+pub(crate) fn to_text(elements: &[Element]) -> String {
+    texts(elements)
+        .map(|(_, element)| element.text.as_str())
+        .collect::<Vec<_>>()
+        .join(TEXT_SEPARATOR)
+}
+
+pub(crate) fn char_window(elements: &[Element], range: Range<usize>) -> WindowSlice {
+    let separator_len = TEXT_SEPARATOR.chars().count();
+    let mut window = WindowSlice::default();
+    let mut position = 0;
+
+    for (n, (index, element)) in texts(elements).enumerate() {
+        if position >= range.end {
+            break;
+        }
+        if n > 0 {
+            // The separator occupies [position, position + separator_len).
+            let start = range.start.max(position);
+            let end = range.end.min(position + separator_len);
+            window
+                .text
+                .extend(TEXT_SEPARATOR.chars().take(end.saturating_sub(start)));
+            position += separator_len;
+        }
+
+        let len = element.text.chars().count();
+        let start = range.start.max(position);
+        let end = range.end.min(position + len);
+        if start < end {
+            window.text.extend(
+                element
+                    .text
+                    .chars()
+                    .skip(start - position)
+                    .take(end - start),
+            );
+            window.parts.push(WindowPart {
+                index,
+                range: start - position..end - position,
+                page_number: element.page_number(),
+            });
+        }
+        position += len;
+    }
+    window
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::element::ElementList;
+
+    fn fixture() -> ElementList {
+        serde_json::from_value(serde_json::json!([
+            {"type": "Title", "element_id": "1", "text": "Intro",
+             "metadata": {"filetype": "application/pdf", "page_number": 1}},
+            {"type": "PageBreak", "element_id": "2", "text": ""},
+            {"type": "NarrativeText", "element_id": "3", "text": "Über",
+             "metadata": {"filetype": "application/pdf", "page_number": 2}},
+            {"type": "NarrativeText", "element_id": "4", "text": "end.",
+             "metadata": {"filetype": "application/pdf", "page_number": 2}}
+        ]))
+        .unwrap()
+    }
+
+    fn parts(elements: &ElementList, range: std::ops::Range<usize>) -> Vec<(usize, usize, usize)> {
+        elements
+            .char_window(range)
+            .parts
+            .iter()
+            .map(|part| (part.index, part.range.start, part.range.end))
+            .collect()
+    }
+
+    #[test]
+    fn test_to_text() {
+        assert_eq!(fixture().to_text(), "Intro\n\nÜber\n\nend.");
+        assert_eq!(ElementList::default().to_text(), "");
+    }
+
+    #[test]
+    fn test_char_window_matches_to_text() {
+        let elements = fixture();
+        let chars: Vec<char> = elements.to_text().chars().collect();
+        for start in 0..=chars.len() {
+            for end in start..=chars.len() + 1 {
+                let expected: String = chars[start..end.min(chars.len())].iter().collect();
+                assert_eq!(
+                    elements.char_window(start..end).text,
+                    expected,
+                    "{start}..{end}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_char_window_boundaries() {
+        let elements = fixture();
+
+        // "Intro" is 0..5, the separators 5..7 and 11..13, "Über" 7..11 and "end." 13..17.
+        assert_eq!(parts(&elements, 0..5), vec![(0, 0, 5)]);
+        assert_eq!(parts(&elements, 4..5), vec![(0, 4, 5)]);
+        assert_eq!(parts(&elements, 5..7), vec![]);
+        assert_eq!(elements.char_window(5..7).text, "\n\n");
+        assert_eq!(parts(&elements, 5..8), vec![(2, 0, 1)]);
+        assert_eq!(parts(&elements, 4..8), vec![(0, 4, 5), (2, 0, 1)]);
+        assert_eq!(parts(&elements, 7..11), vec![(2, 0, 4)]);
+        assert_eq!(parts(&elements, 10..14), vec![(2, 3, 4), (3, 0, 1)]);
+        assert_eq!(parts(&elements, 17..20), vec![]);
+        assert_eq!(parts(&elements, 3..3), vec![]);
+    }
+
+    #[test]
+    fn test_char_window_spanning_three_elements() {
+        let window = fixture().char_window(3..15);
+        assert_eq!(window.text, "ro\n\nÜber\n\nen");
+        assert_eq!(
+            window
+                .parts
+                .iter()
+                .map(|part| (part.index, part.range.clone()))
+                .collect::<Vec<_>>(),
+            vec![(0, 3..5), (2, 0..4), (3, 0..2)]
+        );
+        assert_eq!(window.page_numbers(), vec![1, 2]);
+    }
+}
@@ -1,3 +1,5 @@
+use std::error::Error as StdError;
+use std::io;
 use std::result::Result as BaseResult;
 use thiserror::Error;
 
@@ -6,12 +8,33 @@ pub enum ClientError {
     #[error("Network error: {0}")]
     RequestFailed(#[from] reqwest::Error),
 
+    #[error("Invalid header value: {0}")]
+    InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+
     #[error("Failed to parse URL: {0}")]
     URLParseFailed(String),
 
     #[error("Text extraction failed: {0}")]
     ExtractionFailed(String),
 
+    #[error("Failed to convert coordinates: {0}")]
+    CoordinatesConversionFailed(String),
+
+    #[error("Failed to parse HTML: {0}")]
+    HtmlParseFailed(String),
+
+    #[error("Failed to parse query string: {0}")]
+    QueryStringParseFailed(String),
+
+    #[error("Failed to parse arguments: {0}")]
+    ArgumentParseFailed(String),
+
+    #[error("Failed to decode orig_elements: {0}")]
+    OrigElementsDecodeFailed(String),
+
+    #[error("Tokenizer error: {0}")]
+    TokenizerFailed(String),
+
     #[error("Metadata field not present: {0}")]
     MetadataFieldNotPresent(String),
 
@@ -27,14 +50,185 @@ pub enum ClientError {
     #[error("Timeout occurred")]
     Timeout,
 
+    #[error("Request cancelled")]
+    Cancelled,
+
     #[error("Unexpected response from service: {0}")]
     UnexpectedResponse(String),
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error(transparent)]
+    JsonlParseFailed(#[from] crate::element::JsonlLineError),
+
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[cfg(feature = "arrow")]
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow_schema::ArrowError),
+
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("Invalid partition parameters: {0}")]
+    InvalidParameters(#[from] ParameterError),
+
+    #[error("HTTP options can't be set on a client passed to with_client, configure it instead")]
+    InjectedHttpClient,
+
     #[error("Other error: {0}")]
     Other(String),
 }
 
+impl ClientError {
+    /// Whether the error is likely transient, so that retrying the request may succeed.
+    ///
+    /// Network errors are retryable when the request timed out, or when the connection was
+    /// refused, reset or closed unexpectedly, as found by walking the [source](StdError::source)
+    /// chain of the error. Other network errors, such as DNS and TLS failures, aren't.
+    /// [ClientError::Timeout] and [ClientError::ServiceUnavailable] are retryable as well.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            ClientError::RequestFailed(e) => e.is_timeout() || has_transient_io_error(e),
+            ClientError::Timeout | ClientError::ServiceUnavailable(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// IO error kinds caused by the connection rather than by the request itself.
+const TRANSIENT_IO_ERRORS: &[io::ErrorKind] = &[
+    io::ErrorKind::ConnectionRefused,
+    io::ErrorKind::ConnectionReset,
+    io::ErrorKind::ConnectionAborted,
+    io::ErrorKind::BrokenPipe,
+    io::ErrorKind::TimedOut,
+    io::ErrorKind::UnexpectedEof,
+    io::ErrorKind::Interrupted,
+];
+
+fn has_transient_io_error(error: &(dyn StdError + 'static)) -> bool {
+    std::iter::successors(Some(error), |&e| e.source())
+        .filter_map(|e| e.downcast_ref::<io::Error>())
+        .any(|e| TRANSIENT_IO_ERRORS.contains(&e.kind()))
+}
+
+/// Invalid [PartitionParameters](crate::PartitionParameters) values, caught before a request is
+/// sent.
+#[derive(Debug, Error, PartialEq)]
+pub enum ParameterError {
+    #[error(
+        "Unknown image block type '{0}', expected one of {:?}",
+        crate::partition::IMAGE_BLOCK_TYPES
+    )]
+    UnknownImageBlockType(String),
+
+    #[error(
+        "Unknown document type '{0}', expected one of {:?}",
+        crate::partition::DOCUMENT_TYPES
+    )]
+    UnknownDocumentType(String),
+
+    #[error(
+        "Unknown chunking strategy '{0}', expected one of {:?}",
+        crate::partition::CHUNKING_STRATEGIES
+    )]
+    UnknownChunkingStrategy(String),
+
+    #[error("Unknown language code '{0}', expected a Tesseract language such as 'eng'")]
+    UnknownLanguage(String),
+
+    #[error("Empty hi_res model name")]
+    EmptyHiResModelName,
+}
+
+/// [PartitionParameters](crate::PartitionParameters) values that are accepted but likely not
+/// what was intended, see [PartitionParameters::warnings](crate::PartitionParameters::warnings).
+#[derive(Debug, Error, PartialEq)]
+pub enum ValidationWarning {
+    #[error(
+        "new_after_n_chars ({new_after_n_chars}) is not below max_characters ({max_characters}), \
+         so the soft maximum has no effect"
+    )]
+    SoftMaxExceedsHardMax {
+        new_after_n_chars: i32,
+        max_characters: i32,
+    },
+}
+
 pub type Result<T> = BaseResult<T, ClientError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt;
+
+    /// An error wrapping another one, like the errors of the HTTP stack do.
+    #[derive(Debug)]
+    struct Wrapper(io::Error);
+
+    impl fmt::Display for Wrapper {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("wrapper")
+        }
+    }
+
+    impl StdError for Wrapper {
+        fn source(&self) -> Option<&(dyn StdError + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn test_transient_io_errors() {
+        let wrapped = |kind| Wrapper(io::Error::new(kind, "inner"));
+
+        assert!(has_transient_io_error(&wrapped(
+            io::ErrorKind::ConnectionReset
+        )));
+        assert!(has_transient_io_error(&wrapped(io::ErrorKind::TimedOut)));
+        assert!(has_transient_io_error(&io::Error::from(
+            io::ErrorKind::BrokenPipe
+        )));
+
+        // DNS failures surface as uncategorized IO errors, TLS failures as invalid data.
+        let dns = Wrapper(io::Error::other("failed to lookup address information"));
+        assert!(!has_transient_io_error(&dns));
+        assert!(!has_transient_io_error(&wrapped(
+            io::ErrorKind::InvalidData
+        )));
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(ClientError::Timeout.is_retryable());
+        assert!(ClientError::ServiceUnavailable("busy".to_string()).is_retryable());
+        assert!(!ClientError::Unauthorized("no key".to_string()).is_retryable());
+        assert!(!ClientError::Io(io::Error::from(io::ErrorKind::ConnectionReset)).is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_connection_refused_is_retryable() {
+        // Bind and drop a listener to get a local port nothing listens on.
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+
+        let error = reqwest::get(format!("http://127.0.0.1:{port}"))
+            .await
+            .unwrap_err();
+        assert!(error.is_connect());
+        assert!(ClientError::from(error).is_retryable());
+
+        let error = reqwest::get("http://[::1").await.unwrap_err();
+        assert!(!ClientError::from(error).is_retryable());
+    }
+}
@@ -0,0 +1,431 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::ParameterError;
+
+/// Language codes of the Tesseract 4.0 trained data, as accepted by the `languages` partition
+/// parameter.
+///
+/// Sorted, so it can be searched with `binary_search`.
+pub const TESSERACT_LANGUAGES: &[&str] = &[
+    "afr",
+    "amh",
+    "ara",
+    "asm",
+    "aze",
+    "aze_cyrl",
+    "bel",
+    "ben",
+    "bod",
+    "bos",
+    "bre",
+    "bul",
+    "cat",
+    "ceb",
+    "ces",
+    "chi_sim",
+    "chi_sim_vert",
+    "chi_tra",
+    "chi_tra_vert",
+    "chr",
+    "cos",
+    "cym",
+    "dan",
+    "deu",
+    "div",
+    "dzo",
+    "ell",
+    "eng",
+    "enm",
+    "epo",
+    "equ",
+    "est",
+    "eus",
+    "fao",
+    "fas",
+    "fil",
+    "fin",
+    "fra",
+    "frk",
+    "frm",
+    "fry",
+    "gla",
+    "gle",
+    "glg",
+    "grc",
+    "guj",
+    "hat",
+    "heb",
+    "hin",
+    "hrv",
+    "hun",
+    "hye",
+    "iku",
+    "ind",
+    "isl",
+    "ita",
+    "ita_old",
+    "jav",
+    "jpn",
+    "jpn_vert",
+    "kan",
+    "kat",
+    "kat_old",
+    "kaz",
+    "khm",
+    "kir",
+    "kmr",
+    "kor",
+    "kor_vert",
+    "lao",
+    "lat",
+    "lav",
+    "lit",
+    "ltz",
+    "mal",
+    "mar",
+    "mkd",
+    "mlt",
+    "mon",
+    "mri",
+    "msa",
+    "mya",
+    "nep",
+    "nld",
+    "nor",
+    "oci",
+    "ori",
+    "osd",
+    "pan",
+    "pol",
+    "por",
+    "pus",
+    "que",
+    "ron",
+    "rus",
+    "san",
+    "sin",
+    "slk",
+    "slv",
+    "snd",
+    "spa",
+    "spa_old",
+    "sqi",
+    "srp",
+    "srp_latn",
+    "sun",
+    "swa",
+    "swe",
+    "syr",
+    "tam",
+    "tat",
+    "tel",
+    "tgk",
+    "tha",
+    "tir",
+    "ton",
+    "tur",
+    "uig",
+    "ukr",
+    "urd",
+    "uzb",
+    "uzb_cyrl",
+    "vie",
+    "yid",
+    "yor",
+];
+
+/// Other codes for the languages in [TESSERACT_LANGUAGES], sorted by alias: ISO 639-1 codes, the
+/// ISO 639-2/B variants of ISO 639-2/T codes, and a few deprecated or regional codes.
+const LANGUAGE_ALIASES: &[(&str, &str)] = &[
+    ("af", "afr"),
+    ("alb", "sqi"),
+    ("am", "amh"),
+    ("ar", "ara"),
+    ("arm", "hye"),
+    ("as", "asm"),
+    ("az", "aze"),
+    ("baq", "eus"),
+    ("be", "bel"),
+    ("bg", "bul"),
+    ("bn", "ben"),
+    ("bo", "bod"),
+    ("br", "bre"),
+    ("bs", "bos"),
+    ("bur", "mya"),
+    ("ca", "cat"),
+    ("chi", "chi_sim"),
+    ("co", "cos"),
+    ("cs", "ces"),
+    ("cy", "cym"),
+    ("cze", "ces"),
+    ("da", "dan"),
+    ("de", "deu"),
+    ("dut", "nld"),
+    ("dv", "div"),
+    ("dz", "dzo"),
+    ("el", "ell"),
+    ("en", "eng"),
+    ("eo", "epo"),
+    ("es", "spa"),
+    ("et", "est"),
+    ("eu", "eus"),
+    ("fa", "fas"),
+    ("fi", "fin"),
+    ("fo", "fao"),
+    ("fr", "fra"),
+    ("fre", "fra"),
+    ("fy", "fry"),
+    ("ga", "gle"),
+    ("gd", "gla"),
+    ("geo", "kat"),
+    ("ger", "deu"),
+    ("gl", "glg"),
+    ("gre", "ell"),
+    ("gu", "guj"),
+    ("he", "heb"),
+    ("hi", "hin"),
+    ("hr", "hrv"),
+    ("ht", "hat"),
+    ("hu", "hun"),
+    ("hy", "hye"),
+    ("ice", "isl"),
+    ("id", "ind"),
+    ("in", "ind"),
+    ("is", "isl"),
+    ("it", "ita"),
+    ("iu", "iku"),
+    ("iw", "heb"),
+    ("ja", "jpn"),
+    ("ji", "yid"),
+    ("jv", "jav"),
+    ("ka", "kat"),
+    ("kk", "kaz"),
+    ("km", "khm"),
+    ("kn", "kan"),
+    ("ko", "kor"),
+    ("ku", "kmr"),
+    ("ky", "kir"),
+    ("la", "lat"),
+    ("lb", "ltz"),
+    ("lo", "lao"),
+    ("lt", "lit"),
+    ("lv", "lav"),
+    ("mac", "mkd"),
+    ("mao", "mri"),
+    ("may", "msa"),
+    ("mi", "mri"),
+    ("mk", "mkd"),
+    ("ml", "mal"),
+    ("mn", "mon"),
+    ("mr", "mar"),
+    ("ms", "msa"),
+    ("mt", "mlt"),
+    ("my", "mya"),
+    ("nb", "nor"),
+    ("ne", "nep"),
+    ("nl", "nld"),
+    ("nn", "nor"),
+    ("nno", "nor"),
+    ("no", "nor"),
+    ("nob", "nor"),
+    ("oc", "oci"),
+    ("or", "ori"),
+    ("pa", "pan"),
+    ("per", "fas"),
+    ("pl", "pol"),
+    ("ps", "pus"),
+    ("pt", "por"),
+    ("qu", "que"),
+    ("ro", "ron"),
+    ("ru", "rus"),
+    ("rum", "ron"),
+    ("sa", "san"),
+    ("sd", "snd"),
+    ("si", "sin"),
+    ("sk", "slk"),
+    ("sl", "slv"),
+    ("slo", "slk"),
+    ("sq", "sqi"),
+    ("sr", "srp"),
+    ("su", "sun"),
+    ("sv", "swe"),
+    ("sw", "swa"),
+    ("ta", "tam"),
+    ("te", "tel"),
+    ("tg", "tgk"),
+    ("tgl", "fil"),
+    ("th", "tha"),
+    ("ti", "tir"),
+    ("tib", "bod"),
+    ("tl", "fil"),
+    ("to", "ton"),
+    ("tr", "tur"),
+    ("tt", "tat"),
+    ("ug", "uig"),
+    ("uk", "ukr"),
+    ("ur", "urd"),
+    ("uz", "uzb"),
+    ("vi", "vie"),
+    ("wel", "cym"),
+    ("yi", "yid"),
+    ("yo", "yor"),
+    ("zh", "chi_sim"),
+    ("zh-cn", "chi_sim"),
+    ("zh-hans", "chi_sim"),
+    ("zh-hant", "chi_tra"),
+    ("zh-hk", "chi_tra"),
+    ("zh-tw", "chi_tra"),
+    ("zho", "chi_sim"),
+];
+
+/// A language code such as `eng` or `chi_sim`.
+///
+/// Parsing with [FromStr] only accepts the codes in [TESSERACT_LANGUAGES], which catches typos
+/// like `english` before they are sent to the API. Deserializing accepts any string, since the
+/// API reports languages in other formats as well (e.g. `en`).
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(transparent)]
+pub struct LanguageCode(String);
+
+impl LanguageCode {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the code is one of [TESSERACT_LANGUAGES].
+    pub fn is_known(&self) -> bool {
+        TESSERACT_LANGUAGES.contains(&self.0.as_str())
+    }
+
+    /// The canonical code for this language, i.e. its code in [TESSERACT_LANGUAGES]. These are
+    /// ISO 639-3 codes, apart from script variants such as `chi_sim`.
+    ///
+    /// Codes are matched case-insensitively, and ISO 639-1 codes (`en`), ISO 639-2/B codes
+    /// (`ger`) and codes with a region (`en-US`) are recognised as well. Returns `None` for
+    /// unknown codes.
+    pub fn normalized(&self) -> Option<LanguageCode> {
+        normalize(&self.0).map(|code| LanguageCode(code.to_string()))
+    }
+}
+
+/// Looks up the canonical code for `code`, see [LanguageCode::normalized].
+pub(crate) fn normalize(code: &str) -> Option<&'static str> {
+    let lookup = |code: &str| -> Option<&'static str> {
+        let canonical = code.replace('-', "_");
+        let alias = code.replace('_', "-");
+        TESSERACT_LANGUAGES
+            .binary_search(&canonical.as_str())
+            .map(|i| TESSERACT_LANGUAGES[i])
+            .or_else(|_| {
+                LANGUAGE_ALIASES
+                    .binary_search_by_key(&alias.as_str(), |(alias, _)| alias)
+                    .map(|i| LANGUAGE_ALIASES[i].1)
+            })
+            .ok()
+    };
+
+    let code = code.trim().to_ascii_lowercase();
+    lookup(&code).or_else(|| lookup(code.split(['-', '_']).next()?))
+}
+
+impl FromStr for LanguageCode {
+    type Err = ParameterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if TESSERACT_LANGUAGES.contains(&s) {
+            Ok(LanguageCode(s.to_string()))
+        } else {
+            Err(ParameterError::UnknownLanguage(s.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for LanguageCode {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<&str> for LanguageCode {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_language_code() {
+        let code: LanguageCode = "eng".parse().unwrap();
+        assert_eq!(code, "eng");
+        assert!(code.is_known());
+        assert!("chi_sim".parse::<LanguageCode>().is_ok());
+
+        for input in ["english", "ENG", "en", ""] {
+            assert_eq!(
+                input.parse::<LanguageCode>(),
+                Err(ParameterError::UnknownLanguage(input.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn test_deserialize_any_language_code() {
+        let codes: Vec<LanguageCode> = serde_json::from_str(r#"["eng", "en"]"#).unwrap();
+        assert!(codes[0].is_known());
+        assert!(!codes[1].is_known());
+        assert_eq!(serde_json::to_string(&codes).unwrap(), r#"["eng","en"]"#);
+    }
+
+    #[test]
+    fn test_tesseract_languages_are_sorted() {
+        assert!(TESSERACT_LANGUAGES.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn test_language_aliases() {
+        assert!(LANGUAGE_ALIASES.windows(2).all(|w| w[0].0 < w[1].0));
+        for (alias, code) in LANGUAGE_ALIASES {
+            assert!(TESSERACT_LANGUAGES.contains(code), "{alias} -> {code}");
+            assert!(!TESSERACT_LANGUAGES.contains(alias), "{alias}");
+        }
+    }
+
+    #[test]
+    fn test_normalize() {
+        for (input, expected) in [
+            ("en", "eng"),
+            ("eng", "eng"),
+            ("ENG", "eng"),
+            (" fr ", "fra"),
+            ("ger", "deu"),
+            ("en-US", "eng"),
+            ("pt_BR", "por"),
+            ("zh-TW", "chi_tra"),
+            ("zh", "chi_sim"),
+            ("chi_sim", "chi_sim"),
+            ("CHI-SIM", "chi_sim"),
+        ] {
+            assert_eq!(normalize(input), Some(expected), "{input}");
+        }
+
+        for input in ["english", "xx", "", "-"] {
+            assert_eq!(normalize(input), None, "{input}");
+        }
+
+        let code: LanguageCode = serde_json::from_str(r#""en""#).unwrap();
+        assert_eq!(code.normalized(), Some("eng".parse().unwrap()));
+    }
+}
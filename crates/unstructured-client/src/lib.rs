@@ -1,11 +1,13 @@
 #![doc = include_str!("../README.md")]
 
 pub mod client;
-mod element;
+pub mod element;
 pub mod error;
-mod metadata;
+pub mod language;
+pub mod metadata;
 pub mod partition;
 
 pub use client::UnstructuredClient;
-pub use element::ElementList;
+pub use element::{Element, ElementList, ElementType};
+pub use language::LanguageCode;
 pub use partition::PartitionParameters;
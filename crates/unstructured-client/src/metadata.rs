@@ -1,8 +1,22 @@
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize};
+
+mod coordinates;
+mod mailbox;
+
+pub use self::coordinates::{BoundingBox, CoordinateSystem, Coordinates, Point};
+pub use self::mailbox::Mailbox;
+
+use crate::element::ElementId;
+use crate::error::ParameterError;
+use crate::language::LanguageCode;
 
 /// Struct representing common metadata fields for document elements
 /// from all file types.
-#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct CommonMetadata {
     /// Filename.
     pub filename: Option<String>,
@@ -14,17 +28,24 @@ pub struct CommonMetadata {
     pub last_modified: Option<String>,
 
     /// File type.
+    // Left out of the schema so it doesn't override the `filetype` tag of [ExtendedMetadata]
+    // when flattened into it, and left out when unset so it doesn't serialize as a second,
+    // null `filetype` key next to the tag.
+    #[cfg_attr(feature = "schemars", schemars(skip))]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub filetype: Option<String>,
 
     /// XY Bounding Box Coordinates.
     /// See notes below for further details about the bounding box.
-    pub coordinates: Option<String>,
+    /// Legacy string coordinates (`"x0,y0,x1,y1"`) are converted to the object form.
+    #[serde(default, deserialize_with = "coordinates::deserialize_coordinates")]
+    pub coordinates: Option<Coordinates>,
 
     /// Element Hierarchy.
     /// `parent_id` may be used to infer where an element resides within the overall hierarchy of a document.
     /// For instance, a NarrativeText element may have a Title element as a parent (a “sub-title”),
     /// which in turn may have another Title element as its parent (a “title”).
-    pub parent_id: Option<String>,
+    pub parent_id: Option<ElementId>,
 
     /// Element depth relative to other elements of the same category.
     /// Category depth is the depth of an element relative to other elements of the same category.
@@ -40,13 +61,17 @@ pub struct CommonMetadata {
     /// Document Languages.
     /// At document level or element level.
     /// The list is ordered by probability of being the primary language of the text.
-    pub languages: Option<Vec<String>>,
+    pub languages: Option<Vec<LanguageCode>>,
 
-    /// Emphasized text (bold or italic) in the original document.
-    pub emphasized_text_contents: Option<String>,
+    /// Emphasized text (bold or italic) in the original document, one entry per run. A single
+    /// string, as sent by older API versions, is read as a list of one.
+    #[serde(default, deserialize_with = "deserialize_list")]
+    pub emphasized_text_contents: Option<Vec<String>>,
 
-    /// Tags on text that is emphasized in the original document.
-    pub emphasized_text_tags: Option<String>,
+    /// Tags on text that is emphasized in the original document, such as `b` or `i`, one for
+    /// each entry of `emphasized_text_contents`.
+    #[serde(default, deserialize_with = "deserialize_list")]
+    pub emphasized_text_tags: Option<Vec<String>>,
 
     /// True if the element is a continuation of a previous element.
     /// Only relevant for chunking, if an element was divided into two due to max_characters.
@@ -55,20 +80,108 @@ pub struct CommonMetadata {
     /// Detection model class probabilities.
     /// From unstructured-inference, hi-res strategy.
     pub detection_class_prob: Option<Vec<f64>>,
+
+    /// Confidence of the OCR in the extracted text, between 0 and 1. Only sent by some API
+    /// versions, for text extracted with OCR in the hi-res strategy.
+    pub confidence_score: Option<f64>,
+
+    /// Base64 encoded image data.
+    /// Only present for the element types requested through `extract_image_block_types`.
+    pub image_base64: Option<String>,
+
+    /// MIME type of the image data in `image_base64`.
+    pub image_mime_type: Option<String>,
+
+    /// The elements a chunk was formed from, as base64 encoded, compressed JSON. Only present
+    /// on chunks when `include_orig_elements` is set; decode it with
+    /// [Element::orig_elements](crate::Element::orig_elements).
+    pub orig_elements: Option<String>,
+
+    /// Fields not modeled by this crate, e.g. ones added by newer API versions, kept so that
+    /// they survive a serialization round-trip.
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, serde_json::Value>,
+}
+
+impl CommonMetadata {
+    /// A field not modeled by this crate, by its name in the API response.
+    pub fn get_extra(&self, key: &str) -> Option<&serde_json::Value> {
+        self.extra.get(key)
+    }
+
+    /// `languages` as canonical codes (see [LanguageCode::normalized]), in order and without
+    /// duplicates. Unknown codes are skipped.
+    pub fn normalized_languages(&self) -> Vec<LanguageCode> {
+        let mut normalized: Vec<LanguageCode> = Vec::new();
+        for code in self
+            .languages
+            .iter()
+            .flatten()
+            .filter_map(LanguageCode::normalized)
+        {
+            if !normalized.contains(&code) {
+                normalized.push(code);
+            }
+        }
+        normalized
+    }
+
+    /// Like [normalized_languages](Self::normalized_languages), but fails on the first unknown
+    /// code instead of skipping it.
+    pub fn try_normalized_languages(&self) -> Result<Vec<LanguageCode>, ParameterError> {
+        let mut normalized: Vec<LanguageCode> = Vec::new();
+        for code in self.languages.iter().flatten() {
+            let code = code
+                .normalized()
+                .ok_or_else(|| ParameterError::UnknownLanguage(code.to_string()))?;
+            if !normalized.contains(&code) {
+                normalized.push(code);
+            }
+        }
+        Ok(normalized)
+    }
 }
 
 /// Metadata for DOCX, PDF, PPT, XLSX document types.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct PagedDocument {
     #[serde(flatten)]
     pub common: CommonMetadata,
 
     /// Page number.
     pub page_number: Option<u32>,
+
+    /// Number of pages in the whole document, when reported. Lets callers check that all pages
+    /// were returned, e.g. when partitioning a PDF in page ranges.
+    pub total_pages: Option<u32>,
+}
+
+/// Metadata for PPTX document type.
+///
+/// The [PagedDocument] fields are embedded, so that
+/// [ExtendedMetadata::as_paged] keeps working for presentations. Their `page_number` is the
+/// slide number.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct PptxMetadata {
+    #[serde(flatten)]
+    pub paged: PagedDocument,
+
+    /// True if the text came from the speaker notes of the slide rather than the slide itself.
+    pub is_slide_notes: Option<bool>,
+}
+
+impl PptxMetadata {
+    /// Number of the slide, starting at 1.
+    pub fn slide_number(&self) -> Option<u32> {
+        self.paged.page_number
+    }
 }
 
 /// Metadata for XLSX document type.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct ExcelMetadata {
     #[serde(flatten)]
     pub common: CommonMetadata,
@@ -81,33 +194,131 @@ pub struct ExcelMetadata {
 }
 
 /// Metadata for EML document type.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EmailMetadata {
     #[serde(flatten)]
     pub common: CommonMetadata,
 
-    /// Email sender.
-    pub sent_from: Option<String>,
+    /// Email senders. The API sends a list, a single string is accepted as well.
+    #[serde(default, deserialize_with = "deserialize_list")]
+    pub sent_from: Option<Vec<String>>,
 
-    /// Email recipient.
-    pub sent_to: Option<String>,
+    /// Email recipients. The API sends a list, a single string is accepted as well.
+    #[serde(default, deserialize_with = "deserialize_list")]
+    pub sent_to: Option<Vec<String>>,
 
     /// Email subject.
     pub subject: Option<String>,
 }
 
+impl EmailMetadata {
+    /// The mailboxes in `sent_from`, in order. Entries holding several comma-separated
+    /// mailboxes are split, see [Mailbox::parse_list].
+    pub fn senders(&self) -> Vec<Mailbox> {
+        self.sent_from
+            .iter()
+            .flatten()
+            .flat_map(|entry| Mailbox::parse_list(entry))
+            .collect()
+    }
+
+    /// The mailboxes in `sent_to`, in order. Entries holding several comma-separated mailboxes
+    /// are split, see [Mailbox::parse_list].
+    pub fn recipients(&self) -> Vec<Mailbox> {
+        self.sent_to
+            .iter()
+            .flatten()
+            .flat_map(|entry| Mailbox::parse_list(entry))
+            .collect()
+    }
+}
+
 /// Metadata for MSG document type.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct MsgMetadata {
     #[serde(flatten)]
     pub common: CommonMetadata,
 
     /// Filename that attachment file is attached to.
     pub attached_to_filename: Option<String>,
+
+    /// Email senders, usually just one, see [sender](Self::sender). The API sends a list, a
+    /// single string is accepted as well.
+    #[serde(default, deserialize_with = "deserialize_list")]
+    pub sent_from: Option<Vec<String>>,
+
+    /// Email recipients.
+    #[serde(rename = "sent_to", default, deserialize_with = "deserialize_list")]
+    pub recipients: Option<Vec<String>>,
+
+    /// Carbon copy recipients.
+    #[serde(
+        rename = "cc_recipient",
+        default,
+        deserialize_with = "deserialize_list"
+    )]
+    pub cc: Option<Vec<String>>,
+
+    /// Blind carbon copy recipients, only known for messages sent by the owner of the mailbox.
+    #[serde(
+        rename = "bcc_recipient",
+        default,
+        deserialize_with = "deserialize_list"
+    )]
+    pub bcc: Option<Vec<String>>,
+
+    /// Email subject.
+    pub subject: Option<String>,
+}
+
+impl MsgMetadata {
+    /// The first entry of [sent_from](Self::sent_from).
+    pub fn sender(&self) -> Option<&str> {
+        self.sent_from.as_ref()?.first().map(String::as_str)
+    }
+
+    /// The mailbox of [sender](Self::sender), see [Mailbox::parse].
+    pub fn sender_mailbox(&self) -> Option<Mailbox> {
+        self.sender().map(Mailbox::parse)
+    }
+
+    /// The mailboxes in [recipients](Self::recipients), [cc](Self::cc) and [bcc](Self::bcc), in
+    /// that order. Entries holding several comma-separated mailboxes are split.
+    pub fn all_recipients(&self) -> Vec<Mailbox> {
+        [&self.recipients, &self.cc, &self.bcc]
+            .into_iter()
+            .flatten()
+            .flatten()
+            .flat_map(|entry| Mailbox::parse_list(entry))
+            .collect()
+    }
+}
+
+/// A field the API sends either as a single string or as a list of strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+fn deserialize_list<'de, D>(deserializer: D) -> Result<Option<Vec<String>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(
+        Option::<OneOrMany>::deserialize(deserializer)?.map(|value| match value {
+            OneOrMany::One(value) => vec![value],
+            OneOrMany::Many(values) => values,
+        }),
+    )
 }
 
 /// Metadata for Word Document.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct WordDocMetadata {
     #[serde(flatten)]
     pub common: CommonMetadata,
@@ -120,7 +331,8 @@ pub struct WordDocMetadata {
 }
 
 /// Metadata for HTML document type.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct HtmlMetadata {
     #[serde(flatten)]
     pub common: CommonMetadata,
@@ -133,7 +345,8 @@ pub struct HtmlMetadata {
 }
 
 /// Metadata for EPUB document type.
-#[derive(Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct EpubMetadata {
     #[serde(flatten)]
     pub common: CommonMetadata,
@@ -142,8 +355,18 @@ pub struct EpubMetadata {
     pub section: Option<String>,
 }
 
+/// Metadata for plain text, CSV, Markdown, reStructuredText and Org documents, which carry no
+/// fields of their own.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct TextMetadata {
+    #[serde(flatten)]
+    pub common: CommonMetadata,
+}
+
 /// Enum representing various types of metadata for different document types.
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(tag = "filetype")]
 pub enum ExtendedMetadata {
     // For DOCX, PDF, PPT, XLSX
@@ -154,7 +377,7 @@ pub enum ExtendedMetadata {
     DocxPage(PagedDocument),
 
     #[serde(rename = "application/vnd.openxmlformats-officedocument.presentationml.presentation")]
-    PptPage(PagedDocument),
+    PptPage(PptxMetadata),
 
     #[serde(
         rename = "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
@@ -182,32 +405,366 @@ pub enum ExtendedMetadata {
     // For EPUB
     #[serde(rename = "application/epub+zip")]
     Epub(EpubMetadata),
+
+    // For the plain text family
+    #[serde(rename = "text/plain")]
+    Text(TextMetadata),
+
+    #[serde(rename = "text/csv")]
+    Csv(TextMetadata),
+
+    #[serde(rename = "text/markdown", alias = "text/x-markdown")]
+    Markdown(TextMetadata),
+
+    #[serde(rename = "text/x-rst", alias = "text/prs.fallenstein.rst")]
+    Rst(TextMetadata),
+
+    #[serde(rename = "text/org")]
+    Org(TextMetadata),
+}
+
+impl ExtendedMetadata {
+    /// The `filetype` values that select a variant, including aliases.
+    pub const FILETYPES: &'static [&'static str] = &[
+        "application/pdf",
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        "sheet",
+        "excel",
+        "message/rfc822",
+        "application/vnd.ms-outlook",
+        "application/msword",
+        "text/html",
+        "application/epub+zip",
+        "text/plain",
+        "text/csv",
+        "text/markdown",
+        "text/x-markdown",
+        "text/x-rst",
+        "text/prs.fallenstein.rst",
+        "text/org",
+    ];
+
+    /// The metadata of the PDF, DOCX and PPTX formats, or `None` for other formats. See
+    /// [as_pptx](Self::as_pptx) for the fields specific to presentations.
+    pub fn as_paged(&self) -> Option<&PagedDocument> {
+        match self {
+            ExtendedMetadata::PdfPage(m) | ExtendedMetadata::DocxPage(m) => Some(m),
+            ExtendedMetadata::PptPage(m) => Some(&m.paged),
+            _ => None,
+        }
+    }
+
+    /// The metadata of PPTX documents, or `None` for other formats.
+    pub fn as_pptx(&self) -> Option<&PptxMetadata> {
+        match self {
+            ExtendedMetadata::PptPage(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    /// The metadata of XLSX documents, or `None` for other formats.
+    pub fn as_excel(&self) -> Option<&ExcelMetadata> {
+        match self {
+            ExtendedMetadata::XlsxPage(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    /// The metadata of EML documents, or `None` for other formats.
+    pub fn as_email(&self) -> Option<&EmailMetadata> {
+        match self {
+            ExtendedMetadata::Eml(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    /// The metadata of MSG documents, or `None` for other formats.
+    pub fn as_msg(&self) -> Option<&MsgMetadata> {
+        match self {
+            ExtendedMetadata::Msg(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    /// The metadata of DOC documents, or `None` for other formats.
+    pub fn as_word_doc(&self) -> Option<&WordDocMetadata> {
+        match self {
+            ExtendedMetadata::WordDoc(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    /// The metadata of HTML documents, or `None` for other formats.
+    pub fn as_html(&self) -> Option<&HtmlMetadata> {
+        match self {
+            ExtendedMetadata::Html(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    /// The metadata of EPUB documents, or `None` for other formats.
+    pub fn as_epub(&self) -> Option<&EpubMetadata> {
+        match self {
+            ExtendedMetadata::Epub(m) => Some(m),
+            _ => None,
+        }
+    }
+
+    /// The metadata of plain text, CSV, Markdown, reStructuredText and Org documents, or `None`
+    /// for other formats.
+    pub fn as_text(&self) -> Option<&TextMetadata> {
+        match self {
+            ExtendedMetadata::Text(m)
+            | ExtendedMetadata::Csv(m)
+            | ExtendedMetadata::Markdown(m)
+            | ExtendedMetadata::Rst(m)
+            | ExtendedMetadata::Org(m) => Some(m),
+            _ => None,
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// Element metadata, typed according to its `filetype`.
+///
+/// Metadata whose `filetype` is one of [ExtendedMetadata::FILETYPES] deserializes as
+/// [Metadata::KnownFormat], and any error in it is reported rather than falling back to
+/// [Metadata::UnknownFormat], which is only used for other or missing filetypes.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum Metadata {
     KnownFormat(ExtendedMetadata),
     UnknownFormat(CommonMetadata),
 }
 
+impl<'de> Deserialize<'de> for Metadata {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let known_filetype = value
+            .get("filetype")
+            .and_then(serde_json::Value::as_str)
+            .filter(|filetype| ExtendedMetadata::FILETYPES.contains(filetype))
+            .map(str::to_string);
+
+        match known_filetype {
+            Some(filetype) => ExtendedMetadata::deserialize(value)
+                .map(Metadata::KnownFormat)
+                .map_err(|e| D::Error::custom(format!("invalid {filetype} metadata: {e}"))),
+            None => CommonMetadata::deserialize(value)
+                .map(Metadata::UnknownFormat)
+                .map_err(D::Error::custom),
+        }
+    }
+}
+
 impl Metadata {
     pub fn into_common_metadata(self) -> CommonMetadata {
         match self {
             Metadata::KnownFormat(ext_metadata) => match ext_metadata {
                 ExtendedMetadata::PdfPage(m) => m.common,
                 ExtendedMetadata::DocxPage(m) => m.common,
-                ExtendedMetadata::PptPage(m) => m.common,
+                ExtendedMetadata::PptPage(m) => m.paged.common,
                 ExtendedMetadata::XlsxPage(m) => m.common,
                 ExtendedMetadata::Eml(m) => m.common,
                 ExtendedMetadata::Msg(m) => m.common,
                 ExtendedMetadata::WordDoc(m) => m.common,
                 ExtendedMetadata::Html(m) => m.common,
                 ExtendedMetadata::Epub(m) => m.common,
+                ExtendedMetadata::Text(m)
+                | ExtendedMetadata::Csv(m)
+                | ExtendedMetadata::Markdown(m)
+                | ExtendedMetadata::Rst(m)
+                | ExtendedMetadata::Org(m) => m.common,
+            },
+            Metadata::UnknownFormat(metadata) => metadata,
+        }
+    }
+
+    /// Borrows the metadata fields common to all file types, without consuming the metadata.
+    pub fn common(&self) -> &CommonMetadata {
+        match self {
+            Metadata::KnownFormat(ext_metadata) => match ext_metadata {
+                ExtendedMetadata::PdfPage(m) => &m.common,
+                ExtendedMetadata::DocxPage(m) => &m.common,
+                ExtendedMetadata::PptPage(m) => &m.paged.common,
+                ExtendedMetadata::XlsxPage(m) => &m.common,
+                ExtendedMetadata::Eml(m) => &m.common,
+                ExtendedMetadata::Msg(m) => &m.common,
+                ExtendedMetadata::WordDoc(m) => &m.common,
+                ExtendedMetadata::Html(m) => &m.common,
+                ExtendedMetadata::Epub(m) => &m.common,
+                ExtendedMetadata::Text(m)
+                | ExtendedMetadata::Csv(m)
+                | ExtendedMetadata::Markdown(m)
+                | ExtendedMetadata::Rst(m)
+                | ExtendedMetadata::Org(m) => &m.common,
+            },
+            Metadata::UnknownFormat(metadata) => metadata,
+        }
+    }
+
+    /// Mutably borrows the metadata fields common to all file types.
+    pub fn common_mut(&mut self) -> &mut CommonMetadata {
+        match self {
+            Metadata::KnownFormat(ext_metadata) => match ext_metadata {
+                ExtendedMetadata::PdfPage(m) => &mut m.common,
+                ExtendedMetadata::DocxPage(m) => &mut m.common,
+                ExtendedMetadata::PptPage(m) => &mut m.paged.common,
+                ExtendedMetadata::XlsxPage(m) => &mut m.common,
+                ExtendedMetadata::Eml(m) => &mut m.common,
+                ExtendedMetadata::Msg(m) => &mut m.common,
+                ExtendedMetadata::WordDoc(m) => &mut m.common,
+                ExtendedMetadata::Html(m) => &mut m.common,
+                ExtendedMetadata::Epub(m) => &mut m.common,
+                ExtendedMetadata::Text(m)
+                | ExtendedMetadata::Csv(m)
+                | ExtendedMetadata::Markdown(m)
+                | ExtendedMetadata::Rst(m)
+                | ExtendedMetadata::Org(m) => &mut m.common,
             },
             Metadata::UnknownFormat(metadata) => metadata,
         }
     }
+
+    /// Page number of the element.
+    ///
+    /// Formats without a typed `page_number` field may still carry one in
+    /// [extra](CommonMetadata::extra), e.g. after
+    /// [ElementList::infer_page_numbers](crate::ElementList::infer_page_numbers).
+    pub fn page_number(&self) -> Option<u32> {
+        match self {
+            Metadata::KnownFormat(ExtendedMetadata::PdfPage(m) | ExtendedMetadata::DocxPage(m)) => {
+                m.page_number
+            }
+            Metadata::KnownFormat(ExtendedMetadata::PptPage(m)) => m.paged.page_number,
+            Metadata::KnownFormat(ExtendedMetadata::XlsxPage(m)) => m.page_number,
+            Metadata::KnownFormat(ExtendedMetadata::WordDoc(m)) => m.page_number,
+            _ => self
+                .common()
+                .get_extra("page_number")
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|page_number| u32::try_from(page_number).ok()),
+        }
+    }
+
+    /// Sets the page number, in [extra](CommonMetadata::extra) for formats without a typed
+    /// `page_number` field.
+    pub fn set_page_number(&mut self, page_number: u32) {
+        match self {
+            Metadata::KnownFormat(ExtendedMetadata::PdfPage(m) | ExtendedMetadata::DocxPage(m)) => {
+                m.page_number = Some(page_number)
+            }
+            Metadata::KnownFormat(ExtendedMetadata::PptPage(m)) => {
+                m.paged.page_number = Some(page_number)
+            }
+            Metadata::KnownFormat(ExtendedMetadata::XlsxPage(m)) => {
+                m.page_number = Some(page_number)
+            }
+            Metadata::KnownFormat(ExtendedMetadata::WordDoc(m)) => {
+                m.page_number = Some(page_number)
+            }
+            _ => {
+                self.common_mut()
+                    .extra
+                    .insert("page_number".to_string(), page_number.into());
+            }
+        }
+    }
+
+    /// MIME type of the source document.
+    ///
+    /// For known formats the `filetype` field is consumed to select the variant, so it is
+    /// recovered from the variant instead of [CommonMetadata::filetype].
+    pub fn filetype(&self) -> Option<&str> {
+        match self {
+            Metadata::KnownFormat(ext_metadata) => Some(match ext_metadata {
+                ExtendedMetadata::PdfPage(_) => "application/pdf",
+                ExtendedMetadata::DocxPage(_) => {
+                    "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+                }
+                ExtendedMetadata::PptPage(_) => {
+                    "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+                }
+                ExtendedMetadata::XlsxPage(_) => {
+                    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"
+                }
+                ExtendedMetadata::Eml(_) => "message/rfc822",
+                ExtendedMetadata::Msg(_) => "application/vnd.ms-outlook",
+                ExtendedMetadata::WordDoc(_) => "application/msword",
+                ExtendedMetadata::Html(_) => "text/html",
+                ExtendedMetadata::Epub(_) => "application/epub+zip",
+                ExtendedMetadata::Text(_) => "text/plain",
+                ExtendedMetadata::Csv(_) => "text/csv",
+                ExtendedMetadata::Markdown(_) => "text/markdown",
+                ExtendedMetadata::Rst(_) => "text/x-rst",
+                ExtendedMetadata::Org(_) => "text/org",
+            }),
+            Metadata::UnknownFormat(metadata) => metadata.filetype.as_deref(),
+        }
+    }
+
+    /// ID of the parent element, if any.
+    pub fn parent_id(&self) -> Option<&ElementId> {
+        self.common().parent_id.as_ref()
+    }
+
+    /// The typed metadata, for the formats in [ExtendedMetadata::FILETYPES].
+    pub fn known_format(&self) -> Option<&ExtendedMetadata> {
+        match self {
+            Metadata::KnownFormat(metadata) => Some(metadata),
+            Metadata::UnknownFormat(_) => None,
+        }
+    }
+
+    /// See [ExtendedMetadata::as_paged]. Returns `None` for unknown formats.
+    pub fn as_paged(&self) -> Option<&PagedDocument> {
+        self.known_format()?.as_paged()
+    }
+
+    /// See [ExtendedMetadata::as_pptx]. Returns `None` for unknown formats.
+    pub fn as_pptx(&self) -> Option<&PptxMetadata> {
+        self.known_format()?.as_pptx()
+    }
+
+    /// See [ExtendedMetadata::as_excel]. Returns `None` for unknown formats.
+    pub fn as_excel(&self) -> Option<&ExcelMetadata> {
+        self.known_format()?.as_excel()
+    }
+
+    /// See [ExtendedMetadata::as_email]. Returns `None` for unknown formats.
+    pub fn as_email(&self) -> Option<&EmailMetadata> {
+        self.known_format()?.as_email()
+    }
+
+    /// See [ExtendedMetadata::as_msg]. Returns `None` for unknown formats.
+    pub fn as_msg(&self) -> Option<&MsgMetadata> {
+        self.known_format()?.as_msg()
+    }
+
+    /// See [ExtendedMetadata::as_word_doc]. Returns `None` for unknown formats.
+    pub fn as_word_doc(&self) -> Option<&WordDocMetadata> {
+        self.known_format()?.as_word_doc()
+    }
+
+    /// See [ExtendedMetadata::as_html]. Returns `None` for unknown formats.
+    pub fn as_html(&self) -> Option<&HtmlMetadata> {
+        self.known_format()?.as_html()
+    }
+
+    /// See [ExtendedMetadata::as_epub]. Returns `None` for unknown formats.
+    pub fn as_epub(&self) -> Option<&EpubMetadata> {
+        self.known_format()?.as_epub()
+    }
+
+    /// See [ExtendedMetadata::as_text]. Returns `None` for unknown formats.
+    pub fn as_text(&self) -> Option<&TextMetadata> {
+        self.known_format()?.as_text()
+    }
 }
 
 impl From<Metadata> for CommonMetadata {
@@ -254,6 +811,7 @@ mod tests {
                 ExtendedMetadata::PdfPage(PagedDocument {
                     common: CommonMetadata::default(),
                     page_number: None,
+                    total_pages: None,
                 }),
             ),
             (
@@ -261,13 +819,18 @@ mod tests {
                 ExtendedMetadata::DocxPage(PagedDocument {
                     common: CommonMetadata::default(),
                     page_number: None,
+                    total_pages: None,
                 }),
             ),
             (
                 "application/vnd.openxmlformats-officedocument.presentationml.presentation",
-                ExtendedMetadata::PptPage(PagedDocument {
-                    common: CommonMetadata::default(),
-                    page_number: None,
+                ExtendedMetadata::PptPage(PptxMetadata {
+                    paged: PagedDocument {
+                        common: CommonMetadata::default(),
+                        page_number: None,
+                        total_pages: None,
+                    },
+                    is_slide_notes: None,
                 }),
             ),
             (
@@ -292,6 +855,11 @@ mod tests {
                 ExtendedMetadata::Msg(MsgMetadata {
                     common: CommonMetadata::default(),
                     attached_to_filename: None,
+                    sent_from: None,
+                    recipients: None,
+                    cc: None,
+                    bcc: None,
+                    subject: None,
                 }),
             ),
             (
@@ -317,6 +885,21 @@ mod tests {
                     section: None,
                 }),
             ),
+            (
+                "text/plain",
+                ExtendedMetadata::Text(TextMetadata::default()),
+            ),
+            ("text/csv", ExtendedMetadata::Csv(TextMetadata::default())),
+            (
+                "text/markdown",
+                ExtendedMetadata::Markdown(TextMetadata::default()),
+            ),
+            (
+                "text/x-markdown",
+                ExtendedMetadata::Markdown(TextMetadata::default()),
+            ),
+            ("text/x-rst", ExtendedMetadata::Rst(TextMetadata::default())),
+            ("text/org", ExtendedMetadata::Org(TextMetadata::default())),
         ];
 
         for (mime_type, expected_format) in known_formats {
@@ -325,6 +908,491 @@ mod tests {
 
         Ok(())
     }
+    #[test]
+    fn test_format_accessors() {
+        let parse = |json: serde_json::Value| -> Metadata { serde_json::from_value(json).unwrap() };
+
+        let pdf = parse(
+            serde_json::json!({"filetype": "application/pdf", "page_number": 3,
+                                           "total_pages": 12}),
+        );
+        assert_eq!(pdf.as_paged().unwrap().page_number, Some(3));
+        assert_eq!(pdf.as_paged().unwrap().total_pages, Some(12));
+        assert!(pdf.as_email().is_none());
+
+        let pptx = parse(serde_json::json!({
+            "filetype": "application/vnd.openxmlformats-officedocument.presentationml.presentation"
+        }));
+        assert!(pptx.as_paged().is_some());
+
+        let excel = parse(serde_json::json!({"filetype": "excel", "page_name": "Sheet1"}));
+        assert_eq!(
+            excel.as_excel().unwrap().page_name.as_deref(),
+            Some("Sheet1")
+        );
+        assert!(excel.as_paged().is_none());
+
+        let email = parse(serde_json::json!({"filetype": "message/rfc822", "subject": "Hi"}));
+        assert_eq!(email.as_email().unwrap().subject.as_deref(), Some("Hi"));
+        assert!(email.as_msg().is_none());
+
+        let msg =
+            parse(serde_json::json!({"filetype": "application/vnd.ms-outlook", "subject": "Re"}));
+        assert_eq!(msg.as_msg().unwrap().subject.as_deref(), Some("Re"));
+        assert!(msg.as_email().is_none());
+
+        let doc = parse(
+            serde_json::json!({"filetype": "application/msword", "header_footer_type": "primary"}),
+        );
+        assert_eq!(
+            doc.as_word_doc().unwrap().header_footer_type.as_deref(),
+            Some("primary")
+        );
+
+        let html = parse(
+            serde_json::json!({"filetype": "text/html", "link_urls": ["https://example.com"]}),
+        );
+        assert_eq!(html.as_html().unwrap().link_urls.as_ref().unwrap().len(), 1);
+        assert!(html.as_epub().is_none());
+
+        let epub = parse(serde_json::json!({"filetype": "application/epub+zip", "section": "One"}));
+        assert_eq!(epub.as_epub().unwrap().section.as_deref(), Some("One"));
+
+        let markdown = parse(serde_json::json!({"filetype": "text/markdown", "filename": "a.md"}));
+        assert_eq!(
+            markdown.as_text().unwrap().common.filename.as_deref(),
+            Some("a.md")
+        );
+        assert_eq!(markdown.filetype(), Some("text/markdown"));
+        assert!(html.as_text().is_none());
+
+        let unknown = parse(serde_json::json!({"filetype": "text/foo"}));
+        assert!(unknown.known_format().is_none());
+        assert!(unknown.as_paged().is_none());
+        assert!(unknown.as_html().is_none());
+    }
+
+    #[test]
+    fn test_pptx_metadata() {
+        // Elements as returned for a two slide presentation with speaker notes.
+        let json = r#"[
+            {"type": "Title", "element_id": "5a8ce3c1d1c4e0e1b5b1b9b4d3c2a1f0", "text": "Quarterly Review",
+             "metadata": {"category_depth": 0, "file_directory": "/tmp", "filename": "review.pptx",
+                          "last_modified": "2024-07-01T10:12:44", "languages": ["eng"], "page_number": 1,
+                          "filetype": "application/vnd.openxmlformats-officedocument.presentationml.presentation"}},
+            {"type": "NarrativeText", "element_id": "0b6f4c9e2d8a7b3c1e5f9a0d4c8b2e6f", "text": "Mention the new hires.",
+             "metadata": {"filename": "review.pptx", "languages": ["eng"], "page_number": 1,
+                          "parent_id": "5a8ce3c1d1c4e0e1b5b1b9b4d3c2a1f0", "is_slide_notes": true,
+                          "filetype": "application/vnd.openxmlformats-officedocument.presentationml.presentation"}},
+            {"type": "ListItem", "element_id": "9d2e4f6a8b0c1d3e5f7a9b1c3d5e7f9a", "text": "Revenue up 12%",
+             "metadata": {"category_depth": 1, "filename": "review.pptx", "languages": ["eng"], "page_number": 2,
+                          "filetype": "application/vnd.openxmlformats-officedocument.presentationml.presentation"}}
+        ]"#;
+        let elements: crate::ElementList = serde_json::from_str(json).unwrap();
+
+        let slides: Vec<_> = elements
+            .iter()
+            .map(|element| element.as_pptx().unwrap().slide_number())
+            .collect();
+        assert_eq!(slides, vec![Some(1), Some(1), Some(2)]);
+        let notes: Vec<_> = elements
+            .iter()
+            .map(|element| element.as_pptx().unwrap().is_slide_notes)
+            .collect();
+        assert_eq!(notes, vec![None, Some(true), None]);
+
+        // The paged accessors keep working for presentations.
+        assert_eq!(elements[2].as_paged().unwrap().page_number, Some(2));
+        assert_eq!(elements[2].page_number(), Some(2));
+        assert_eq!(
+            elements[0].as_paged().unwrap().common.filename.as_deref(),
+            Some("review.pptx")
+        );
+        assert!(elements[1].common_metadata().unwrap().extra.is_empty());
+        assert!(elements[0]
+            .as_pptx()
+            .is_some_and(|m| m.paged.total_pages.is_none()));
+
+        let pdf: Metadata = serde_json::from_str(r#"{"filetype": "application/pdf"}"#).unwrap();
+        assert!(pdf.as_pptx().is_none());
+    }
+
+    #[test]
+    fn test_msg_metadata() {
+        let json_str = r#"
+        {
+            "filetype": "application/vnd.ms-outlook",
+            "filename": "meeting.msg",
+            "languages": ["eng"],
+            "sent_from": ["Jane Doe <jane@example.com>"],
+            "sent_to": ["John Doe <john@example.com>", "team@example.com"],
+            "subject": "Quarterly planning"
+        }
+        "#;
+        let metadata: Metadata = serde_json::from_str(json_str).unwrap();
+        let Metadata::KnownFormat(ExtendedMetadata::Msg(msg)) = metadata else {
+            panic!("Expected MSG metadata, got {metadata:?}");
+        };
+        assert_eq!(msg.sender(), Some("Jane Doe <jane@example.com>"));
+        assert_eq!(
+            msg.recipients,
+            Some(vec![
+                "John Doe <john@example.com>".to_string(),
+                "team@example.com".to_string()
+            ])
+        );
+        assert_eq!(msg.subject.as_deref(), Some("Quarterly planning"));
+        assert_eq!(msg.common.filename.as_deref(), Some("meeting.msg"));
+        assert_eq!(msg.cc, None);
+        assert_eq!(msg.bcc, None);
+
+        // A message with attachments, as returned by the API.
+        let json_str = r#"
+        {
+            "filetype": "application/vnd.ms-outlook",
+            "filename": "offer.msg",
+            "last_modified": "2024-05-02T09:30:00",
+            "languages": ["eng"],
+            "sent_from": ["\"Doe, Jane\" <jane@example.com>"],
+            "sent_to": ["john@example.com"],
+            "cc_recipient": ["Legal <legal@example.com>", "hr@example.com"],
+            "bcc_recipient": ["archive@example.com"],
+            "subject": "Offer letter",
+            "attached_to_filename": "offer.msg",
+            "email_message_id": "<123@example.com>"
+        }
+        "#;
+        let metadata: Metadata = serde_json::from_str(json_str).unwrap();
+        let msg = metadata.as_msg().unwrap();
+        assert_eq!(
+            msg.cc,
+            Some(vec![
+                "Legal <legal@example.com>".to_string(),
+                "hr@example.com".to_string()
+            ])
+        );
+        assert_eq!(msg.bcc, Some(vec!["archive@example.com".to_string()]));
+        assert_eq!(msg.attached_to_filename.as_deref(), Some("offer.msg"));
+        assert_eq!(
+            msg.sender_mailbox().and_then(|mailbox| mailbox.name),
+            Some("Doe, Jane".to_string())
+        );
+        let addresses: Vec<String> = msg
+            .all_recipients()
+            .into_iter()
+            .map(|mailbox| mailbox.address)
+            .collect();
+        assert_eq!(
+            addresses,
+            vec![
+                "john@example.com",
+                "legal@example.com",
+                "hr@example.com",
+                "archive@example.com"
+            ]
+        );
+        assert!(msg.common.get_extra("cc_recipient").is_none());
+        assert!(msg.common.get_extra("email_message_id").is_some());
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap()["sent_from"],
+            serde_json::json!(["\"Doe, Jane\" <jane@example.com>"])
+        );
+
+        // Single strings are accepted as well.
+        let metadata: Metadata = serde_json::from_str(
+            r#"{"filetype": "application/vnd.ms-outlook", "sent_from": "jane@example.com", "sent_to": "john@example.com"}"#,
+        )
+        .unwrap();
+        let Metadata::KnownFormat(ExtendedMetadata::Msg(msg)) = metadata else {
+            panic!("Expected MSG metadata, got {metadata:?}");
+        };
+        assert_eq!(msg.sender(), Some("jane@example.com"));
+
+        // All senders survive a round trip.
+        let json = serde_json::json!({
+            "filetype": "application/vnd.ms-outlook",
+            "sent_from": ["jane@example.com", "john@example.com"]
+        });
+        let metadata: Metadata = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(
+            metadata.as_msg().unwrap().sender(),
+            Some("jane@example.com")
+        );
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap()["sent_from"],
+            json["sent_from"]
+        );
+        assert_eq!(msg.recipients, Some(vec!["john@example.com".to_string()]));
+    }
+
+    #[test]
+    fn test_known_filetypes_are_typed() {
+        for filetype in ExtendedMetadata::FILETYPES {
+            let json_str = format!(r#"{{"filetype": "{filetype}"}}"#);
+            let metadata: Metadata = serde_json::from_str(&json_str).unwrap();
+            assert!(
+                matches!(metadata, Metadata::KnownFormat(_)),
+                "{filetype}: {metadata:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_malformed_known_format_is_an_error() {
+        let error = serde_json::from_str::<Metadata>(
+            r#"{"filetype": "application/pdf", "page_number": "two"}"#,
+        )
+        .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .starts_with("invalid application/pdf metadata: invalid type: string \"two\""),
+            "{error}"
+        );
+
+        // List-valued email fields, as sent by the API, are valid.
+        let metadata: Metadata = serde_json::from_str(
+            r#"{"filetype": "message/rfc822", "sent_from": ["Jane <j@x.com>"], "sent_to": ["a@x.com", "b@x.com"]}"#,
+        )
+        .unwrap();
+        let email = metadata.as_email().unwrap();
+        assert_eq!(email.sent_from, Some(vec!["Jane <j@x.com>".to_string()]));
+        assert_eq!(
+            email.sent_to,
+            Some(vec!["a@x.com".to_string(), "b@x.com".to_string()])
+        );
+        assert!(serde_json::from_str::<Metadata>(
+            r#"{"filetype": "message/rfc822", "sent_from": [1]}"#
+        )
+        .is_err());
+
+        // Common fields are checked for unknown filetypes too.
+        assert!(
+            serde_json::from_str::<Metadata>(r#"{"filetype": "text/foo", "parent_id": 1}"#)
+                .is_err()
+        );
+
+        // Fields of known formats are ignored for unknown filetypes.
+        let metadata: Metadata =
+            serde_json::from_str(r#"{"filetype": "text/foo", "page_number": "two"}"#).unwrap();
+        assert!(matches!(metadata, Metadata::UnknownFormat(_)));
+    }
+
+    /// Removes the null fields, which are serialized for unset options.
+    fn without_nulls(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(fields) => fields
+                .into_iter()
+                .filter(|(_, value)| !value.is_null())
+                .map(|(key, value)| (key, without_nulls(value)))
+                .collect(),
+            value => value,
+        }
+    }
+
+    #[test]
+    fn test_extra_fields_round_trip() {
+        for json_str in [
+            r#"{"filetype": "application/pdf", "page_number": 2, "filename": "a.pdf",
+                "detection_origin": "yolox", "signature": {"signed": true, "by": ["x"]}}"#,
+            r#"{"filetype": "message/rfc822", "sent_from": ["a@example.com"], "detection_origin": "eml"}"#,
+            r#"{"filetype": "text/foo", "filename": "a.foo", "new_field": [1, 2]}"#,
+            r#"{"detection_origin": "unknown"}"#,
+        ] {
+            let input: serde_json::Value = serde_json::from_str(json_str).unwrap();
+            let metadata: Metadata = serde_json::from_str(json_str).unwrap();
+            let output = without_nulls(serde_json::to_value(&metadata).unwrap());
+
+            // serde_json sorts object keys, so equal values produce the same bytes.
+            assert_eq!(output.to_string(), input.to_string());
+        }
+
+        let metadata: Metadata = serde_json::from_str(
+            r#"{"filetype": "application/pdf", "page_number": 2, "detection_origin": "yolox"}"#,
+        )
+        .unwrap();
+        let common = metadata.common();
+        assert_eq!(common.extra.len(), 1);
+        assert_eq!(
+            common.get_extra("detection_origin"),
+            Some(&serde_json::json!("yolox"))
+        );
+        assert_eq!(common.get_extra("page_number"), None);
+        assert_eq!(metadata.page_number(), Some(2));
+    }
+
+    #[test]
+    fn test_set_page_number() {
+        let mut pdf: Metadata = serde_json::from_str(r#"{"filetype": "application/pdf"}"#).unwrap();
+        pdf.set_page_number(3);
+        assert_eq!(pdf.as_paged().unwrap().page_number, Some(3));
+        assert!(pdf.common().extra.is_empty());
+
+        let mut html: Metadata = serde_json::from_str(r#"{"filetype": "text/html"}"#).unwrap();
+        assert_eq!(html.page_number(), None);
+        html.set_page_number(2);
+        assert_eq!(html.page_number(), Some(2));
+        assert_eq!(
+            serde_json::to_value(&html).unwrap()["page_number"],
+            serde_json::json!(2)
+        );
+
+        let unknown: Metadata =
+            serde_json::from_str(r#"{"filetype": "text/foo", "page_number": 4}"#).unwrap();
+        assert_eq!(unknown.page_number(), Some(4));
+    }
+
+    #[test]
+    fn test_email_mailboxes() {
+        // The list form returned by the API.
+        let metadata: Metadata = serde_json::from_str(
+            r#"{"filetype": "message/rfc822",
+                "sent_from": ["Jane Doe <jane@example.com>"],
+                "sent_to": ["\"Doe, John\" <john@example.com>", "team@example.com, hr@example.com"]}"#,
+        )
+        .unwrap();
+        let email = metadata.as_email().unwrap();
+        assert_eq!(
+            email.senders(),
+            vec![Mailbox {
+                name: Some("Jane Doe".to_string()),
+                address: "jane@example.com".to_string(),
+            }]
+        );
+        let recipients: Vec<String> = email.recipients().iter().map(Mailbox::to_string).collect();
+        assert_eq!(
+            recipients,
+            vec![
+                "\"Doe, John\" <john@example.com>",
+                "team@example.com",
+                "hr@example.com"
+            ]
+        );
+
+        // A single comma-separated string.
+        let metadata: Metadata = serde_json::from_str(
+            r#"{"filetype": "message/rfc822",
+                "sent_from": "Jane Doe <jane@example.com>",
+                "sent_to": "\"Doe, John\" <john@example.com>, team@example.com"}"#,
+        )
+        .unwrap();
+        let Metadata::KnownFormat(ExtendedMetadata::Eml(email)) = metadata else {
+            panic!("expected email metadata, got {metadata:?}");
+        };
+
+        assert_eq!(
+            email.senders(),
+            vec![Mailbox {
+                name: Some("Jane Doe".to_string()),
+                address: "jane@example.com".to_string(),
+            }]
+        );
+        let recipients: Vec<String> = email.recipients().iter().map(Mailbox::to_string).collect();
+        assert_eq!(
+            recipients,
+            vec!["\"Doe, John\" <john@example.com>", "team@example.com"]
+        );
+
+        let empty = EmailMetadata {
+            sent_from: None,
+            ..email
+        };
+        assert!(empty.senders().is_empty());
+    }
+
+    #[test]
+    fn test_common_metadata_default() {
+        let default = CommonMetadata::default();
+        let json = serde_json::to_value(&default).unwrap();
+        let fields = json.as_object().unwrap();
+        assert!(!fields.is_empty());
+        assert!(
+            fields.values().all(serde_json::Value::is_null),
+            "{fields:?}"
+        );
+
+        // An empty object deserializes to the default
+        let metadata: CommonMetadata = serde_json::from_str("{}").unwrap();
+        assert_eq!(metadata, default);
+    }
+
+    #[test]
+    fn test_common() {
+        let mime_types = [
+            "application/pdf",
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+            "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+            "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "message/rfc822",
+            "application/vnd.ms-outlook",
+            "application/msword",
+            "text/html",
+            "application/epub+zip",
+            "unknown/type",
+        ];
+
+        for mime_type in mime_types {
+            let json_str =
+                format!(r#"{{"filetype": "{mime_type}", "filename": "file", "parent_id": "p"}}"#);
+            let metadata: Metadata = serde_json::from_str(&json_str).unwrap();
+            let common = metadata.common();
+            assert_eq!(common.filename.as_deref(), Some("file"), "{mime_type}");
+            assert_eq!(
+                common.parent_id.as_ref().map(ElementId::as_str),
+                Some("p"),
+                "{mime_type}"
+            );
+            assert_eq!(
+                metadata.into_common_metadata().filename.as_deref(),
+                Some("file")
+            );
+        }
+    }
+
+    #[test]
+    fn test_normalized_languages() {
+        let metadata = |languages: &[&str]| CommonMetadata {
+            languages: Some(
+                languages
+                    .iter()
+                    .map(|code| serde_json::from_value(serde_json::json!(code)).unwrap())
+                    .collect(),
+            ),
+            ..CommonMetadata::default()
+        };
+
+        let mixed = metadata(&["en", "fra", "eng", "de"]);
+        assert_eq!(mixed.normalized_languages(), vec!["eng", "fra", "deu"]);
+        assert_eq!(
+            mixed.try_normalized_languages(),
+            Ok(mixed.normalized_languages())
+        );
+
+        let canonical = metadata(&["eng", "chi_sim"]);
+        assert_eq!(canonical.normalized_languages(), vec!["eng", "chi_sim"]);
+
+        let unknown = metadata(&["en", "klingon"]);
+        assert_eq!(unknown.normalized_languages(), vec!["eng"]);
+        assert_eq!(
+            unknown.try_normalized_languages(),
+            Err(ParameterError::UnknownLanguage("klingon".to_string()))
+        );
+
+        assert!(CommonMetadata::default().normalized_languages().is_empty());
+    }
+
+    #[test]
+    fn test_filetype() {
+        let known: Metadata = serde_json::from_str(r#"{"filetype": "text/html"}"#).unwrap();
+        assert_eq!(known.filetype(), Some("text/html"));
+
+        let unknown: Metadata = serde_json::from_str(r#"{"filetype": "text/foo"}"#).unwrap();
+        assert_eq!(unknown.filetype(), Some("text/foo"));
+
+        let missing: Metadata = serde_json::from_str(r#"{"filename": "foo"}"#).unwrap();
+        assert_eq!(missing.filetype(), None);
+    }
+
     #[test]
     fn test_pdf_element() -> Result<()> {
         // Example JSON string
@@ -334,7 +1402,12 @@ mod tests {
         "filename": "example.pdf",
         "file_directory": "/documents",
         "last_modified": "2023-10-01",
-        "coordinates": "100,100,200,200",
+        "coordinates": {
+            "points": [[100, 100], [100, 200], [200, 200], [200, 100]],
+            "system": "PixelSpace",
+            "layout_width": 1700,
+            "layout_height": 2200
+        },
         "parent_id": "1",
         "category_depth": 2,
         "text_as_html": "<p>Example</p>",
@@ -362,6 +1435,99 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_emphasized_text_forms() {
+        let emphasis = |contents: serde_json::Value, tags: serde_json::Value| {
+            let metadata: Metadata = serde_json::from_value(serde_json::json!({
+                "filetype": "text/html",
+                "emphasized_text_contents": contents,
+                "emphasized_text_tags": tags
+            }))
+            .unwrap();
+            let common = metadata.into_common_metadata();
+            (common.emphasized_text_contents, common.emphasized_text_tags)
+        };
+        let list = |values: &[&str]| Some(values.iter().map(|v| v.to_string()).collect());
+
+        assert_eq!(
+            emphasis(
+                serde_json::json!(["Important", "note"]),
+                serde_json::json!(["b", "i"])
+            ),
+            (list(&["Important", "note"]), list(&["b", "i"]))
+        );
+        assert_eq!(
+            emphasis(serde_json::json!("Important"), serde_json::json!("b")),
+            (list(&["Important"]), list(&["b"]))
+        );
+        assert_eq!(
+            emphasis(serde_json::Value::Null, serde_json::json!([])),
+            (None, list(&[]))
+        );
+
+        let metadata: Metadata = serde_json::from_value(serde_json::json!({
+            "filetype": "text/html", "emphasized_text_contents": "Important"
+        }))
+        .unwrap();
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap()["emphasized_text_contents"],
+            serde_json::json!(["Important"])
+        );
+    }
+
+    #[test]
+    fn test_coordinates_forms() {
+        let coordinates = |value: serde_json::Value| {
+            let metadata: Metadata = serde_json::from_value(serde_json::json!({
+                "filetype": "application/pdf",
+                "coordinates": value
+            }))
+            .unwrap();
+            metadata.common().coordinates.clone()
+        };
+
+        let object = coordinates(serde_json::json!({
+            "points": [[10, 20], [10, 40], [30, 40], [30, 20]],
+            "system": "PixelSpace",
+            "layout_width": 1700,
+            "layout_height": 2200
+        }))
+        .unwrap();
+        assert_eq!(object.system, Some(CoordinateSystem::PixelSpace));
+        assert_eq!(object.layout_width, Some(1700.0));
+
+        let legacy = coordinates(serde_json::json!("10, 20, 30, 40")).unwrap();
+        assert_eq!(legacy.points, object.points);
+        assert_eq!(legacy.system, None);
+        assert_eq!(legacy.layout_width, None);
+        assert_eq!(
+            coordinates(serde_json::json!("(10.5,20,30,40)"))
+                .unwrap()
+                .points[0],
+            (10.5, 20.0)
+        );
+
+        assert_eq!(coordinates(serde_json::Value::Null), None);
+        assert_eq!(coordinates(serde_json::json!("")), None);
+        let missing: Metadata = serde_json::from_str(r#"{"filetype": "application/pdf"}"#).unwrap();
+        assert_eq!(missing.common().coordinates, None);
+
+        // Serializing always emits the object form.
+        let json = serde_json::to_value(&legacy).unwrap();
+        assert_eq!(
+            json["points"],
+            serde_json::json!([[10.0, 20.0], [10.0, 40.0], [30.0, 40.0], [30.0, 20.0]])
+        );
+
+        for invalid in [r#""10,20,30""#, r#""a,b,c,d""#, "42"] {
+            let json = format!(r#"{{"filetype": "application/pdf", "coordinates": {invalid}}}"#);
+            assert!(
+                serde_json::from_str::<CommonMetadata>(&json).is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
     #[test]
     fn test_unknown_element() -> Result<()> {
         // Example JSON string
@@ -0,0 +1,561 @@
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+use crate::error::{ClientError, Result};
+
+/// A point `(x, y)`.
+pub type Point = (f64, f64);
+
+/// The location of an element on its page, as a polygon.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct Coordinates {
+    /// The corners of the polygon, usually the four corners of a rectangle starting at the top
+    /// left.
+    pub points: Vec<Point>,
+
+    /// The coordinate system of the points.
+    pub system: Option<CoordinateSystem>,
+
+    /// Width of the page in the coordinate system.
+    pub layout_width: Option<f64>,
+
+    /// Height of the page in the coordinate system.
+    pub layout_height: Option<f64>,
+}
+
+/// The forms in which the API sends coordinates. Older versions sent a bounding box as a
+/// string.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum CoordinatesRepr {
+    Object(Coordinates),
+    Legacy(String),
+}
+
+/// Deserializes the `coordinates` metadata, accepting the object form, the legacy string form
+/// `"x0,y0,x1,y1"` and null.
+///
+/// A legacy bounding box becomes the four corners of the rectangle, starting at `(x0, y0)`,
+/// without a coordinate system or layout size. An empty string counts as null.
+pub(crate) fn deserialize_coordinates<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<Coordinates>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    match Option::<CoordinatesRepr>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(CoordinatesRepr::Object(coordinates)) => Ok(Some(coordinates)),
+        Some(CoordinatesRepr::Legacy(value)) => parse_legacy(&value).map_err(de::Error::custom),
+    }
+}
+
+/// Parses a `"x0,y0,x1,y1"` bounding box, optionally wrapped in brackets or parentheses.
+fn parse_legacy(value: &str) -> std::result::Result<Option<Coordinates>, String> {
+    let inner = value
+        .trim()
+        .trim_start_matches(['(', '['])
+        .trim_end_matches([')', ']']);
+    if inner.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let numbers = inner
+        .split(',')
+        .map(|number| number.trim().parse::<f64>())
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|_| format!("invalid coordinates {value:?}"))?;
+    let [x0, y0, x1, y1] = numbers[..] else {
+        return Err(format!(
+            "invalid coordinates {value:?}, expected \"x0,y0,x1,y1\""
+        ));
+    };
+    Ok(Some(Coordinates {
+        points: vec![(x0, y0), (x0, y1), (x1, y1), (x1, y0)],
+        ..Coordinates::default()
+    }))
+}
+
+/// The coordinate systems used by Unstructured.
+///
+/// These follow the orientation of the Unstructured library: pixel coordinates have their
+/// origin at the top left of the page with y pointing down, the others have their origin at the
+/// bottom left with y pointing up.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CoordinateSystem {
+    /// Pixels of the rendered page image, e.g. from OCR or layout detection.
+    PixelSpace,
+
+    /// PDF points.
+    PointSpace,
+
+    /// Fractions of the page width and height, between 0 and 1.
+    RelativeCoordinateSystem,
+
+    /// A Cartesian system of arbitrary size.
+    CartesianCoordinateSystem,
+
+    /// Any other coordinate system, holding its original name.
+    Unknown(String),
+}
+
+impl CoordinateSystem {
+    const KNOWN: [CoordinateSystem; 4] = [
+        CoordinateSystem::PixelSpace,
+        CoordinateSystem::PointSpace,
+        CoordinateSystem::RelativeCoordinateSystem,
+        CoordinateSystem::CartesianCoordinateSystem,
+    ];
+
+    /// The name of the coordinate system as used by Unstructured, e.g. `PixelSpace`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            CoordinateSystem::PixelSpace => "PixelSpace",
+            CoordinateSystem::PointSpace => "PointSpace",
+            CoordinateSystem::RelativeCoordinateSystem => "RelativeCoordinateSystem",
+            CoordinateSystem::CartesianCoordinateSystem => "CartesianCoordinateSystem",
+            CoordinateSystem::Unknown(name) => name,
+        }
+    }
+
+    /// Whether y points down, i.e. the origin is at the top of the page.
+    pub(crate) fn is_screen_oriented(&self) -> bool {
+        *self == CoordinateSystem::PixelSpace
+    }
+}
+
+impl Serialize for CoordinateSystem {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for CoordinateSystem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Ok(CoordinateSystem::KNOWN
+            .into_iter()
+            .find(|system| system.as_str() == name)
+            .unwrap_or(CoordinateSystem::Unknown(name)))
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for CoordinateSystem {
+    fn schema_name() -> String {
+        "CoordinateSystem".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        String::json_schema(gen)
+    }
+}
+
+/// An axis aligned rectangle, with `x0 <= x1` and `y0 <= y1`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub x0: f64,
+    pub y0: f64,
+    pub x1: f64,
+    pub y1: f64,
+}
+
+impl Coordinates {
+    /// Converts the points to the [CoordinateSystem::RelativeCoordinateSystem], scaling them by
+    /// the layout size and flipping the y axis when coming from pixels.
+    ///
+    /// Fails when the coordinate system is unknown, or the layout size is missing or zero.
+    pub fn to_relative(&self) -> Result<Coordinates> {
+        let system = self.system.as_ref().ok_or_else(|| {
+            ClientError::CoordinatesConversionFailed("coordinate system is not set".to_string())
+        })?;
+        if *system == CoordinateSystem::RelativeCoordinateSystem {
+            return Ok(self.clone());
+        }
+        if let CoordinateSystem::Unknown(name) = system {
+            return Err(ClientError::CoordinatesConversionFailed(format!(
+                "unknown coordinate system '{name}'"
+            )));
+        }
+
+        let (width, height) = match (self.layout_width, self.layout_height) {
+            (Some(width), Some(height)) if width > 0.0 && height > 0.0 => (width, height),
+            _ => {
+                return Err(ClientError::CoordinatesConversionFailed(format!(
+                    "{system:?} coordinates need a layout width and height"
+                )))
+            }
+        };
+        let flip = system.is_screen_oriented();
+
+        Ok(Coordinates {
+            points: self
+                .points
+                .iter()
+                .map(|&(x, y)| {
+                    let y = y / height;
+                    (x / width, if flip { 1.0 - y } else { y })
+                })
+                .collect(),
+            system: Some(CoordinateSystem::RelativeCoordinateSystem),
+            layout_width: Some(1.0),
+            layout_height: Some(1.0),
+        })
+    }
+
+    /// Converts the points to the [CoordinateSystem::PixelSpace] of a page of `width` by `height`
+    /// pixels.
+    ///
+    /// Fails when the points can't be converted [to_relative](Self::to_relative) first.
+    pub fn to_pixel(&self, width: f64, height: f64) -> Result<Coordinates> {
+        let relative = self.to_relative()?;
+        Ok(Coordinates {
+            points: relative
+                .points
+                .iter()
+                .map(|&(x, y)| (x * width, (1.0 - y) * height))
+                .collect(),
+            system: Some(CoordinateSystem::PixelSpace),
+            layout_width: Some(width),
+            layout_height: Some(height),
+        })
+    }
+
+    /// The smallest rectangle containing all points, or `None` when there are no (finite)
+    /// points.
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        BoundingBox::around(self.points.iter().copied())
+    }
+
+    /// Area of the bounding box, 0 when there is none.
+    pub fn area(&self) -> f64 {
+        self.bounding_box().map_or(0.0, |bbox| bbox.area())
+    }
+
+    /// Whether `point` lies within the bounding box, edges included.
+    pub fn contains(&self, point: Point) -> bool {
+        self.bounding_box().is_some_and(|bbox| bbox.contains(point))
+    }
+
+    /// Whether the bounding boxes overlap or touch.
+    pub fn intersects(&self, other: &Coordinates) -> bool {
+        match (self.bounding_box(), other.bounding_box()) {
+            (Some(a), Some(b)) => a.intersects(&b),
+            _ => false,
+        }
+    }
+
+    /// Intersection over union of the bounding boxes, see [BoundingBox::iou].
+    pub fn iou(&self, other: &Coordinates) -> f64 {
+        match (self.bounding_box(), other.bounding_box()) {
+            (Some(a), Some(b)) => a.iou(&b),
+            _ => 0.0,
+        }
+    }
+}
+
+impl BoundingBox {
+    /// Creates a bounding box from two opposite corners, in any order.
+    pub fn new(a: Point, b: Point) -> Self {
+        BoundingBox {
+            x0: a.0.min(b.0),
+            y0: a.1.min(b.1),
+            x1: a.0.max(b.0),
+            y1: a.1.max(b.1),
+        }
+    }
+
+    /// The smallest rectangle containing all `points`, ignoring non-finite coordinates. Returns
+    /// `None` when no point is left.
+    pub fn around(points: impl IntoIterator<Item = Point>) -> Option<Self> {
+        points
+            .into_iter()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .map(|point| BoundingBox::new(point, point))
+            .reduce(|a, b| BoundingBox {
+                x0: a.x0.min(b.x0),
+                y0: a.y0.min(b.y0),
+                x1: a.x1.max(b.x1),
+                y1: a.y1.max(b.y1),
+            })
+    }
+
+    pub fn width(&self) -> f64 {
+        self.x1 - self.x0
+    }
+
+    pub fn height(&self) -> f64 {
+        self.y1 - self.y0
+    }
+
+    /// Area of the box, 0 for degenerate boxes such as a single point or a line.
+    pub fn area(&self) -> f64 {
+        self.width() * self.height()
+    }
+
+    /// Whether `point` lies within the box, edges included.
+    pub fn contains(&self, (x, y): Point) -> bool {
+        self.x0 <= x && x <= self.x1 && self.y0 <= y && y <= self.y1
+    }
+
+    /// Whether the boxes overlap or touch.
+    pub fn intersects(&self, other: &BoundingBox) -> bool {
+        self.x0 <= other.x1 && other.x0 <= self.x1 && self.y0 <= other.y1 && other.y0 <= self.y1
+    }
+
+    /// The overlapping part of both boxes, if they intersect.
+    pub fn intersection(&self, other: &BoundingBox) -> Option<BoundingBox> {
+        self.intersects(other).then(|| BoundingBox {
+            x0: self.x0.max(other.x0),
+            y0: self.y0.max(other.y0),
+            x1: self.x1.min(other.x1),
+            y1: self.y1.min(other.y1),
+        })
+    }
+
+    /// Intersection over union: the area of the overlap divided by the area covered by either
+    /// box, between 0 (disjoint) and 1 (identical).
+    ///
+    /// Degenerate boxes have no area, so their IoU is 0 even when they're identical.
+    pub fn iou(&self, other: &BoundingBox) -> f64 {
+        let intersection = self.intersection(other).map_or(0.0, |bbox| bbox.area());
+        let union = self.area() + other.area() - intersection;
+        if union > 0.0 {
+            intersection / union
+        } else {
+            0.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x0: f64, y0: f64, x1: f64, y1: f64) -> BoundingBox {
+        BoundingBox::new((x0, y0), (x1, y1))
+    }
+
+    fn coordinates(points: Vec<Point>) -> Coordinates {
+        Coordinates {
+            points,
+            ..Coordinates::default()
+        }
+    }
+
+    /// Deterministic pseudo-random boxes for the property tests.
+    fn random_boxes(count: usize) -> Vec<BoundingBox> {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as f64 / (1u64 << 31) as f64 * 100.0
+        };
+        (0..count)
+            .map(|_| rect(next(), next(), next(), next()))
+            .collect()
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let json = r#"{
+            "points": [[10.0, 20.0], [10.0, 40.0], [30.0, 40.0], [30.0, 20.0]],
+            "system": "PixelSpace",
+            "layout_width": 1700,
+            "layout_height": 2200
+        }"#;
+        let coordinates: Coordinates = serde_json::from_str(json).unwrap();
+        assert_eq!(coordinates.points.len(), 4);
+        assert_eq!(coordinates.system, Some(CoordinateSystem::PixelSpace));
+        assert_eq!(coordinates.layout_width, Some(1700.0));
+        assert_eq!(
+            coordinates.bounding_box(),
+            Some(rect(10.0, 20.0, 30.0, 40.0))
+        );
+        assert_eq!(coordinates.area(), 400.0);
+    }
+
+    fn assert_points_eq(actual: &[Point], expected: &[Point]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!(
+                (a.0 - e.0).abs() < 1e-9 && (a.1 - e.1).abs() < 1e-9,
+                "{actual:?} != {expected:?}"
+            );
+        }
+    }
+
+    fn pixels() -> Coordinates {
+        Coordinates {
+            points: vec![
+                (170.0, 220.0),
+                (170.0, 440.0),
+                (850.0, 440.0),
+                (850.0, 220.0),
+            ],
+            system: Some(CoordinateSystem::PixelSpace),
+            layout_width: Some(1700.0),
+            layout_height: Some(2200.0),
+        }
+    }
+
+    #[test]
+    fn test_to_relative() {
+        let relative = pixels().to_relative().unwrap();
+        assert_eq!(
+            relative.system,
+            Some(CoordinateSystem::RelativeCoordinateSystem)
+        );
+        assert_eq!(relative.layout_width, Some(1.0));
+        assert_points_eq(
+            &relative.points,
+            &[(0.1, 0.9), (0.1, 0.8), (0.5, 0.8), (0.5, 0.9)],
+        );
+        assert_eq!(relative.to_relative().unwrap(), relative);
+
+        // Points aren't flipped, their origin is already at the bottom.
+        let points = Coordinates {
+            points: vec![(61.2, 79.2)],
+            system: Some(CoordinateSystem::PointSpace),
+            layout_width: Some(612.0),
+            layout_height: Some(792.0),
+        };
+        assert_points_eq(&points.to_relative().unwrap().points, &[(0.1, 0.1)]);
+    }
+
+    #[test]
+    fn test_to_pixel_round_trip() {
+        let original = pixels();
+        let round_tripped = original
+            .to_relative()
+            .unwrap()
+            .to_pixel(1700.0, 2200.0)
+            .unwrap();
+        assert_eq!(round_tripped.system, Some(CoordinateSystem::PixelSpace));
+        assert_eq!(round_tripped.layout_height, Some(2200.0));
+        assert_points_eq(&round_tripped.points, &original.points);
+
+        let scaled = original.to_pixel(850.0, 1100.0).unwrap();
+        assert_points_eq(
+            &scaled.points,
+            &[(85.0, 110.0), (85.0, 220.0), (425.0, 220.0), (425.0, 110.0)],
+        );
+    }
+
+    #[test]
+    fn test_conversion_errors() {
+        let missing_layout = Coordinates {
+            layout_height: None,
+            ..pixels()
+        };
+        let zero_layout = Coordinates {
+            layout_width: Some(0.0),
+            ..pixels()
+        };
+        let no_system = Coordinates {
+            system: None,
+            ..pixels()
+        };
+        let unknown: Coordinates = serde_json::from_value(serde_json::json!({
+            "points": [[0, 0]], "system": "SomethingElse", "layout_width": 1, "layout_height": 1
+        }))
+        .unwrap();
+        assert_eq!(
+            unknown.system,
+            Some(CoordinateSystem::Unknown("SomethingElse".to_string()))
+        );
+        assert_eq!(
+            serde_json::to_value(&unknown).unwrap()["system"],
+            "SomethingElse"
+        );
+
+        for coordinates in [missing_layout, zero_layout, no_system, unknown] {
+            assert!(matches!(
+                coordinates.to_relative(),
+                Err(ClientError::CoordinatesConversionFailed(_))
+            ));
+            assert!(coordinates.to_pixel(100.0, 100.0).is_err());
+        }
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        assert_eq!(coordinates(vec![]).bounding_box(), None);
+        assert_eq!(
+            coordinates(vec![(5.0, 5.0)]).bounding_box(),
+            Some(rect(5.0, 5.0, 5.0, 5.0))
+        );
+        assert_eq!(
+            coordinates(vec![(3.0, 4.0), (1.0, 2.0)]).bounding_box(),
+            Some(rect(1.0, 2.0, 3.0, 4.0))
+        );
+        assert_eq!(
+            coordinates(vec![(f64::NAN, 1.0), (2.0, f64::INFINITY), (1.0, 1.0)]).bounding_box(),
+            Some(rect(1.0, 1.0, 1.0, 1.0))
+        );
+        assert_eq!(coordinates(vec![(f64::NAN, f64::NAN)]).bounding_box(), None);
+    }
+
+    #[test]
+    fn test_contains_and_intersects() {
+        let a = rect(0.0, 0.0, 10.0, 10.0);
+        assert!(a.contains((0.0, 10.0)));
+        assert!(a.contains((5.0, 5.0)));
+        assert!(!a.contains((10.1, 5.0)));
+
+        assert!(a.intersects(&rect(10.0, 10.0, 20.0, 20.0)));
+        assert!(!a.intersects(&rect(10.5, 0.0, 20.0, 10.0)));
+
+        let empty = coordinates(vec![]);
+        assert!(!empty.contains((0.0, 0.0)));
+        assert!(!empty.intersects(&coordinates(vec![(0.0, 0.0)])));
+        assert_eq!(empty.iou(&empty), 0.0);
+    }
+
+    #[test]
+    fn test_iou() {
+        let a = rect(0.0, 0.0, 10.0, 10.0);
+        assert_eq!(a.iou(&a), 1.0);
+        assert_eq!(a.iou(&rect(20.0, 20.0, 30.0, 30.0)), 0.0);
+        // Overlap of 50 over a union of 150.
+        assert!((a.iou(&rect(5.0, 0.0, 15.0, 10.0)) - 1.0 / 3.0).abs() < 1e-12);
+        // A box inside another: 25 / 100.
+        assert_eq!(a.iou(&rect(0.0, 0.0, 5.0, 5.0)), 0.25);
+        // Touching edges overlap with zero area.
+        assert_eq!(a.iou(&rect(10.0, 0.0, 20.0, 10.0)), 0.0);
+
+        let point = rect(1.0, 1.0, 1.0, 1.0);
+        assert_eq!(point.area(), 0.0);
+        assert_eq!(point.iou(&point), 0.0);
+        assert_eq!(point.iou(&a), 0.0);
+    }
+
+    #[test]
+    fn test_geometry_properties() {
+        let boxes = random_boxes(50);
+        for a in &boxes {
+            assert!(a.x0 <= a.x1 && a.y0 <= a.y1);
+            assert!(a.area() >= 0.0);
+            assert!(a.contains((a.x0, a.y0)) && a.contains((a.x1, a.y1)));
+
+            for b in &boxes {
+                let iou = a.iou(b);
+                assert!((0.0..=1.0).contains(&iou));
+                assert_eq!(iou, b.iou(a));
+                assert_eq!(a.intersects(b), b.intersects(a));
+
+                match a.intersection(b) {
+                    Some(overlap) => {
+                        assert!(a.intersects(b));
+                        assert!(overlap.area() <= a.area().min(b.area()) + 1e-9);
+                    }
+                    None => assert_eq!(iou, 0.0),
+                }
+            }
+        }
+    }
+}
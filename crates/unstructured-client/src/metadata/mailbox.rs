@@ -0,0 +1,208 @@
+use std::fmt;
+
+/// An email mailbox, e.g. `Jane Doe <jane@example.com>`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mailbox {
+    /// Display name, without surrounding quotes.
+    pub name: Option<String>,
+
+    /// Email address, without angle brackets.
+    pub address: String,
+}
+
+impl Mailbox {
+    /// Parses a single mailbox.
+    ///
+    /// Accepts bare addresses (`jane@example.com`) and addresses with a display name, which may
+    /// be quoted (`"Doe, Jane" <jane@example.com>`). Input that doesn't match either form is
+    /// kept as the address as-is, so this never fails.
+    pub fn parse(input: &str) -> Mailbox {
+        let input = input.trim();
+
+        let bracketed = input.strip_suffix('>').and_then(|rest| {
+            rest.rfind('<')
+                .map(|start| (&rest[..start], &rest[start + 1..]))
+        });
+        match bracketed {
+            Some((name, address)) if !address.trim().is_empty() => {
+                let name = unquote(name.trim());
+                Mailbox {
+                    name: (!name.is_empty()).then_some(name),
+                    address: address.trim().to_string(),
+                }
+            }
+            _ => Mailbox {
+                name: None,
+                address: input.to_string(),
+            },
+        }
+    }
+
+    /// Parses a comma-separated list of mailboxes, see [parse](Self::parse).
+    ///
+    /// Commas inside quoted display names and angle brackets don't separate mailboxes. Empty
+    /// entries are skipped.
+    pub fn parse_list(input: &str) -> Vec<Mailbox> {
+        split_list(input)
+            .into_iter()
+            .filter(|part| !part.trim().is_empty())
+            .map(Mailbox::parse)
+            .collect()
+    }
+}
+
+impl fmt::Display for Mailbox {
+    /// Formats the mailbox as `Name <address>`, quoting the name when needed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) if name.contains([',', ';', '<', '>', '"', '@', '(', ')']) => {
+                let escaped = name.replace('\\', "\\\\").replace('"', "\\\"");
+                write!(f, "\"{escaped}\" <{}>", self.address)
+            }
+            Some(name) => write!(f, "{name} <{}>", self.address),
+            None => f.write_str(&self.address),
+        }
+    }
+}
+
+/// Splits `input` on commas that are outside of quotes and angle brackets.
+fn split_list(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut in_brackets = false;
+    let mut escaped = false;
+
+    for (i, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_brackets = true,
+            '>' if !in_quotes => in_brackets = false,
+            ',' if !in_quotes && !in_brackets => {
+                parts.push(&input[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+/// Strips surrounding double quotes and resolves backslash escapes inside them.
+fn unquote(name: &str) -> String {
+    let Some(inner) = name
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+    else {
+        return name.to_string();
+    };
+
+    let mut unquoted = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unquoted.extend(chars.next()),
+            c => unquoted.push(c),
+        }
+    }
+    unquoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mailbox(name: Option<&str>, address: &str) -> Mailbox {
+        Mailbox {
+            name: name.map(String::from),
+            address: address.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Mailbox::parse("Jane Doe <jane@example.com>"),
+            mailbox(Some("Jane Doe"), "jane@example.com")
+        );
+        assert_eq!(
+            Mailbox::parse("  jane@example.com "),
+            mailbox(None, "jane@example.com")
+        );
+        assert_eq!(
+            Mailbox::parse("<jane@example.com>"),
+            mailbox(None, "jane@example.com")
+        );
+        assert_eq!(
+            Mailbox::parse("Zoë Ünal-Ødegård <zoe@example.com>"),
+            mailbox(Some("Zoë Ünal-Ødegård"), "zoe@example.com")
+        );
+        assert_eq!(
+            Mailbox::parse("山田 太郎 <taro@example.jp>"),
+            mailbox(Some("山田 太郎"), "taro@example.jp")
+        );
+    }
+
+    #[test]
+    fn test_parse_quoted_name() {
+        assert_eq!(
+            Mailbox::parse(r#""Doe, Jane" <jane@example.com>"#),
+            mailbox(Some("Doe, Jane"), "jane@example.com")
+        );
+        assert_eq!(
+            Mailbox::parse(r#""Jane \"JD\" Doe" <jane@example.com>"#),
+            mailbox(Some(r#"Jane "JD" Doe"#), "jane@example.com")
+        );
+        assert_eq!(
+            Mailbox::parse(r#""" <jane@example.com>"#),
+            mailbox(None, "jane@example.com")
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(Mailbox::parse("Jane <"), mailbox(None, "Jane <"));
+        assert_eq!(Mailbox::parse("Jane <>"), mailbox(None, "Jane <>"));
+        assert_eq!(
+            Mailbox::parse("not an address"),
+            mailbox(None, "not an address")
+        );
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(
+            Mailbox::parse_list(
+                r#"Jane Doe <jane@example.com>, "Doe, John" <john@example.com>,, team@example.com"#
+            ),
+            vec![
+                mailbox(Some("Jane Doe"), "jane@example.com"),
+                mailbox(Some("Doe, John"), "john@example.com"),
+                mailbox(None, "team@example.com"),
+            ]
+        );
+        assert_eq!(Mailbox::parse_list(""), vec![]);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            mailbox(Some("Jane Doe"), "jane@example.com").to_string(),
+            "Jane Doe <jane@example.com>"
+        );
+        assert_eq!(
+            mailbox(Some(r#"Doe, "JD""#), "jane@example.com").to_string(),
+            r#""Doe, \"JD\"" <jane@example.com>"#
+        );
+        assert_eq!(
+            mailbox(None, "jane@example.com").to_string(),
+            "jane@example.com"
+        );
+
+        let quoted = mailbox(Some(r#"Doe, "JD""#), "jane@example.com");
+        assert_eq!(Mailbox::parse(&quoted.to_string()), quoted);
+    }
+}
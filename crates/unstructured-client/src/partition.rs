@@ -1,9 +1,31 @@
+use crate::error::{self, ClientError, Result};
+use crate::language;
 use crate::ElementList;
+use reqwest::header::HeaderMap;
 use reqwest::multipart::Form;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+use std::str::FromStr;
+
+#[cfg(feature = "clap")]
+mod args;
+
+/// Element types whose image blocks can be extracted through `extract_image_block_types`.
+pub const IMAGE_BLOCK_TYPES: &[&str] = &["Image", "Table"];
+
+/// Document types (by file extension) accepted by `skip_infer_table_types`.
+pub const DOCUMENT_TYPES: &[&str] = &[
+    "bmp", "csv", "doc", "docx", "eml", "epub", "heic", "html", "jpeg", "jpg", "md", "msg", "odt",
+    "org", "p7s", "pdf", "png", "ppt", "pptx", "rst", "rtf", "tiff", "tsv", "txt", "xls", "xlsx",
+    "xml",
+];
+
+/// Names of the chunking strategies accepted by `chunking_strategy`.
+pub const CHUNKING_STRATEGIES: &[&str] = &["basic", "by_page", "by_similarity", "by_title"];
 
 /// This chunks the returned elements after partitioning.
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ChunkingStrategy {
     Basic,
@@ -12,8 +34,17 @@ pub enum ChunkingStrategy {
     ByTitle,
 }
 
+/// Parses a chunking strategy from its API name, e.g. `by_title`.
+impl FromStr for ChunkingStrategy {
+    type Err = error::ParameterError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse_enum(s).ok_or_else(|| error::ParameterError::UnknownChunkingStrategy(s.to_string()))
+    }
+}
+
 /// The strategy to use for partitioning PDF/image.
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum Strategy {
     Fast,
@@ -22,8 +53,67 @@ pub enum Strategy {
     OcrOnly,
 }
 
+/// Names of the hi_res models known to the API, see [HiResModel].
+pub const HI_RES_MODELS: &[&str] = &["yolox", "detectron2_onnx"];
+
+/// The inference model used when strategy is hi_res.
+///
+/// Serializes as the model name. Names other than [HI_RES_MODELS] parse as
+/// [Custom](HiResModel::Custom) and are sent as they are, for models added to the API later.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Hash)]
+#[serde(from = "String", into = "String")]
+pub enum HiResModel {
+    Yolox,
+    Detectron2Onnx,
+    Custom(String),
+}
+
+impl HiResModel {
+    /// The model name, as sent to the API.
+    pub fn as_str(&self) -> &str {
+        match self {
+            HiResModel::Yolox => "yolox",
+            HiResModel::Detectron2Onnx => "detectron2_onnx",
+            HiResModel::Custom(name) => name,
+        }
+    }
+
+    /// Whether the name is one of [HI_RES_MODELS].
+    pub fn is_known(&self) -> bool {
+        HI_RES_MODELS.contains(&self.as_str())
+    }
+}
+
+impl From<&str> for HiResModel {
+    fn from(name: &str) -> Self {
+        match name {
+            "yolox" => HiResModel::Yolox,
+            "detectron2_onnx" => HiResModel::Detectron2Onnx,
+            _ => HiResModel::Custom(name.to_string()),
+        }
+    }
+}
+
+impl From<String> for HiResModel {
+    fn from(name: String) -> Self {
+        HiResModel::from(name.as_str())
+    }
+}
+
+impl From<HiResModel> for String {
+    fn from(model: HiResModel) -> Self {
+        model.as_str().to_string()
+    }
+}
+
+impl std::fmt::Display for HiResModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// The format of the response.
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum OutputFormat {
     #[serde(rename = "application/json")]
     ApplicationJson,
@@ -32,7 +122,8 @@ pub enum OutputFormat {
     TextCsv,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub struct PartitionParameters {
     /// If `True`, return coordinates for each element extracted via OCR. Default: `False`.
     pub coordinates: bool,
@@ -47,7 +138,7 @@ pub struct PartitionParameters {
     pub gz_uncompressed_content_type: Option<String>,
 
     /// The name of the inference model used when strategy is hi_res
-    pub hi_res_model_name: Option<String>,
+    pub hi_res_model_name: Option<HiResModel>,
 
     /// If true, the output will include page breaks if the filetype supports it. Default: false
     pub include_page_breaks: bool,
@@ -73,6 +164,16 @@ pub struct PartitionParameters {
     /// If `True`, will retain the XML tags in the output. Otherwise it will simply extract the text from within the tags. Only applies to XML documents. Default: false
     pub xml_keep_tags: bool,
 
+    /// When `True`, the server splits PDFs into pages and partitions them in parallel, which
+    /// speeds up large documents. Only supported by newer API versions. Default: false
+    #[serde(default)]
+    pub split_pdf_page: bool,
+
+    /// The number of pages partitioned in parallel when `split_pdf_page` is set. Left to the
+    /// server when unset.
+    #[serde(default)]
+    pub split_pdf_concurrency_level: Option<u32>,
+
     /// Use one of the supported strategies to chunk the returned elements after partitioning. When 'chunking_strategy' is not specified, no chunking is performed and any other chunking parameters provided are ignored. Supported strategies: 'basic', 'by_page', 'by_similarity', or 'by_title'
     pub chunking_strategy: Option<ChunkingStrategy>,
 
@@ -91,8 +192,9 @@ pub struct PartitionParameters {
     /// If chunking strategy is set, cut off new sections after reaching a length of n chars (soft max). Default: 1500
     pub new_after_n_chars: Option<i32>,
 
-    /// Specifies the length of a string ('tail') to be drawn from each chunk and prefixed to the next chunk as a context-preserving mechanism. By default, this only applies to split-chunks where an oversized element is divided into multiple chunks by text-splitting. Default 0.
-    pub overlap: i32,
+    /// Specifies the length in characters of a string ('tail') to be drawn from each chunk and prefixed to the next chunk as a context-preserving mechanism. By default, this only applies to split-chunks where an oversized element is divided into multiple chunks by text-splitting. Default 0.
+    #[serde(rename = "overlap")]
+    pub overlap_chars: i32,
 
     /// When `True`, apply overlap between 'normal' chunks formed from whole elements and not subject to text-splitting. Use this with caution as it entails a certain level of 'pollution' of otherwise clean semantic chunk boundaries. Default false.
     pub overlap_all: bool,
@@ -117,87 +219,370 @@ impl Default for PartitionParameters {
             strategy: Strategy::Auto,
             unique_element_ids: false,
             xml_keep_tags: false,
+            split_pdf_page: false,
+            split_pdf_concurrency_level: None,
             chunking_strategy: None,
             combine_under_n_chars: None,
             include_orig_elements: true,
             max_characters: None,
             multipage_sections: true,
             new_after_n_chars: None,
-            overlap: 0,
+            overlap_chars: 0,
             overlap_all: false,
             similarity_threshold: None,
         }
     }
 }
 
-impl From<PartitionParameters> for Form {
-    fn from(value: PartitionParameters) -> Self {
-        let mut form = Form::new();
-        form = form.text("coordinates", value.coordinates.to_string());
-        if let Some(encoding) = value.encoding.clone() {
-            form = form.text("encoding", encoding);
+impl PartitionParameters {
+    /// Checks the parameters for values the API is known to reject.
+    ///
+    /// This is called by [UnstructuredClient::partition_file](crate::UnstructuredClient::partition_file)
+    /// before sending a request.
+    pub fn validate(&self) -> std::result::Result<(), error::ParameterError> {
+        if let Some(block_type) = self
+            .extract_image_block_types
+            .iter()
+            .find(|t| !IMAGE_BLOCK_TYPES.contains(&t.as_str()))
+        {
+            return Err(error::ParameterError::UnknownImageBlockType(
+                block_type.clone(),
+            ));
+        }
+
+        if let Some(document_type) = self
+            .skip_infer_table_types
+            .iter()
+            .find(|t| !DOCUMENT_TYPES.contains(&t.as_str()))
+        {
+            return Err(error::ParameterError::UnknownDocumentType(
+                document_type.clone(),
+            ));
+        }
+
+        if let Some(HiResModel::Custom(name)) = &self.hi_res_model_name {
+            if name.trim().is_empty() {
+                return Err(error::ParameterError::EmptyHiResModelName);
+            }
+            tracing::debug!("Using hi_res model '{name}', which isn't one of {HI_RES_MODELS:?}");
+        }
+
+        for warning in self.warnings() {
+            tracing::warn!("{warning}");
+        }
+        Ok(())
+    }
+
+    /// Combinations of values that [validate](Self::validate) accepts, and only logs, but that
+    /// are likely not what was intended.
+    pub fn warnings(&self) -> Vec<error::ValidationWarning> {
+        let mut warnings = Vec::new();
+        if self.chunking_strategy.is_some() {
+            if let (Some(new_after_n_chars), Some(max_characters)) =
+                (self.new_after_n_chars, self.max_characters)
+            {
+                if new_after_n_chars >= max_characters {
+                    warnings.push(error::ValidationWarning::SoftMaxExceedsHardMax {
+                        new_after_n_chars,
+                        max_characters,
+                    });
+                }
+            }
+        }
+        warnings
+    }
+
+    /// The entries of `languages` that aren't Tesseract language codes or an alias of one (see
+    /// [LanguageCode::normalized](language::LanguageCode::normalized)), in order.
+    ///
+    /// The API skips OCR for languages it doesn't recognise rather than failing, so this is worth
+    /// checking for user supplied languages.
+    pub fn validate_languages(&self) -> Vec<String> {
+        self.languages
+            .iter()
+            .flatten()
+            .filter(|language| language::normalize(language).is_none())
+            .cloned()
+            .collect()
+    }
+
+    /// Replaces aliases in `languages` by their Tesseract language code, and removes the entries
+    /// that aren't known, logging a warning for each.
+    pub fn sanitize_languages(&mut self) {
+        if let Some(languages) = &mut self.languages {
+            *languages = languages
+                .drain(..)
+                .filter_map(|language| match language::normalize(&language) {
+                    Some(code) => Some(code.to_string()),
+                    None => {
+                        tracing::warn!("Removing unknown language code '{language}'");
+                        None
+                    }
+                })
+                .collect();
+        }
+    }
+
+    /// Sets `languages` to `languages`, e.g.
+    /// `PartitionParameters::default().with_languages(&["eng", "fra"])`.
+    pub fn with_languages(self, languages: &[&str]) -> Self {
+        Self {
+            languages: Some(
+                languages
+                    .iter()
+                    .map(|language| language.to_string())
+                    .collect(),
+            ),
+            ..self
+        }
+    }
+
+    /// Appends `language` to `languages`.
+    pub fn add_language(mut self, language: &str) -> Self {
+        self.languages
+            .get_or_insert_with(Vec::new)
+            .push(language.to_string());
+        self
+    }
+
+    /// Compares two sets of parameters, allowing the floating point fields to differ by at most
+    /// `epsilon`. All other fields must be equal.
+    ///
+    /// Useful when parameters have gone through a serialization round-trip where exact float
+    /// equality can't be relied upon.
+    pub fn approximate_eq(&self, other: &Self, epsilon: f64) -> bool {
+        let thresholds_match = match (self.similarity_threshold, other.similarity_threshold) {
+            (Some(a), Some(b)) => (a - b).abs() <= epsilon,
+            (None, None) => true,
+            _ => false,
+        };
+
+        thresholds_match
+            && Self {
+                similarity_threshold: None,
+                ..self.clone()
+            } == Self {
+                similarity_threshold: None,
+                ..other.clone()
+            }
+    }
+}
+
+/// How list parameters such as `languages` are encoded in the multipart form.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ListEncoding {
+    /// A single field holding a JSON array, e.g. `languages=["eng","fra"]`.
+    #[default]
+    JsonArray,
+
+    /// One field per value, e.g. `languages=eng` and `languages=fra`, as some API versions
+    /// require.
+    RepeatedFields,
+}
+
+impl PartitionParameters {
+    /// The `(name, value)` pairs sent to the API, in the encoding it expects. List fields are
+    /// encoded as JSON arrays and unset optional fields are left out.
+    fn fields(&self) -> Vec<(&'static str, String)> {
+        self.fields_with(ListEncoding::JsonArray)
+    }
+
+    /// Like [fields](Self::fields), with list fields encoded according to `list_encoding`.
+    fn fields_with(&self, list_encoding: ListEncoding) -> Vec<(&'static str, String)> {
+        let push_list =
+            |fields: &mut Vec<(&'static str, String)>, name: &'static str, values: &[String]| {
+                match list_encoding {
+                    ListEncoding::JsonArray => {
+                        fields.push((name, serde_json::to_string(values).unwrap()))
+                    }
+                    ListEncoding::RepeatedFields => {
+                        fields.extend(values.iter().map(|value| (name, value.clone())))
+                    }
+                }
+            };
+
+        let mut fields = vec![("coordinates", self.coordinates.to_string())];
+        if let Some(encoding) = &self.encoding {
+            fields.push(("encoding", encoding.clone()));
         }
-        form = form.text(
+        push_list(
+            &mut fields,
             "extract_image_block_types",
-            serde_json::to_string(&value.extract_image_block_types).unwrap(),
+            &self.extract_image_block_types,
         );
-        if let Some(gz_uncompressed_content_type) = value.gz_uncompressed_content_type.clone() {
-            form = form.text("gz_uncompressed_content_type", gz_uncompressed_content_type);
+        if let Some(gz_uncompressed_content_type) = &self.gz_uncompressed_content_type {
+            fields.push((
+                "gz_uncompressed_content_type",
+                gz_uncompressed_content_type.clone(),
+            ));
         }
-        if let Some(hi_res_model_name) = value.hi_res_model_name.clone() {
-            form = form.text("hi_res_model_name", hi_res_model_name);
+        if let Some(hi_res_model_name) = &self.hi_res_model_name {
+            fields.push(("hi_res_model_name", hi_res_model_name.to_string()));
         }
-        form = form.text("include_page_breaks", value.include_page_breaks.to_string());
-        if let Some(languages) = value.languages.clone() {
-            form = form.text("languages", serde_json::to_string(&languages).unwrap());
+        fields.push(("include_page_breaks", self.include_page_breaks.to_string()));
+        if let Some(languages) = &self.languages {
+            push_list(&mut fields, "languages", languages);
         }
-        form = form.text("output_format", value.output_format.clone());
-        form = form.text(
+        fields.push(("output_format", self.output_format.clone()));
+        push_list(
+            &mut fields,
             "skip_infer_table_types",
-            serde_json::to_string(&value.skip_infer_table_types).unwrap(),
+            &self.skip_infer_table_types,
         );
-        if let Some(starting_page_number) = value.starting_page_number {
-            form = form.text("starting_page_number", starting_page_number.to_string());
+        if let Some(starting_page_number) = self.starting_page_number {
+            fields.push(("starting_page_number", starting_page_number.to_string()));
         }
-        form = form.text("strategy", {
-            let s = String::from(
-                serde_json::to_string(&value.strategy)
-                    .expect("Could not convert Strategy enum to string.")
-                    .trim_matches('"'),
-            );
-            s
-        });
-        form = form.text("unique_element_ids", value.unique_element_ids.to_string());
-        form = form.text("xml_keep_tags", value.xml_keep_tags.to_string());
-        if let Some(chunking_strategy) = value
-            .chunking_strategy
-            .as_ref()
-            .map(serde_json::to_string)
-            .transpose()
-            .expect("Could not convert Chunking Strategy enum to string.")
-        {
-            form = form.text(
-                "chunking_strategy",
-                chunking_strategy.trim_matches('"').to_string(),
-            );
+        fields.push(("strategy", enum_str(&self.strategy)));
+        fields.push(("unique_element_ids", self.unique_element_ids.to_string()));
+        fields.push(("xml_keep_tags", self.xml_keep_tags.to_string()));
+        fields.push(("split_pdf_page", self.split_pdf_page.to_string()));
+        if let Some(split_pdf_concurrency_level) = self.split_pdf_concurrency_level {
+            fields.push((
+                "split_pdf_concurrency_level",
+                split_pdf_concurrency_level.to_string(),
+            ));
+        }
+        if let Some(chunking_strategy) = &self.chunking_strategy {
+            fields.push(("chunking_strategy", enum_str(chunking_strategy)));
         }
-        if let Some(combine_under_n_chars) = value.combine_under_n_chars {
-            form = form.text("combine_under_n_chars", combine_under_n_chars.to_string());
+        if let Some(combine_under_n_chars) = self.combine_under_n_chars {
+            fields.push(("combine_under_n_chars", combine_under_n_chars.to_string()));
         }
-        form = form.text(
+        fields.push((
             "include_orig_elements",
-            value.include_orig_elements.to_string(),
-        );
-        if let Some(max_characters) = value.max_characters {
-            form = form.text("max_characters", max_characters.to_string());
+            self.include_orig_elements.to_string(),
+        ));
+        if let Some(max_characters) = self.max_characters {
+            fields.push(("max_characters", max_characters.to_string()));
         }
-        form = form.text("multipage_sections", value.multipage_sections.to_string());
-        if let Some(new_after_n_chars) = value.new_after_n_chars {
-            form = form.text("new_after_n_chars", new_after_n_chars.to_string());
+        fields.push(("multipage_sections", self.multipage_sections.to_string()));
+        if let Some(new_after_n_chars) = self.new_after_n_chars {
+            fields.push(("new_after_n_chars", new_after_n_chars.to_string()));
         }
-        form = form.text("overlap", value.overlap.to_string());
-        form = form.text("overlap_all", value.overlap_all.to_string());
-        form
+        fields.push(("overlap", self.overlap_chars.to_string()));
+        fields.push(("overlap_all", self.overlap_all.to_string()));
+        if let Some(similarity_threshold) = self.similarity_threshold {
+            fields.push(("similarity_threshold", similarity_threshold.to_string()));
+        }
+        fields
+    }
+
+    /// Builds the multipart form sent to the API, without the file part. Unlike the `From`
+    /// conversion this keeps the parameters, e.g. to log them after sending the request.
+    pub fn to_form(&self) -> Form {
+        self.to_form_with(ListEncoding::default())
+    }
+
+    /// Like [to_form](Self::to_form), with list parameters encoded according to
+    /// `list_encoding`.
+    pub fn to_form_with(&self, list_encoding: ListEncoding) -> Form {
+        self.fields_with(list_encoding)
+            .into_iter()
+            .fold(Form::new(), |form, (name, value)| form.text(name, value))
+    }
+
+    /// Encodes the parameters as a URL query string, using the same field names and values as
+    /// the multipart form sent to the API.
+    ///
+    /// Handy for logging exactly which parameters a request was made with.
+    pub fn to_query_string(&self) -> String {
+        url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(self.fields())
+            .finish()
+    }
+
+    /// Parses parameters from a query string as produced by
+    /// [to_query_string](Self::to_query_string). Fields that are left out keep their default
+    /// value.
+    pub fn from_query_string(s: &str) -> Result<Self> {
+        let mut params = Self::default();
+        for (key, value) in url::form_urlencoded::parse(s.as_bytes()) {
+            let invalid =
+                || ClientError::QueryStringParseFailed(format!("invalid value for {key}: {value}"));
+            let value = value.as_ref();
+            match key.as_ref() {
+                "coordinates" => params.coordinates = value.parse().map_err(|_| invalid())?,
+                "encoding" => params.encoding = Some(value.to_string()),
+                "extract_image_block_types" => {
+                    params.extract_image_block_types =
+                        serde_json::from_str(value).map_err(|_| invalid())?
+                }
+                "gz_uncompressed_content_type" => {
+                    params.gz_uncompressed_content_type = Some(value.to_string())
+                }
+                "hi_res_model_name" => params.hi_res_model_name = Some(value.into()),
+                "include_page_breaks" => {
+                    params.include_page_breaks = value.parse().map_err(|_| invalid())?
+                }
+                "languages" => {
+                    params.languages = Some(serde_json::from_str(value).map_err(|_| invalid())?)
+                }
+                "output_format" => params.output_format = value.to_string(),
+                "skip_infer_table_types" => {
+                    params.skip_infer_table_types =
+                        serde_json::from_str(value).map_err(|_| invalid())?
+                }
+                "starting_page_number" => {
+                    params.starting_page_number = Some(value.parse().map_err(|_| invalid())?)
+                }
+                "strategy" => params.strategy = parse_enum(value).ok_or_else(invalid)?,
+                "unique_element_ids" => {
+                    params.unique_element_ids = value.parse().map_err(|_| invalid())?
+                }
+                "xml_keep_tags" => params.xml_keep_tags = value.parse().map_err(|_| invalid())?,
+                "split_pdf_page" => params.split_pdf_page = value.parse().map_err(|_| invalid())?,
+                "split_pdf_concurrency_level" => {
+                    params.split_pdf_concurrency_level = Some(value.parse().map_err(|_| invalid())?)
+                }
+                "chunking_strategy" => {
+                    params.chunking_strategy = Some(parse_enum(value).ok_or_else(invalid)?)
+                }
+                "combine_under_n_chars" => {
+                    params.combine_under_n_chars = Some(value.parse().map_err(|_| invalid())?)
+                }
+                "include_orig_elements" => {
+                    params.include_orig_elements = value.parse().map_err(|_| invalid())?
+                }
+                "max_characters" => {
+                    params.max_characters = Some(value.parse().map_err(|_| invalid())?)
+                }
+                "multipage_sections" => {
+                    params.multipage_sections = value.parse().map_err(|_| invalid())?
+                }
+                "new_after_n_chars" => {
+                    params.new_after_n_chars = Some(value.parse().map_err(|_| invalid())?)
+                }
+                "overlap" => params.overlap_chars = value.parse().map_err(|_| invalid())?,
+                "overlap_all" => params.overlap_all = value.parse().map_err(|_| invalid())?,
+                "similarity_threshold" => {
+                    params.similarity_threshold = Some(value.parse().map_err(|_| invalid())?)
+                }
+                _ => {
+                    return Err(ClientError::QueryStringParseFailed(format!(
+                        "unknown parameter: {key}"
+                    )))
+                }
+            }
+        }
+        Ok(params)
+    }
+}
+
+/// The serialized name of a unit enum variant, e.g. `"hi_res"` for [Strategy::HiRes].
+fn enum_str<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => panic!("Could not convert enum to string."),
+    }
+}
+
+fn parse_enum<T: DeserializeOwned>(value: &str) -> Option<T> {
+    serde_json::from_value(serde_json::Value::String(value.to_string())).ok()
+}
+
+impl From<PartitionParameters> for Form {
+    fn from(value: PartitionParameters) -> Self {
+        value.to_form()
     }
 }
 
@@ -215,11 +600,44 @@ pub struct ValidationError {
     pub r#type: String,
 }
 
+/// A successful partition response.
+///
+/// Serializes as the bare element list; the HTTP status and headers are only filled in for
+/// responses received by [UnstructuredClient](crate::UnstructuredClient), and are `0` and empty
+/// otherwise. Dereferences to the elements.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct PartitionSuccess {
+    pub elements: ElementList,
+
+    /// HTTP status code of the response.
+    #[serde(skip)]
+    pub status: u16,
+
+    /// HTTP response headers, e.g. the `x-unstructured-*` quota headers some servers add.
+    #[serde(skip)]
+    pub headers: HeaderMap,
+}
+
+impl Deref for PartitionSuccess {
+    type Target = ElementList;
+
+    fn deref(&self) -> &Self::Target {
+        &self.elements
+    }
+}
+
+impl From<PartitionSuccess> for ElementList {
+    fn from(success: PartitionSuccess) -> Self {
+        success.elements
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum PartitionResponse {
-    /// Successful response; returns a list of elements.
-    Success(ElementList),
+    /// Successful response; returns a list of elements along with the HTTP status and headers.
+    Success(PartitionSuccess),
 
     /// Failed to validate value
     ValidationFailure(ValidationError),
@@ -228,6 +646,20 @@ pub enum PartitionResponse {
     UnknownFailure(serde_json::Value),
 }
 
+impl PartitionResponse {
+    /// Records the HTTP status and headers of a successful response.
+    pub(crate) fn with_http_metadata(self, status: u16, headers: HeaderMap) -> Self {
+        match self {
+            PartitionResponse::Success(success) => PartitionResponse::Success(PartitionSuccess {
+                status,
+                headers,
+                ..success
+            }),
+            response => response,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -245,6 +677,24 @@ mod tests {
         assert_eq!(strategy, ChunkingStrategy::Basic);
     }
 
+    #[test]
+    fn test_parse_chunking_strategy() {
+        for name in CHUNKING_STRATEGIES {
+            let strategy: ChunkingStrategy = name.parse().unwrap();
+            assert_eq!(enum_str(&strategy), *name);
+        }
+
+        let error = "by_chapter".parse::<ChunkingStrategy>().unwrap_err();
+        assert_eq!(
+            error,
+            error::ParameterError::UnknownChunkingStrategy("by_chapter".to_string())
+        );
+        assert_eq!(
+            error.to_string(),
+            r#"Unknown chunking strategy 'by_chapter', expected one of ["basic", "by_page", "by_similarity", "by_title"]"#
+        );
+    }
+
     #[test]
     fn test_deserialize_strategy() {
         let json = r#""auto""#;
@@ -286,13 +736,368 @@ mod tests {
             "similarity_threshold": null
         }"#;
         let params: PartitionParameters = serde_json::from_str(json).unwrap();
-        assert_eq!(params.coordinates, true);
+        assert!(params.coordinates);
         assert_eq!(params.encoding.unwrap(), "utf-8");
-        assert_eq!(params.include_page_breaks, true);
+        assert!(params.include_page_breaks);
         assert_eq!(params.output_format, "application/json".to_string());
-        assert_eq!(params.include_orig_elements, true);
-        assert_eq!(params.multipage_sections, true);
-        assert_eq!(params.overlap, 0);
-        assert_eq!(params.overlap_all, false);
+        assert!(params.include_orig_elements);
+        assert!(params.multipage_sections);
+        assert_eq!(params.overlap_chars, 0);
+        assert!(!params.overlap_all);
+        // Parameters serialized before `split_pdf_page` was added still deserialize.
+        assert!(!params.split_pdf_page);
+        assert_eq!(params.split_pdf_concurrency_level, None);
+    }
+
+    /// The parameter names of the `/general/v0/general` endpoint, as documented by Unstructured.
+    const API_PARAMETER_NAMES: &[&str] = &[
+        "chunking_strategy",
+        "combine_under_n_chars",
+        "coordinates",
+        "encoding",
+        "extract_image_block_types",
+        "gz_uncompressed_content_type",
+        "hi_res_model_name",
+        "include_orig_elements",
+        "include_page_breaks",
+        "languages",
+        "max_characters",
+        "multipage_sections",
+        "new_after_n_chars",
+        "output_format",
+        "overlap",
+        "overlap_all",
+        "similarity_threshold",
+        "skip_infer_table_types",
+        "split_pdf_concurrency_level",
+        "split_pdf_page",
+        "starting_page_number",
+        "strategy",
+        "unique_element_ids",
+        "xml_keep_tags",
+    ];
+
+    #[test]
+    fn test_serialized_keys_match_api() {
+        let value = serde_json::to_value(PartitionParameters::default()).unwrap();
+        let mut keys: Vec<&str> = value
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(keys, API_PARAMETER_NAMES);
+
+        // The form sent to the API must use the same names when every field is set.
+        let params = PartitionParameters {
+            gz_uncompressed_content_type: Some("text/plain".to_string()),
+            hi_res_model_name: Some(HiResModel::Yolox),
+            languages: Some(vec!["eng".to_string()]),
+            starting_page_number: Some(1),
+            split_pdf_concurrency_level: Some(4),
+            chunking_strategy: Some(ChunkingStrategy::ByTitle),
+            combine_under_n_chars: Some(100),
+            max_characters: Some(500),
+            new_after_n_chars: Some(400),
+            similarity_threshold: Some(0.5),
+            ..PartitionParameters::default()
+        };
+        let mut names: Vec<&str> = params.fields().into_iter().map(|(name, _)| name).collect();
+        names.sort_unstable();
+        assert_eq!(names, API_PARAMETER_NAMES);
+    }
+
+    #[test]
+    fn test_validate_default() {
+        assert_eq!(PartitionParameters::default().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_image_block_types() {
+        let params = PartitionParameters {
+            extract_image_block_types: vec!["Image".to_string(), "Table".to_string()],
+            ..PartitionParameters::default()
+        };
+        assert_eq!(params.validate(), Ok(()));
+
+        let params = PartitionParameters {
+            extract_image_block_types: vec!["Image".to_string(), "foo".to_string()],
+            ..PartitionParameters::default()
+        };
+        assert_eq!(
+            params.validate(),
+            Err(error::ParameterError::UnknownImageBlockType(
+                "foo".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_validate_skip_infer_table_types() {
+        let params = PartitionParameters {
+            skip_infer_table_types: vec!["pdf".to_string(), "xlsx".to_string()],
+            ..PartitionParameters::default()
+        };
+        assert_eq!(params.validate(), Ok(()));
+
+        let params = PartitionParameters {
+            skip_infer_table_types: vec!["spreadsheet".to_string()],
+            ..PartitionParameters::default()
+        };
+        assert_eq!(
+            params.validate(),
+            Err(error::ParameterError::UnknownDocumentType(
+                "spreadsheet".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_hi_res_model() {
+        for name in HI_RES_MODELS {
+            let model = HiResModel::from(*name);
+            assert!(model.is_known());
+            assert_eq!(model.as_str(), *name);
+        }
+        assert_eq!(HiResModel::from("yolox"), HiResModel::Yolox);
+        let custom = HiResModel::from("chipper");
+        assert_eq!(custom, HiResModel::Custom("chipper".to_string()));
+        assert!(!custom.is_known());
+
+        assert_eq!(
+            serde_json::to_value(HiResModel::Detectron2Onnx).unwrap(),
+            serde_json::json!("detectron2_onnx")
+        );
+        let parsed: HiResModel = serde_json::from_value(serde_json::json!("chipper")).unwrap();
+        assert_eq!(parsed, custom);
+
+        let params = PartitionParameters::from_query_string("hi_res_model_name=yolox").unwrap();
+        assert_eq!(params.hi_res_model_name, Some(HiResModel::Yolox));
+        assert!(params
+            .fields()
+            .contains(&("hi_res_model_name", "yolox".to_string())));
+    }
+
+    #[test]
+    fn test_validate_hi_res_model() {
+        let params = PartitionParameters {
+            hi_res_model_name: Some(HiResModel::Custom("chipper".to_string())),
+            ..PartitionParameters::default()
+        };
+        assert_eq!(params.validate(), Ok(()));
+
+        let params = PartitionParameters {
+            hi_res_model_name: Some(HiResModel::Custom(" ".to_string())),
+            ..PartitionParameters::default()
+        };
+        assert_eq!(
+            params.validate(),
+            Err(error::ParameterError::EmptyHiResModelName)
+        );
+    }
+
+    #[test]
+    fn test_warnings() {
+        let params = PartitionParameters {
+            chunking_strategy: Some(ChunkingStrategy::ByTitle),
+            max_characters: Some(500),
+            new_after_n_chars: Some(400),
+            ..PartitionParameters::default()
+        };
+        assert!(params.warnings().is_empty());
+
+        let params = PartitionParameters {
+            new_after_n_chars: Some(500),
+            ..params
+        };
+        assert_eq!(
+            params.warnings(),
+            vec![error::ValidationWarning::SoftMaxExceedsHardMax {
+                new_after_n_chars: 500,
+                max_characters: 500
+            }]
+        );
+        // A warning doesn't fail validation.
+        assert_eq!(params.validate(), Ok(()));
+
+        // Without chunking, the limits aren't used.
+        let params = PartitionParameters {
+            chunking_strategy: None,
+            ..params
+        };
+        assert!(params.warnings().is_empty());
+        assert!(PartitionParameters::default().warnings().is_empty());
+    }
+
+    #[test]
+    fn test_partition_parameters_round_trip_eq() {
+        let params = PartitionParameters {
+            chunking_strategy: Some(ChunkingStrategy::ByTitle),
+            similarity_threshold: Some(0.5),
+            ..PartitionParameters::default()
+        };
+        let json = serde_json::to_string(&params).unwrap();
+        let round_tripped: PartitionParameters = serde_json::from_str(&json).unwrap();
+        assert_eq!(params, round_tripped);
+        assert_ne!(params, PartitionParameters::default());
+    }
+
+    #[test]
+    fn test_partition_parameters_approximate_eq() {
+        let a = PartitionParameters {
+            similarity_threshold: Some(0.3),
+            ..PartitionParameters::default()
+        };
+        let b = PartitionParameters {
+            similarity_threshold: Some(0.1 + 0.2),
+            ..PartitionParameters::default()
+        };
+        assert!(a.approximate_eq(&b, 1e-9));
+        assert!(!a.approximate_eq(&PartitionParameters::default(), 1e-9));
+
+        let c = PartitionParameters {
+            overlap_chars: 10,
+            ..b.clone()
+        };
+        assert!(!a.approximate_eq(&c, 1e-9));
+    }
+
+    #[test]
+    fn test_query_string_round_trip() {
+        let params = PartitionParameters {
+            coordinates: true,
+            extract_image_block_types: vec!["Image".to_string()],
+            languages: Some(vec!["eng".to_string(), "nld".to_string()]),
+            strategy: Strategy::HiRes,
+            chunking_strategy: Some(ChunkingStrategy::BySimilarity),
+            max_characters: Some(1000),
+            overlap_chars: 20,
+            similarity_threshold: Some(0.25),
+            ..PartitionParameters::default()
+        };
+        let query = params.to_query_string();
+        assert!(query.contains("strategy=hi_res"));
+        assert!(query.contains("languages=%5B%22eng%22%2C%22nld%22%5D"));
+        assert!(query.contains("overlap=20"));
+
+        let parsed = PartitionParameters::from_query_string(&query).unwrap();
+        assert_eq!(parsed, params);
+    }
+
+    #[test]
+    fn test_from_query_string_defaults() {
+        let params = PartitionParameters::from_query_string("strategy=fast").unwrap();
+        assert_eq!(
+            params,
+            PartitionParameters {
+                strategy: Strategy::Fast,
+                ..PartitionParameters::default()
+            }
+        );
+        assert_eq!(
+            PartitionParameters::from_query_string("").unwrap(),
+            PartitionParameters::default()
+        );
+    }
+
+    #[test]
+    fn test_from_query_string_invalid() {
+        for query in [
+            "coordinates=yes",
+            "strategy=best",
+            "max_characters=many",
+            "foo=1",
+        ] {
+            assert!(matches!(
+                PartitionParameters::from_query_string(query),
+                Err(ClientError::QueryStringParseFailed(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_fields_with_repeated_lists() {
+        let params = PartitionParameters {
+            extract_image_block_types: vec!["Image".to_string(), "Table".to_string()],
+            languages: Some(vec!["eng".to_string()]),
+            skip_infer_table_types: vec![],
+            ..PartitionParameters::default()
+        };
+        let values = |fields: &[(&str, String)], name: &str| -> Vec<String> {
+            fields
+                .iter()
+                .filter(|(field, _)| *field == name)
+                .map(|(_, value)| value.clone())
+                .collect()
+        };
+
+        let json = params.fields_with(ListEncoding::JsonArray);
+        assert_eq!(
+            values(&json, "extract_image_block_types"),
+            vec![r#"["Image","Table"]"#]
+        );
+        assert_eq!(values(&json, "languages"), vec![r#"["eng"]"#]);
+        assert_eq!(values(&json, "skip_infer_table_types"), vec!["[]"]);
+        assert_eq!(json, params.fields());
+
+        let repeated = params.fields_with(ListEncoding::RepeatedFields);
+        assert_eq!(
+            values(&repeated, "extract_image_block_types"),
+            vec!["Image", "Table"]
+        );
+        assert_eq!(values(&repeated, "languages"), vec!["eng"]);
+        assert!(values(&repeated, "skip_infer_table_types").is_empty());
+        assert_eq!(repeated.len(), json.len());
+    }
+
+    #[test]
+    fn test_with_languages() {
+        let params = PartitionParameters::default().with_languages(&["eng", "fra"]);
+        assert_eq!(
+            params.languages,
+            Some(vec!["eng".to_string(), "fra".to_string()])
+        );
+
+        let params = params.add_language("deu").with_languages(&["nld"]);
+        assert_eq!(params.languages, Some(vec!["nld".to_string()]));
+
+        let params = PartitionParameters::default()
+            .add_language("eng")
+            .add_language("chi_sim");
+        assert_eq!(
+            params.languages,
+            Some(vec!["eng".to_string(), "chi_sim".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_validate_languages() {
+        assert!(PartitionParameters::default()
+            .validate_languages()
+            .is_empty());
+
+        let mut params = PartitionParameters {
+            languages: Some(vec![
+                "eng".to_string(),
+                "english".to_string(),
+                "chi_sim".to_string(),
+                "fr".to_string(),
+                "xx".to_string(),
+            ]),
+            ..PartitionParameters::default()
+        };
+        assert_eq!(params.validate_languages(), vec!["english", "xx"]);
+
+        params.sanitize_languages();
+        assert_eq!(
+            params.languages,
+            Some(vec![
+                "eng".to_string(),
+                "chi_sim".to_string(),
+                "fra".to_string()
+            ])
+        );
+        assert!(params.validate_languages().is_empty());
     }
 }
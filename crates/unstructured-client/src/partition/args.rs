@@ -0,0 +1,282 @@
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+
+use crate::error::{ClientError, Result};
+use crate::partition::{parse_enum, ChunkingStrategy, PartitionParameters};
+
+impl PartitionParameters {
+    /// Adds an argument for each partition parameter to `cmd`, e.g. `--strategy` or
+    /// `--chunking-strategy`. The argument IDs are the field names.
+    ///
+    /// The parameters can then be read back with
+    /// [from_arg_matches](PartitionParameters::from_arg_matches).
+    pub fn augment_args(cmd: Command) -> Command {
+        cmd.args([
+            flag("coordinates", "If `True`, return coordinates for each element extracted via OCR. Default: `False`"),
+            option("encoding", "The encoding method used to decode the text input. Default: utf-8"),
+            list("extract_image_block_types", "The types of elements to extract, for use in extracting image blocks as base64 encoded data stored in metadata fields. Default: []"),
+            option("gz_uncompressed_content_type", "If file is gzipped, use this content type after unzipping"),
+            option("hi_res_model_name", "The name of the inference model used when strategy is hi_res"),
+            flag("include_page_breaks", "If true, the output will include page breaks if the filetype supports it. Default: false"),
+            list("languages", "The languages present in the document, for use in partitioning and/or OCR. See the Tesseract documentation for a full list of languages. Default: []"),
+            option("output_format", "The format of the response. Supported formats are application/json (or json) and text/csv (or csv). Default: application/json")
+                .default_value("application/json"),
+            list("skip_infer_table_types", "The document types that you want to skip table extraction with. Default: []"),
+            option("starting_page_number", "When PDF is split into pages before sending it into the API, providing this information will allow the page number to be assigned correctly. Introduced in 1.0.27")
+                .value_parser(value_parser!(i32)),
+            option("strategy", "The strategy to use for partitioning PDF/image. Options are fast, hi_res, auto. Default: auto")
+                .default_value("auto"),
+            flag("unique_element_ids", "When `True`, assign UUIDs to element IDs, which guarantees their uniqueness (useful when using them as primary keys in database). Otherwise a SHA-256 of element text is used. Default: `False`"),
+            flag("xml_keep_tags", "If `True`, will retain the XML tags in the output. Otherwise it will simply extract the text from within the tags. Only applies to XML documents. Default: false"),
+            flag("split_pdf_page", "When `True`, the server splits PDFs into pages and partitions them in parallel. Only supported by newer API versions. Default: false"),
+            option("split_pdf_concurrency_level", "The number of pages partitioned in parallel when split_pdf_page is set")
+                .value_parser(value_parser!(u32)),
+            option("chunking_strategy", "Use one of the supported strategies to chunk the returned elements after partitioning. When 'chunking_strategy' is not specified, no chunking is performed and any other chunking parameters provided are ignored. Supported strategies: 'basic', 'by_page', 'by_similarity', or 'by_title'"),
+            option("combine_under_n_chars", "If chunking strategy is set, combine elements until a section reaches a length of n chars. Default: 500")
+                .value_parser(value_parser!(i32)),
+            toggle("include_orig_elements", "When a chunking strategy is specified, each returned chunk will include the elements consolidated to form that chunk as `.metadata.orig_elements`. Default: true"),
+            option("max_characters", "If chunking strategy is set, cut off new sections after reaching a length of n chars (hard max). Default: 500")
+                .value_parser(value_parser!(i32)),
+            toggle("multipage_sections", "If chunking strategy is set, determines if sections can span multiple sections. Default: true"),
+            option("new_after_n_chars", "If chunking strategy is set, cut off new sections after reaching a length of n chars (soft max). Default: 1500")
+                .value_parser(value_parser!(i32)),
+            option("overlap", "Specifies the length in characters of a string ('tail') to be drawn from each chunk and prefixed to the next chunk as a context-preserving mechanism. By default, this only applies to split-chunks where an oversized element is divided into multiple chunks by text-splitting. Default 0")
+                .value_parser(value_parser!(i32))
+                .default_value("0"),
+            flag("overlap_all", "When `True`, apply overlap between 'normal' chunks formed from whole elements and not subject to text-splitting. Use this with caution as it entails a certain level of 'pollution' of otherwise clean semantic chunk boundaries. Default false"),
+            option("similarity_threshold", "A value between 0.0 and 1.0 describing the minimum similarity two elements must have to be included in the same chunk. Note that similar elements may be separated to meet chunk-size criteria; this value can only guarantee that two elements with similarity below the threshold will appear in separate chunks")
+                .value_parser(value_parser!(f64)),
+        ])
+    }
+
+    /// Reads the partition parameters from arguments parsed by a command set up with
+    /// [augment_args](PartitionParameters::augment_args).
+    pub fn from_arg_matches(matches: &ArgMatches) -> Result<Self> {
+        let strategy = get::<String>(matches, "strategy")?
+            .map(|s| parse_enum(&s).ok_or_else(|| invalid("strategy", &s)))
+            .transpose()?;
+        let chunking_strategy = get::<String>(matches, "chunking_strategy")?
+            .map(|s| s.parse::<ChunkingStrategy>())
+            .transpose()?;
+
+        let defaults = Self::default();
+        Ok(PartitionParameters {
+            coordinates: get(matches, "coordinates")?.unwrap_or(defaults.coordinates),
+            encoding: get(matches, "encoding")?,
+            extract_image_block_types: get_many(matches, "extract_image_block_types")?,
+            gz_uncompressed_content_type: get(matches, "gz_uncompressed_content_type")?,
+            hi_res_model_name: get::<String>(matches, "hi_res_model_name")?.map(Into::into),
+            include_page_breaks: get(matches, "include_page_breaks")?
+                .unwrap_or(defaults.include_page_breaks),
+            languages: Some(get_many(matches, "languages")?),
+            output_format: get::<String>(matches, "output_format")?
+                .map(|format| expand_output_format(&format))
+                .unwrap_or(defaults.output_format),
+            skip_infer_table_types: get_many(matches, "skip_infer_table_types")?,
+            starting_page_number: get(matches, "starting_page_number")?,
+            strategy: strategy.unwrap_or(defaults.strategy),
+            unique_element_ids: get(matches, "unique_element_ids")?
+                .unwrap_or(defaults.unique_element_ids),
+            xml_keep_tags: get(matches, "xml_keep_tags")?.unwrap_or(defaults.xml_keep_tags),
+            split_pdf_page: get(matches, "split_pdf_page")?.unwrap_or(defaults.split_pdf_page),
+            split_pdf_concurrency_level: get(matches, "split_pdf_concurrency_level")?,
+            chunking_strategy,
+            combine_under_n_chars: get(matches, "combine_under_n_chars")?,
+            include_orig_elements: get(matches, "include_orig_elements")?
+                .unwrap_or(defaults.include_orig_elements),
+            max_characters: get(matches, "max_characters")?,
+            multipage_sections: get(matches, "multipage_sections")?
+                .unwrap_or(defaults.multipage_sections),
+            new_after_n_chars: get(matches, "new_after_n_chars")?,
+            overlap_chars: get(matches, "overlap")?.unwrap_or(defaults.overlap_chars),
+            overlap_all: get(matches, "overlap_all")?.unwrap_or(defaults.overlap_all),
+            similarity_threshold: get(matches, "similarity_threshold")?,
+        })
+    }
+}
+
+fn long_name(id: &'static str) -> String {
+    id.replace('_', "-")
+}
+
+fn flag(id: &'static str, help: &'static str) -> Arg {
+    Arg::new(id)
+        .long(long_name(id))
+        .help(help)
+        .action(ArgAction::SetTrue)
+}
+
+/// A boolean that defaults to true, so it takes a value to turn it off, e.g.
+/// `--multipage-sections=false`. Without a value it is set to true.
+fn toggle(id: &'static str, help: &'static str) -> Arg {
+    Arg::new(id)
+        .long(long_name(id))
+        .help(help)
+        .action(ArgAction::Set)
+        .value_name("BOOL")
+        .value_parser(value_parser!(bool))
+        .num_args(0..=1)
+        .require_equals(true)
+        .default_value("true")
+        .default_missing_value("true")
+}
+
+fn option(id: &'static str, help: &'static str) -> Arg {
+    Arg::new(id)
+        .long(long_name(id))
+        .help(help)
+        .action(ArgAction::Set)
+        .value_name(id.to_uppercase())
+        .value_parser(value_parser!(String))
+}
+
+fn list(id: &'static str, help: &'static str) -> Arg {
+    Arg::new(id)
+        .long(long_name(id))
+        .help(help)
+        .action(ArgAction::Append)
+        .value_name(id.to_uppercase())
+        .value_parser(value_parser!(String))
+}
+
+fn get<T: Clone + Send + Sync + 'static>(matches: &ArgMatches, id: &str) -> Result<Option<T>> {
+    matches
+        .try_get_one::<T>(id)
+        .map(Option::<&T>::cloned)
+        .map_err(|e| ClientError::ArgumentParseFailed(format!("{id}: {e}")))
+}
+
+fn get_many(matches: &ArgMatches, id: &str) -> Result<Vec<String>> {
+    matches
+        .try_get_many::<String>(id)
+        .map(|values| values.into_iter().flatten().cloned().collect())
+        .map_err(|e| ClientError::ArgumentParseFailed(format!("{id}: {e}")))
+}
+
+/// Expands the `json` and `csv` shorthands to the MIME types expected by the API.
+fn expand_output_format(format: &str) -> String {
+    match format.to_ascii_lowercase().as_str() {
+        "json" => "application/json".to_string(),
+        "csv" => "text/csv".to_string(),
+        _ => format.to_string(),
+    }
+}
+
+fn invalid(id: &str, value: &str) -> ClientError {
+    ClientError::ArgumentParseFailed(format!("invalid value for {id}: {value}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ParameterError;
+    use crate::partition::Strategy;
+
+    fn parse(args: &[&str]) -> Result<PartitionParameters> {
+        let cmd = PartitionParameters::augment_args(Command::new("test"));
+        let matches = cmd
+            .try_get_matches_from(std::iter::once("test").chain(args.iter().copied()))
+            .unwrap();
+        PartitionParameters::from_arg_matches(&matches)
+    }
+
+    #[test]
+    fn test_from_arg_matches_defaults() {
+        let params = parse(&[]).unwrap();
+        assert_eq!(
+            params,
+            PartitionParameters {
+                encoding: None,
+                languages: Some(vec![]),
+                ..PartitionParameters::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches() {
+        let params = parse(&[
+            "--coordinates",
+            "--languages",
+            "eng",
+            "--languages",
+            "nld",
+            "--strategy",
+            "hi_res",
+            "--chunking-strategy",
+            "by_title",
+            "--max-characters",
+            "1000",
+            "--overlap",
+            "20",
+            "--similarity-threshold",
+            "0.5",
+            "--split-pdf-page",
+            "--split-pdf-concurrency-level",
+            "8",
+        ])
+        .unwrap();
+
+        assert!(params.coordinates);
+        assert_eq!(
+            params.languages,
+            Some(vec!["eng".to_string(), "nld".to_string()])
+        );
+        assert_eq!(params.strategy, Strategy::HiRes);
+        assert_eq!(params.chunking_strategy, Some(ChunkingStrategy::ByTitle));
+        assert_eq!(params.max_characters, Some(1000));
+        assert_eq!(params.overlap_chars, 20);
+        assert_eq!(params.similarity_threshold, Some(0.5));
+        assert!(params.split_pdf_page);
+        assert_eq!(params.split_pdf_concurrency_level, Some(8));
+    }
+
+    #[test]
+    fn test_from_arg_matches_toggles() {
+        let params = parse(&[]).unwrap();
+        assert!(params.include_orig_elements);
+        assert!(params.multipage_sections);
+
+        let params = parse(&["--include-orig-elements", "--multipage-sections"]).unwrap();
+        assert!(params.include_orig_elements);
+        assert!(params.multipage_sections);
+
+        let params = parse(&[
+            "--include-orig-elements=false",
+            "--multipage-sections=false",
+        ])
+        .unwrap();
+        assert!(!params.include_orig_elements);
+        assert!(!params.multipage_sections);
+    }
+
+    #[test]
+    fn test_from_arg_matches_output_format() {
+        for (input, expected) in [
+            ("json", "application/json"),
+            ("CSV", "text/csv"),
+            ("text/csv", "text/csv"),
+            ("application/json", "application/json"),
+        ] {
+            let params = parse(&["--output-format", input]).unwrap();
+            assert_eq!(params.output_format, expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid_strategy() {
+        assert!(matches!(
+            parse(&["--strategy", "best"]),
+            Err(ClientError::ArgumentParseFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid_chunking_strategy() {
+        assert!(matches!(
+            parse(&["--chunking-strategy", "by_chapter"]),
+            Err(ClientError::InvalidParameters(
+                ParameterError::UnknownChunkingStrategy(_)
+            ))
+        ));
+    }
+}